@@ -148,18 +148,41 @@ pub struct PendingCommand {
     pub created_at: String,
 }
 
+/// Load a persisted queue file, starting empty if it is missing or corrupt.
+fn load_queue_file<T: serde::de::DeserializeOwned + Default>(path: &std::path::Path) -> T {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Best-effort save; a failed write must never block queueing.
+fn save_queue_file<T: Serialize>(path: &std::path::Path, value: &T) {
+    if let Ok(text) = serde_json::to_string_pretty(value) {
+        let _ = crate::config_io::write_text(path, &text);
+    }
+}
+
 #[derive(Clone)]
 pub struct CommandQueue {
     commands: Arc<Mutex<Vec<PendingCommand>>>,
+    path: std::path::PathBuf,
 }
 
 impl CommandQueue {
+    /// Restores any queue persisted in `~/.clawpal/command-queue.json`.
     pub fn new() -> Self {
+        let path = resolve_paths().clawpal_dir.join("command-queue.json");
         Self {
-            commands: Arc::new(Mutex::new(Vec::new())),
+            commands: Arc::new(Mutex::new(load_queue_file(&path))),
+            path,
         }
     }
 
+    fn persist(&self, cmds: &[PendingCommand]) {
+        save_queue_file(&self.path, &cmds);
+    }
+
     pub fn enqueue(&self, label: String, command: Vec<String>) -> PendingCommand {
         let cmd = PendingCommand {
             id: Uuid::new_v4().to_string(),
@@ -167,7 +190,9 @@ impl CommandQueue {
             command,
             created_at: chrono::Utc::now().to_rfc3339(),
         };
-        self.commands.lock().unwrap().push(cmd.clone());
+        let mut cmds = self.commands.lock().unwrap();
+        cmds.push(cmd.clone());
+        self.persist(&cmds);
         cmd
     }
 
@@ -175,6 +200,7 @@ impl CommandQueue {
         let mut cmds = self.commands.lock().unwrap();
         let before = cmds.len();
         cmds.retain(|c| c.id != id);
+        self.persist(&cmds);
         cmds.len() < before
     }
 
@@ -183,7 +209,9 @@ impl CommandQueue {
     }
 
     pub fn clear(&self) {
-        self.commands.lock().unwrap().clear();
+        let mut cmds = self.commands.lock().unwrap();
+        cmds.clear();
+        self.persist(&cmds);
     }
 
     pub fn is_empty(&self) -> bool {
@@ -193,6 +221,34 @@ impl CommandQueue {
     pub fn len(&self) -> usize {
         self.commands.lock().unwrap().len()
     }
+
+    pub fn move_command(&self, index: usize, new_index: usize) -> Result<Vec<PendingCommand>, String> {
+        let mut cmds = self.commands.lock().unwrap();
+        move_pending_command(&mut cmds, index, new_index)?;
+        self.persist(&cmds);
+        Ok(cmds.clone())
+    }
+
+    pub fn dedupe(&self) -> Vec<PendingCommand> {
+        let mut cmds = self.commands.lock().unwrap();
+        dedupe_adjacent_commands(&mut cmds);
+        self.persist(&cmds);
+        cmds.clone()
+    }
+}
+
+fn move_pending_command(cmds: &mut Vec<PendingCommand>, index: usize, new_index: usize) -> Result<(), String> {
+    if index >= cmds.len() || new_index >= cmds.len() {
+        return Err(format!("queue index out of range (queue has {} commands)", cmds.len()));
+    }
+    let cmd = cmds.remove(index);
+    cmds.insert(new_index, cmd);
+    Ok(())
+}
+
+/// Collapse runs of adjacent entries that would run the same command.
+fn dedupe_adjacent_commands(cmds: &mut Vec<PendingCommand>) {
+    cmds.dedup_by(|next, prev| next.command == prev.command);
 }
 
 impl Default for CommandQueue {
@@ -247,6 +303,22 @@ pub fn queued_commands_count(
     Ok(queue.len())
 }
 
+#[tauri::command]
+pub fn move_queued_command(
+    queue: tauri::State<CommandQueue>,
+    index: usize,
+    new_index: usize,
+) -> Result<Vec<PendingCommand>, String> {
+    queue.move_command(index, new_index)
+}
+
+#[tauri::command]
+pub fn dedupe_queued_commands(
+    queue: tauri::State<CommandQueue>,
+) -> Result<Vec<PendingCommand>, String> {
+    Ok(queue.dedupe())
+}
+
 // ---------------------------------------------------------------------------
 // Preview — sandbox execution with OPENCLAW_HOME
 // ---------------------------------------------------------------------------
@@ -258,6 +330,61 @@ pub struct PreviewQueueResult {
     pub config_before: String,
     pub config_after: String,
     pub errors: Vec<String>,
+    /// Net config diff of the whole queue (`format_diff` of before/after).
+    #[serde(default)]
+    pub diff: String,
+    /// What each queued command did in the sandbox, in queue order.
+    #[serde(default)]
+    pub effects: Vec<QueuedCommandEffect>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedCommandEffect {
+    pub id: String,
+    pub label: String,
+    /// Config paths this command changed; empty for non-config commands.
+    pub changed_paths: Vec<String>,
+    /// Config diff for config-mutating commands, otherwise the command text.
+    pub detail: String,
+}
+
+fn parse_config_text(text: &str) -> Option<Value> {
+    json5::from_str(text).ok()
+}
+
+/// Describe one sandboxed command by comparing the config text around it.
+fn queued_command_effect(cmd: &PendingCommand, before: &str, after: &str) -> QueuedCommandEffect {
+    let changed = match (parse_config_text(before), parse_config_text(after)) {
+        (Some(b), Some(a)) if b != a => Some((crate::recipe::collect_changed_config_paths(&b, &a), crate::recipe::format_diff(&b, &a))),
+        _ => None,
+    };
+    match changed {
+        Some((changes, diff)) => QueuedCommandEffect {
+            id: cmd.id.clone(),
+            label: cmd.label.clone(),
+            changed_paths: changes,
+            detail: diff,
+        },
+        None => QueuedCommandEffect {
+            id: cmd.id.clone(),
+            label: cmd.label.clone(),
+            changed_paths: Vec::new(),
+            detail: if matches!(cmd.command.first().map(String::as_str), Some("__config_write__") | Some("__rollback__")) {
+                cmd.label.clone()
+            } else {
+                cmd.command.join(" ")
+            },
+        },
+    }
+}
+
+/// Net diff between two (normalized) config texts.
+fn queue_net_diff(before: &str, after: &str) -> String {
+    match (parse_config_text(before), parse_config_text(after)) {
+        (Some(b), Some(a)) => crate::recipe::format_diff(&b, &a),
+        _ => String::new(),
+    }
 }
 
 #[tauri::command]
@@ -305,8 +432,21 @@ pub async fn preview_queued_commands(
             sandbox_root.to_string_lossy().to_string(),
         );
 
+        // Replace sandbox paths with real paths so the diff doesn't show sandbox artifacts.
+        let sandbox_prefix = sandbox_root.to_string_lossy().to_string();
+        let real_home = paths.openclaw_dir.parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let read_preview = |fallback: &str| {
+            crate::config_io::read_text(&preview_config)
+                .unwrap_or_else(|_| fallback.to_string())
+                .replace(&sandbox_prefix, &real_home)
+        };
+
         // Execute each command in sandbox
         let mut errors = Vec::new();
+        let mut effects = Vec::new();
+        let mut step_before = config_before.clone();
         for cmd in &commands {
             if matches!(cmd.command.first().map(|s| s.as_str()), Some("__config_write__") | Some("__rollback__")) {
                 // Internal command: write config content directly
@@ -315,6 +455,9 @@ pub async fn preview_queued_commands(
                         errors.push(format!("{}: {}", cmd.label, e));
                     }
                 }
+                let step_after = read_preview(&step_before);
+                effects.push(queued_command_effect(cmd, &step_before, &step_after));
+                step_before = step_after;
                 continue;
             }
             let args: Vec<&str> = cmd.command.iter().skip(1).map(|s| s.as_str()).collect();
@@ -334,17 +477,13 @@ pub async fn preview_queued_commands(
                 }
                 _ => {}
             }
+            let step_after = read_preview(&step_before);
+            effects.push(queued_command_effect(cmd, &step_before, &step_after));
+            step_before = step_after;
         }
 
         // Always read result config from sandbox (commands may have partially succeeded)
-        // Replace sandbox paths with real paths so the diff doesn't show sandbox artifacts.
-        let sandbox_prefix = sandbox_root.to_string_lossy().to_string();
-        let real_home = paths.openclaw_dir.parent()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_default();
-        let config_after_raw = crate::config_io::read_text(&preview_config)
-            .unwrap_or_else(|_| config_before.clone())
-            .replace(&sandbox_prefix, &real_home);
+        let config_after_raw = read_preview(&config_before);
 
         // Normalize both configs to sorted-key pretty JSON so the diff only
         // shows semantic changes, not key reordering by the CLI.
@@ -376,10 +515,12 @@ pub async fn preview_queued_commands(
         let _ = std::fs::remove_dir_all(paths.clawpal_dir.join("preview"));
 
         Ok(PreviewQueueResult {
+            diff: queue_net_diff(&config_before, &config_after),
             commands,
             config_before,
             config_after,
             errors,
+            effects,
         })
     }).await.map_err(|e| e.to_string())?
 }
@@ -538,15 +679,23 @@ pub async fn apply_queued_commands(
 
 pub struct RemoteCommandQueues {
     queues: Mutex<HashMap<String, Vec<PendingCommand>>>,
+    path: std::path::PathBuf,
 }
 
 impl RemoteCommandQueues {
+    /// Restores per-host queues persisted in `~/.clawpal/remote-command-queues.json`.
     pub fn new() -> Self {
+        let path = resolve_paths().clawpal_dir.join("remote-command-queues.json");
         Self {
-            queues: Mutex::new(HashMap::new()),
+            queues: Mutex::new(load_queue_file(&path)),
+            path,
         }
     }
 
+    fn persist(&self, queues: &HashMap<String, Vec<PendingCommand>>) {
+        save_queue_file(&self.path, queues);
+    }
+
     pub fn enqueue(&self, host_id: &str, label: String, command: Vec<String>) -> PendingCommand {
         let cmd = PendingCommand {
             id: Uuid::new_v4().to_string(),
@@ -554,12 +703,12 @@ impl RemoteCommandQueues {
             command,
             created_at: chrono::Utc::now().to_rfc3339(),
         };
-        self.queues
-            .lock()
-            .unwrap()
+        let mut queues = self.queues.lock().unwrap();
+        queues
             .entry(host_id.to_string())
             .or_default()
             .push(cmd.clone());
+        self.persist(&queues);
         cmd
     }
 
@@ -568,7 +717,9 @@ impl RemoteCommandQueues {
         if let Some(cmds) = queues.get_mut(host_id) {
             let before = cmds.len();
             cmds.retain(|c| c.id != id);
-            return cmds.len() < before;
+            let removed = cmds.len() < before;
+            self.persist(&queues);
+            return removed;
         }
         false
     }
@@ -583,7 +734,9 @@ impl RemoteCommandQueues {
     }
 
     pub fn clear(&self, host_id: &str) {
-        self.queues.lock().unwrap().remove(host_id);
+        let mut queues = self.queues.lock().unwrap();
+        queues.remove(host_id);
+        self.persist(&queues);
     }
 
     pub fn len(&self, host_id: &str) -> usize {
@@ -594,6 +747,29 @@ impl RemoteCommandQueues {
             .map(|v| v.len())
             .unwrap_or(0)
     }
+
+    pub fn move_command(&self, host_id: &str, index: usize, new_index: usize) -> Result<Vec<PendingCommand>, String> {
+        let mut queues = self.queues.lock().unwrap();
+        let cmds = queues
+            .get_mut(host_id)
+            .filter(|cmds| !cmds.is_empty())
+            .ok_or_else(|| format!("No pending commands for host {host_id}"))?;
+        move_pending_command(cmds, index, new_index)?;
+        let updated = cmds.clone();
+        self.persist(&queues);
+        Ok(updated)
+    }
+
+    pub fn dedupe(&self, host_id: &str) -> Vec<PendingCommand> {
+        let mut queues = self.queues.lock().unwrap();
+        let Some(cmds) = queues.get_mut(host_id) else {
+            return Vec::new();
+        };
+        dedupe_adjacent_commands(cmds);
+        let updated = cmds.clone();
+        self.persist(&queues);
+        updated
+    }
 }
 
 impl Default for RemoteCommandQueues {
@@ -653,6 +829,24 @@ pub fn remote_queued_commands_count(
     Ok(queues.len(&host_id))
 }
 
+#[tauri::command]
+pub fn remote_move_queued_command(
+    queues: tauri::State<RemoteCommandQueues>,
+    host_id: String,
+    index: usize,
+    new_index: usize,
+) -> Result<Vec<PendingCommand>, String> {
+    queues.move_command(&host_id, index, new_index)
+}
+
+#[tauri::command]
+pub fn remote_dedupe_queued_commands(
+    queues: tauri::State<RemoteCommandQueues>,
+    host_id: String,
+) -> Result<Vec<PendingCommand>, String> {
+    Ok(queues.dedupe(&host_id))
+}
+
 // ---------------------------------------------------------------------------
 // Remote preview — sandbox execution via SSH
 // ---------------------------------------------------------------------------
@@ -684,9 +878,26 @@ pub async fn remote_preview_queued_commands(
         "cp ~/.openclaw/openclaw.json ~/.clawpal/preview/.openclaw/openclaw.json",
     )).await?;
 
+    // Replace sandbox paths with real paths in preview output.
+    // The sandbox is at ~/.clawpal/preview, real OPENCLAW_HOME is ~.
+    // Resolve ~ on remote to get absolute sandbox prefix.
+    let resolved_home = pool.exec(&host_id, "echo $HOME").await
+        .map(|r| r.stdout.trim().to_string())
+        .unwrap_or_default();
+    let unsandbox = |raw: String| -> String {
+        if !resolved_home.is_empty() {
+            let sandbox_prefix = format!("{}/.clawpal/preview", resolved_home);
+            raw.replace(&sandbox_prefix, &resolved_home)
+        } else {
+            raw
+        }
+    };
+
     // Execute each command in sandbox with OPENCLAW_HOME override
     // OPENCLAW_HOME should point to the parent of .openclaw/ (CLI adds .openclaw/ itself)
     let mut errors = Vec::new();
+    let mut effects = Vec::new();
+    let mut step_before = config_before.clone();
     for cmd in &commands {
         // Handle internal commands (__config_write__, __rollback__) — write config directly
         if matches!(cmd.command.first().map(|s| s.as_str()), Some("__config_write__") | Some("__rollback__")) {
@@ -696,6 +907,11 @@ pub async fn remote_preview_queued_commands(
                     break;
                 }
             }
+            let step_after = pool.sftp_read(&host_id, "~/.clawpal/preview/.openclaw/openclaw.json").await
+                .map(&unsandbox)
+                .unwrap_or_else(|_| step_before.clone());
+            effects.push(queued_command_effect(cmd, &step_before, &step_after));
+            step_before = step_after;
             continue;
         }
 
@@ -722,23 +938,17 @@ pub async fn remote_preview_queued_commands(
             }
             _ => {}
         }
+        let step_after = pool.sftp_read(&host_id, "~/.clawpal/preview/.openclaw/openclaw.json").await
+            .map(&unsandbox)
+            .unwrap_or_else(|_| step_before.clone());
+        effects.push(queued_command_effect(cmd, &step_before, &step_after));
+        step_before = step_after;
     }
 
     let config_after = if errors.is_empty() {
         let raw = pool.sftp_read(&host_id, "~/.clawpal/preview/.openclaw/openclaw.json")
             .await?;
-        // Replace sandbox paths with real paths in preview output.
-        // The sandbox is at ~/.clawpal/preview, real OPENCLAW_HOME is ~.
-        // Resolve ~ on remote to get absolute sandbox prefix.
-        let resolved_home = pool.exec(&host_id, "echo $HOME").await
-            .map(|r| r.stdout.trim().to_string())
-            .unwrap_or_default();
-        if !resolved_home.is_empty() {
-            let sandbox_prefix = format!("{}/.clawpal/preview", resolved_home);
-            raw.replace(&sandbox_prefix, &resolved_home)
-        } else {
-            raw
-        }
+        unsandbox(raw)
     } else {
         config_before.clone()
     };
@@ -771,11 +981,15 @@ pub async fn remote_preview_queued_commands(
         }
     };
 
+    let config_before = normalize(&config_before);
+    let config_after = normalize(&config_after);
     Ok(PreviewQueueResult {
+        diff: queue_net_diff(&config_before, &config_after),
         commands,
-        config_before: normalize(&config_before),
-        config_after: normalize(&config_after),
+        config_before,
+        config_after,
         errors,
+        effects,
     })
 }
 
@@ -787,6 +1001,7 @@ pub async fn remote_preview_queued_commands(
 pub async fn remote_apply_queued_commands(
     pool: tauri::State<'_, SshConnectionPool>,
     queues: tauri::State<'_, RemoteCommandQueues>,
+    cache: tauri::State<'_, CliCache>,
     host_id: String,
 ) -> Result<ApplyQueueResult, String> {
     let commands = queues.list(&host_id);
@@ -898,6 +1113,7 @@ pub async fn remote_apply_queued_commands(
     }
 
     queues.clear(&host_id);
+    cache.invalidate(&crate::commands::remote_agents_overview_cache_key(&host_id));
     let _ = pool
         .exec_login(&host_id, "openclaw gateway restart")
         .await;
@@ -950,8 +1166,66 @@ impl CliCache {
             .insert(key, (std::time::Instant::now(), value));
     }
 
+    /// List cached keys with the age of each entry.
+    pub fn entries(&self) -> Vec<(String, std::time::Duration)> {
+        let cache = self.cache.lock().unwrap();
+        let mut entries: Vec<_> = cache
+            .iter()
+            .map(|(key, (ts, _))| (key.clone(), ts.elapsed()))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    /// Drop a single entry, e.g. when the host it describes disconnects.
+    pub fn invalidate(&self, key: &str) {
+        self.cache.lock().unwrap().remove(key);
+    }
+
     /// Invalidate all cache entries (called after Apply).
     pub fn invalidate_all(&self) {
         self.cache.lock().unwrap().clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(id: &str, command: &[&str]) -> PendingCommand {
+        PendingCommand {
+            id: id.into(),
+            label: id.into(),
+            command: command.iter().map(|s| s.to_string()).collect(),
+            created_at: String::new(),
+        }
+    }
+
+    fn ids(cmds: &[PendingCommand]) -> Vec<&str> {
+        cmds.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_move_pending_command() {
+        let mut cmds = vec![pending("a", &["x"]), pending("b", &["y"]), pending("c", &["z"])];
+        move_pending_command(&mut cmds, 0, 2).unwrap();
+        assert_eq!(ids(&cmds), vec!["b", "c", "a"]);
+        move_pending_command(&mut cmds, 2, 0).unwrap();
+        assert_eq!(ids(&cmds), vec!["a", "b", "c"]);
+        assert!(move_pending_command(&mut cmds, 3, 0).is_err());
+        assert!(move_pending_command(&mut cmds, 0, 3).is_err());
+        assert!(move_pending_command(&mut Vec::new(), 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_dedupe_adjacent_commands() {
+        let mut cmds = vec![
+            pending("a", &["openclaw", "gateway", "restart"]),
+            pending("b", &["openclaw", "gateway", "restart"]),
+            pending("c", &["openclaw", "doctor"]),
+            pending("d", &["openclaw", "gateway", "restart"]),
+        ];
+        dedupe_adjacent_commands(&mut cmds);
+        assert_eq!(ids(&cmds), vec!["a", "c", "d"]);
+    }
+}
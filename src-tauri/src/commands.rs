@@ -8,7 +8,10 @@ use serde_json::{Map, Value};
 use tauri::{Manager, State};
 
 use crate::config_io::{ensure_dirs, read_openclaw_config, write_json, write_text};
-use crate::doctor::{apply_auto_fixes, run_doctor, DoctorReport};
+use crate::doctor::{
+    apply_auto_fixes, escape_pointer_segment, find_orphaned_bindings, find_unknown_config_keys_in,
+    find_unreachable_agents_in, run_doctor, DoctorReport, OrphanedBinding, UnknownConfigKey,
+};
 use crate::history::{add_snapshot, list_snapshots, read_snapshot};
 use crate::models::resolve_paths;
 use crate::ssh::{SshConnectionPool, SshHostConfig, SshExecResult, SftpEntry};
@@ -147,11 +150,23 @@ pub struct ExtractModelProfilesResult {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExtractModelProfileEntry {
+    /// Normalized `provider/model` ref; pass back in `approved` to create it.
+    pub key: String,
     pub provider: String,
     pub model: String,
+    pub auth_ref: String,
+    pub base_url: Option<String>,
     pub source: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractModelProfilesPreview {
+    pub profiles: Vec<ExtractModelProfileEntry>,
+    pub reused: usize,
+    pub skipped_invalid: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenclawUpdateCache {
@@ -227,9 +242,15 @@ pub struct SessionAnalysis {
     pub last_activity: Option<String>,
     pub age_days: f64,
     pub total_tokens: u64,
+    /// True when `total_tokens` is a chars/4 estimate because sessions.json
+    /// had no `totalTokens` for this session.
+    pub tokens_estimated: bool,
     pub model: Option<String>,
     pub category: String,
     pub kind: String,
+    /// File modification time in unix seconds; lets callers track a
+    /// high-water mark for incremental re-analysis.
+    pub mtime: u64,
 }
 
 #[derive(Debug, Serialize)]
@@ -267,6 +288,9 @@ pub struct ModelProfile {
     pub base_url: Option<String>,
     pub description: Option<String>,
     pub enabled: bool,
+    /// Pinned profiles are listed first.
+    #[serde(default)]
+    pub favorite: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -274,6 +298,14 @@ pub struct ModelProfile {
 pub struct ModelCatalogModel {
     pub id: String,
     pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u64>,
+    /// Price per million input tokens, as reported by the CLI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_price: Option<f64>,
+    /// Price per million output tokens, as reported by the CLI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_price: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -306,6 +338,15 @@ pub struct DiscordGuildChannel {
     pub channel_name: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelegramChannel {
+    /// Account under `channels.telegram.accounts`, if the chat came from one.
+    pub account_id: Option<String>,
+    pub chat_id: String,
+    pub chat_name: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderAuthSuggestion {
@@ -334,6 +375,8 @@ pub struct HistoryItem {
     pub can_rollback: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rollback_of: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -361,6 +404,9 @@ pub struct AgentOverview {
     pub online: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub workspace: Option<String>,
+    /// Most recent session activity, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_active: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -386,6 +432,17 @@ pub fn clear_openclaw_version_cache() {
 
 static OPENCLAW_VERSION_CACHE: std::sync::Mutex<Option<Option<String>>> = std::sync::Mutex::new(None);
 
+/// Quick gateway health: TCP connect to the configured gateway port.
+fn probe_gateway_port(cfg: &Value) -> bool {
+    let gateway_port = cfg.pointer("/gateway/port")
+        .and_then(Value::as_u64)
+        .unwrap_or(18789) as u16;
+    std::net::TcpStream::connect_timeout(
+        &std::net::SocketAddr::from(([127, 0, 0, 1], gateway_port)),
+        std::time::Duration::from_millis(200),
+    ).is_ok()
+}
+
 /// Fast status: reads config + quick TCP probe of gateway port.
 #[tauri::command]
 pub fn get_status_light() -> Result<StatusLight, String> {
@@ -411,14 +468,7 @@ pub fn get_status_light() -> Result<StatusLight, String> {
         .map(|arr| arr.iter().filter_map(Value::as_str).map(String::from).collect())
         .unwrap_or_default();
 
-    // Quick gateway health: TCP connect to gateway port
-    let gateway_port = cfg.pointer("/gateway/port")
-        .and_then(Value::as_u64)
-        .unwrap_or(18789) as u16;
-    let healthy = std::net::TcpStream::connect_timeout(
-        &std::net::SocketAddr::from(([127, 0, 0, 1], gateway_port)),
-        std::time::Duration::from_millis(200),
-    ).is_ok();
+    let healthy = probe_gateway_port(&cfg);
 
     Ok(StatusLight {
         healthy,
@@ -428,6 +478,105 @@ pub fn get_status_light() -> Result<StatusLight, String> {
     })
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestCheck {
+    pub id: String,
+    pub passed: bool,
+    pub detail: String,
+    /// What to do when the check fails.
+    pub hint: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+fn self_test_check(id: &str, passed: bool, detail: String, hint: &str) -> SelfTestCheck {
+    SelfTestCheck {
+        id: id.into(),
+        passed,
+        detail,
+        hint: (!passed).then(|| hint.to_string()),
+    }
+}
+
+/// Local model servers that accept requests without an API key.
+fn provider_is_keyless(provider: &str) -> bool {
+    matches!(provider.trim().to_ascii_lowercase().as_str(), "ollama" | "lmstudio" | "vllm")
+}
+
+/// Onboarding check: binary, config, gateway, model profiles and doctor
+/// score, each with a pass/fail and a pointer to the fix.
+#[tauri::command]
+pub async fn self_test() -> Result<SelfTestReport, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let paths = resolve_paths();
+        let mut checks = Vec::new();
+
+        let version = resolve_openclaw_version();
+        checks.push(self_test_check(
+            "binary",
+            version != "unknown",
+            format!("openclaw version: {version}"),
+            "Install openclaw (npm i -g openclaw) or check the PATH via cli_environment_info",
+        ));
+
+        let cfg = read_openclaw_config(&paths);
+        checks.push(self_test_check(
+            "config",
+            cfg.is_ok(),
+            match &cfg {
+                Ok(_) => format!("{} parses", paths.config_path.display()),
+                Err(e) => e.clone(),
+            },
+            "Fix the config syntax (run_doctor_command / fix_issues) or restore a snapshot",
+        ));
+
+        let gateway_up = cfg.as_ref().map(probe_gateway_port).unwrap_or(false);
+        checks.push(self_test_check(
+            "gateway",
+            gateway_up,
+            if gateway_up { "Gateway port is accepting connections".into() } else { "Gateway port is not reachable".into() },
+            "Start or restart the gateway (restart_gateway)",
+        ));
+
+        let profiles = load_model_profiles(&paths);
+        let usable = profiles
+            .iter()
+            .filter(|p| {
+                p.enabled
+                    && (provider_is_keyless(&p.provider)
+                        || !resolve_profile_api_key(p, &paths.base_dir).trim().is_empty())
+            })
+            .count();
+        checks.push(self_test_check(
+            "modelProfiles",
+            usable > 0,
+            format!("{usable} of {} model profiles are usable", profiles.len()),
+            "Create a model profile with an API key (upsert_model_profile) or extract them from the config",
+        ));
+
+        let report = run_doctor(&paths);
+        checks.push(self_test_check(
+            "doctor",
+            report.ok,
+            format!("Doctor score {} with {} issue(s)", report.score, report.issues.len()),
+            "Review the doctor issues (run_doctor_command) and apply fixes",
+        ));
+
+        Ok(SelfTestReport {
+            passed: checks.iter().all(|c| c.passed),
+            checks,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 /// Local status extra: openclaw version (cached) + no duplicate detection needed locally.
 #[tauri::command]
 pub fn get_status_extra() -> Result<StatusExtra, String> {
@@ -473,6 +622,142 @@ pub fn refresh_model_catalog() -> Result<Vec<ModelCatalogProvider>, String> {
     load_model_catalog(&paths)
 }
 
+/// Keep providers matching `provider` exactly (case-insensitive) and models
+/// whose id or name contains `query`. Providers left without models are dropped.
+fn filter_model_catalog(
+    catalog: Vec<ModelCatalogProvider>,
+    provider: Option<&str>,
+    query: Option<&str>,
+) -> Vec<ModelCatalogProvider> {
+    let provider = provider.map(str::trim).filter(|p| !p.is_empty());
+    let query = query
+        .map(|q| q.trim().to_lowercase())
+        .filter(|q| !q.is_empty());
+    catalog
+        .into_iter()
+        .filter(|p| provider.is_none_or(|want| p.provider.eq_ignore_ascii_case(want)))
+        .filter_map(|mut p| {
+            if let Some(q) = &query {
+                p.models.retain(|m| {
+                    m.id.to_lowercase().contains(q)
+                        || m.name.as_deref().is_some_and(|n| n.to_lowercase().contains(q))
+                });
+                if p.models.is_empty() {
+                    return None;
+                }
+            }
+            Some(p)
+        })
+        .collect()
+}
+
+/// Load the catalog (from cache when fresh) and filter it server-side.
+#[tauri::command]
+pub fn list_model_catalog(
+    provider: Option<String>,
+    query: Option<String>,
+) -> Result<Vec<ModelCatalogProvider>, String> {
+    let paths = resolve_paths();
+    let catalog = load_model_catalog(&paths)?;
+    Ok(filter_model_catalog(catalog, provider.as_deref(), query.as_deref()))
+}
+
+#[cfg(test)]
+mod model_catalog_filter_tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_model_catalog_by_provider_and_query() {
+        let catalog = vec![
+            ModelCatalogProvider {
+                provider: "OpenAI".into(),
+                base_url: None,
+                models: vec![
+                    ModelCatalogModel {
+                        id: "gpt-4o".into(),
+                        name: Some("GPT-4o".into()),
+                        context_window: None,
+                        input_price: None,
+                        output_price: None,
+                    },
+                    ModelCatalogModel {
+                        id: "o3-mini".into(),
+                        name: None,
+                        context_window: None,
+                        input_price: None,
+                        output_price: None,
+                    },
+                ],
+            },
+            ModelCatalogProvider {
+                provider: "openrouter".into(),
+                base_url: None,
+                models: vec![ModelCatalogModel {
+                    id: "openai/gpt-4o".into(),
+                    name: None,
+                    context_window: None,
+                    input_price: None,
+                    output_price: None,
+                }],
+            },
+        ];
+        assert_eq!(filter_model_catalog(catalog.clone(), None, Some("  ")).len(), 2);
+        let by_provider = filter_model_catalog(catalog.clone(), Some("openai"), None);
+        assert_eq!(by_provider.len(), 1);
+        assert_eq!(by_provider[0].models.len(), 2);
+        let by_query = filter_model_catalog(catalog, None, Some("GPT"));
+        assert_eq!(by_query.len(), 2);
+        assert_eq!(by_query[0].models.len(), 1);
+    }
+}
+
+const MODEL_SUGGESTION_LIMIT: usize = 20;
+
+/// Rank catalog entries against a typed prefix: full `provider/model` prefix
+/// matches first, then model-name prefix matches, then substring matches.
+fn rank_model_suggestions(catalog: &[ModelCatalogProvider], prefix: &str, limit: usize) -> Vec<String> {
+    let needle = normalize_model_ref(prefix);
+    let mut ranked: Vec<(u8, String)> = Vec::new();
+    let mut seen = HashSet::new();
+    for provider in catalog {
+        for model in &provider.models {
+            let full = if model.id.contains('/') {
+                model.id.clone()
+            } else {
+                format!("{}/{}", provider.provider, model.id)
+            };
+            let key = normalize_model_ref(&full);
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            let model_part = key.split_once('/').map(|(_, m)| m).unwrap_or(&key);
+            let rank = if needle.is_empty() || key.starts_with(&needle) {
+                0
+            } else if model_part.starts_with(&needle) {
+                1
+            } else if key.contains(&needle) {
+                2
+            } else {
+                continue;
+            };
+            ranked.push((rank, full));
+        }
+    }
+    ranked.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.len().cmp(&b.1.len())).then_with(|| a.1.cmp(&b.1)));
+    ranked.into_iter().take(limit).map(|(_, full)| full).collect()
+}
+
+#[tauri::command]
+pub async fn suggest_models(prefix: String, limit: Option<usize>) -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let paths = resolve_paths();
+        let catalog = load_model_catalog(&paths)?;
+        Ok(rank_model_suggestions(&catalog, &prefix, limit.unwrap_or(MODEL_SUGGESTION_LIMIT)))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 pub fn get_system_status() -> Result<SystemStatus, String> {
     let paths = resolve_paths();
@@ -515,34 +800,204 @@ pub fn get_system_status() -> Result<SystemStatus, String> {
     })
 }
 
+/// Stable sort putting favorites first, otherwise keeping stored order.
+fn sort_favorites_first(profiles: &mut [ModelProfile]) {
+    profiles.sort_by_key(|p| !p.favorite);
+}
+
 #[tauri::command]
 pub fn list_model_profiles() -> Result<Vec<ModelProfile>, String> {
     let paths = resolve_paths();
-    Ok(load_model_profiles(&paths))
+    let mut profiles = load_model_profiles(&paths);
+    sort_favorites_first(&mut profiles);
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod profile_favorite_tests {
+    use super::*;
+
+    #[test]
+    fn favorites_sort_on_listing_without_reordering_storage() {
+        let root = std::env::temp_dir().join(format!("clawpal-favorite-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let paths = crate::models::OpenClawPaths {
+            openclaw_dir: root.join("openclaw"),
+            config_path: root.join("openclaw").join("openclaw.json"),
+            base_dir: root.join("openclaw"),
+            clawpal_dir: root.join("clawpal"),
+            history_dir: root.join("clawpal").join("history"),
+            metadata_path: root.join("clawpal").join("metadata.json"),
+        };
+        let profiles: Vec<ModelProfile> = ["a", "b", "c"]
+            .iter()
+            .map(|id| ModelProfile {
+                id: id.to_string(),
+                name: id.to_string(),
+                provider: "openai".into(),
+                model: "gpt-4o".into(),
+                auth_ref: String::new(),
+                api_key: None,
+                base_url: None,
+                description: None,
+                enabled: true,
+                favorite: *id == "c",
+            })
+            .collect();
+        save_model_profiles(&paths, &profiles).unwrap();
+
+        let mut listed = load_model_profiles(&paths);
+        sort_favorites_first(&mut listed);
+        let ids: Vec<&str> = listed.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a", "b"]);
+
+        // A load/save round trip, as every mutating command does, keeps stored order.
+        let stored = load_model_profiles(&paths);
+        save_model_profiles(&paths, &stored).unwrap();
+        let ids: Vec<String> = load_model_profiles(&paths).into_iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        let _ = fs::remove_dir_all(&root);
+    }
 }
 
+/// Serialize all profiles as a portable bundle. API keys are dropped unless
+/// `include_secrets` is set.
 #[tauri::command]
-pub fn check_openclaw_update() -> Result<OpenclawUpdateCheck, String> {
+pub fn export_model_profiles(include_secrets: bool) -> Result<String, String> {
     let paths = resolve_paths();
-    check_openclaw_update_cached(&paths, true)
+    let mut profiles = load_model_profiles(&paths);
+    if !include_secrets {
+        for profile in &mut profiles {
+            profile.api_key = None;
+        }
+    }
+    serde_json::to_string_pretty(&serde_json::json!({ "profiles": profiles, "version": 1 }))
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelProfileImportResult {
+    pub added: usize,
+    pub updated: usize,
+    /// Copy of the previous profiles file, written before a replace.
+    pub backup_path: Option<String>,
+}
+
+/// Accept either a `{ "profiles": [...] }` bundle or a bare array.
+fn parse_profile_bundle(json: &str) -> Result<Vec<ModelProfile>, String> {
+    let value: Value = serde_json::from_str(json).map_err(|e| format!("Invalid profile bundle: {e}"))?;
+    let list = value.get("profiles").cloned().unwrap_or(value);
+    let profiles: Vec<ModelProfile> =
+        serde_json::from_value(list).map_err(|e| format!("Invalid profile bundle: {e}"))?;
+    if let Some(bad) = profiles.iter().find(|p| p.id.trim().is_empty()) {
+        return Err(format!("Profile '{}' has no id", bad.name));
+    }
+    Ok(profiles)
+}
+
+/// Merge `incoming` into `existing` by id. Existing API keys survive when the
+/// incoming profile has none (e.g. an export without secrets).
+fn merge_profiles(existing: &mut Vec<ModelProfile>, incoming: Vec<ModelProfile>) -> (usize, usize) {
+    let (mut added, mut updated) = (0, 0);
+    for mut profile in incoming {
+        match existing.iter_mut().find(|p| p.id == profile.id) {
+            Some(current) => {
+                if profile.api_key.as_ref().is_none_or(|k| k.trim().is_empty()) {
+                    profile.api_key = current.api_key.clone();
+                }
+                *current = profile;
+                updated += 1;
+            }
+            None => {
+                existing.push(profile);
+                added += 1;
+            }
+        }
+    }
+    (added, updated)
 }
 
+/// Import a profile bundle, merging by id or replacing the whole set.
 #[tauri::command]
-pub fn extract_model_profiles_from_config() -> Result<ExtractModelProfilesResult, String> {
+pub fn import_model_profiles(json: String, merge: bool) -> Result<ModelProfileImportResult, String> {
+    let incoming = parse_profile_bundle(&json)?;
     let paths = resolve_paths();
-    let cfg = read_openclaw_config(&paths)?;
-    let profiles = load_model_profiles(&paths);
-    let bindings = collect_model_bindings(&cfg, &profiles);
-    let mut created = 0usize;
-    let mut reused = 0usize;
-    let mut skipped_invalid = 0usize;
-    let mut seen = HashSet::new();
+    if merge {
+        let mut profiles = load_model_profiles(&paths);
+        let (added, updated) = merge_profiles(&mut profiles, incoming);
+        save_model_profiles(&paths, &profiles)?;
+        return Ok(ModelProfileImportResult { added, updated, backup_path: None });
+    }
+
+    let path = model_profiles_path(&paths);
+    let backup_path = if path.exists() {
+        let backup = path.with_file_name(format!("model-profiles.{}.bak.json", unix_timestamp_secs()));
+        fs::copy(&path, &backup).map_err(|e| format!("Failed to back up profiles: {e}"))?;
+        Some(backup.to_string_lossy().to_string())
+    } else {
+        None
+    };
+    let added = incoming.len();
+    save_model_profiles(&paths, &incoming)?;
+    Ok(ModelProfileImportResult { added, updated: 0, backup_path })
+}
+
+#[cfg(test)]
+mod profile_bundle_tests {
+    use super::*;
 
-    let mut next_profiles = profiles;
-    let mut model_profile_map: HashMap<String, String> = HashMap::new();
-    for profile in &next_profiles {
-        model_profile_map.insert(normalize_model_ref(&profile_to_model_value(profile)), profile.id.clone());
+    #[test]
+    fn merge_updates_by_id_and_keeps_keys() {
+        let bundle = r#"{"profiles": [
+            {"id": "a", "name": "A2", "provider": "openai", "model": "gpt-4o", "enabled": true},
+            {"id": "b", "name": "B", "provider": "anthropic", "model": "claude", "enabled": true}
+        ]}"#;
+        let incoming = parse_profile_bundle(bundle).unwrap();
+        let mut existing = parse_profile_bundle(
+            r#"[{"id": "a", "name": "A", "provider": "openai", "model": "gpt-4o", "apiKey": "sk-1", "enabled": true}]"#,
+        )
+        .unwrap();
+        assert_eq!(merge_profiles(&mut existing, incoming), (1, 1));
+        assert_eq!(existing[0].name, "A2");
+        assert_eq!(existing[0].api_key.as_deref(), Some("sk-1"));
+        assert_eq!(existing[1].id, "b");
+        assert!(parse_profile_bundle(r#"[{"id": "", "name": "x", "provider": "p", "model": "m", "enabled": true}]"#).is_err());
     }
+}
+
+/// Flip a profile's favorite flag. Returns the new value.
+#[tauri::command]
+pub fn toggle_model_profile_favorite(profile_id: String) -> Result<bool, String> {
+    let paths = resolve_paths();
+    let mut profiles = load_model_profiles(&paths);
+    let profile = profiles
+        .iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| "Profile not found".to_string())?;
+    profile.favorite = !profile.favorite;
+    let favorite = profile.favorite;
+    save_model_profiles(&paths, &profiles)?;
+    Ok(favorite)
+}
+
+#[tauri::command]
+pub fn check_openclaw_update() -> Result<OpenclawUpdateCheck, String> {
+    let paths = resolve_paths();
+    check_openclaw_update_cached(&paths, true)
+}
+
+/// Walk every model binding in the config and work out which profiles an
+/// extraction would create, without touching the stored profiles.
+fn plan_extracted_profiles(cfg: &Value, profiles: &[ModelProfile]) -> ExtractModelProfilesPreview {
+    let bindings = collect_model_bindings(cfg, profiles);
+    let mut reused = 0usize;
+    let mut skipped_invalid = 0usize;
+    let mut planned = Vec::new();
+    let mut known: HashSet<String> = profiles
+        .iter()
+        .map(|profile| normalize_model_ref(&profile_to_model_value(profile)))
+        .collect();
 
     for binding in bindings {
         let scope_label = match binding.scope.as_str() {
@@ -558,7 +1013,7 @@ pub fn extract_model_profiles_from_config() -> Result<ExtractModelProfilesResult
         if model_ref.trim().is_empty() {
             continue;
         }
-        if model_profile_map.contains_key(&model_ref) || seen.contains(&model_ref) {
+        if known.contains(&model_ref) {
             reused += 1;
             continue;
         }
@@ -569,29 +1024,80 @@ pub fn extract_model_profiles_from_config() -> Result<ExtractModelProfilesResult
             skipped_invalid += 1;
             continue;
         }
-        let auth_ref = resolve_auth_ref_for_provider(&cfg, provider)
+        let auth_ref = resolve_auth_ref_for_provider(cfg, provider)
             .unwrap_or_else(|| format!("{provider}:default"));
-        let base_url = resolve_model_provider_base_url(&cfg, provider);
-        let profile = ModelProfile {
-            id: uuid::Uuid::new_v4().to_string(),
-            name: format!("{scope_label} model profile"),
+        let base_url = resolve_model_provider_base_url(cfg, provider);
+        known.insert(model_ref.clone());
+        planned.push(ExtractModelProfileEntry {
+            key: model_ref,
             provider: provider.to_string(),
             model: model.to_string(),
             auth_ref,
-            api_key: None,
             base_url,
-            description: Some(format!("Extracted from config ({scope_label})")),
+            source: scope_label,
+        });
+    }
+
+    ExtractModelProfilesPreview {
+        profiles: planned,
+        reused,
+        skipped_invalid,
+    }
+}
+
+/// Turn planned entries into new profiles, keeping only `approved` keys when
+/// a subset was given. Returns the number created.
+fn apply_extracted_profiles(
+    plan: ExtractModelProfilesPreview,
+    approved: Option<&[String]>,
+    profiles: &mut Vec<ModelProfile>,
+) -> usize {
+    let approved: Option<HashSet<String>> =
+        approved.map(|keys| keys.iter().map(|k| normalize_model_ref(k)).collect());
+    let mut created = 0usize;
+    for entry in plan.profiles {
+        if approved.as_ref().is_some_and(|keys| !keys.contains(&entry.key)) {
+            continue;
+        }
+        profiles.push(ModelProfile {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: format!("{} model profile", entry.source),
+            provider: entry.provider,
+            model: entry.model,
+            auth_ref: entry.auth_ref,
+            api_key: None,
+            base_url: entry.base_url,
+            description: Some(format!("Extracted from config ({})", entry.source)),
             enabled: true,
-        };
-        let key = profile_to_model_value(&profile);
-        model_profile_map.insert(normalize_model_ref(&key), profile.id.clone());
-        next_profiles.push(profile);
-        seen.insert(model_ref);
+            favorite: false,
+        });
         created += 1;
     }
+    created
+}
+
+#[tauri::command]
+pub fn preview_extract_model_profiles() -> Result<ExtractModelProfilesPreview, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    let profiles = load_model_profiles(&paths);
+    Ok(plan_extracted_profiles(&cfg, &profiles))
+}
+
+#[tauri::command]
+pub fn extract_model_profiles_from_config(
+    approved: Option<Vec<String>>,
+) -> Result<ExtractModelProfilesResult, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    let mut profiles = load_model_profiles(&paths);
+    let plan = plan_extracted_profiles(&cfg, &profiles);
+    let reused = plan.reused;
+    let skipped_invalid = plan.skipped_invalid;
+    let created = apply_extracted_profiles(plan, approved.as_deref(), &mut profiles);
 
     if created > 0 {
-        save_model_profiles(&paths, &next_profiles)?;
+        save_model_profiles(&paths, &profiles)?;
     }
 
     Ok(ExtractModelProfilesResult {
@@ -648,10 +1154,36 @@ pub fn upsert_model_profile(mut profile: ModelProfile) -> Result<ModelProfile, S
     Ok(profile)
 }
 
+/// Error listing the scopes that use `profile`, so deleting it does not
+/// orphan their model references.
+fn ensure_profile_unused(cfg: &Value, profile: &ModelProfile) -> Result<(), String> {
+    let usage = profile_usage_in(cfg, profile);
+    if usage.is_empty() {
+        return Ok(());
+    }
+    let scopes: Vec<String> = usage
+        .iter()
+        .map(|b| format!("{} {}", b.scope, b.scope_id))
+        .collect();
+    Err(format!(
+        "Profile {} is in use by {} scope(s): {}",
+        profile.id,
+        scopes.len(),
+        scopes.join(", ")
+    ))
+}
+
+/// Delete a profile. Refuses while config still uses its model unless
+/// `force` is set.
 #[tauri::command]
-pub fn delete_model_profile(profile_id: String) -> Result<bool, String> {
+pub fn delete_model_profile(profile_id: String, force: Option<bool>) -> Result<bool, String> {
     let paths = resolve_paths();
     let mut profiles = load_model_profiles(&paths);
+    if !force.unwrap_or(false) {
+        if let Some(profile) = profiles.iter().find(|p| p.id == profile_id) {
+            ensure_profile_unused(&read_openclaw_config(&paths)?, profile)?;
+        }
+    }
     let before = profiles.len();
     profiles.retain(|p| p.id != profile_id);
     if profiles.len() == before {
@@ -661,43 +1193,382 @@ pub fn delete_model_profile(profile_id: String) -> Result<bool, String> {
     Ok(true)
 }
 
-#[tauri::command]
-pub fn resolve_provider_auth(provider: String) -> Result<ProviderAuthSuggestion, String> {
-    let provider_trimmed = provider.trim();
-    if provider_trimmed.is_empty() {
-        return Ok(ProviderAuthSuggestion { auth_ref: None, has_key: false, source: String::new() });
+#[cfg(test)]
+mod profile_delete_guard_tests {
+    use super::*;
+
+    #[test]
+    fn in_use_profile_cannot_be_deleted() {
+        let profile = parse_profile_bundle(
+            r#"[{"id": "a", "name": "A", "provider": "openai", "model": "gpt-4o", "enabled": true}]"#,
+        )
+        .unwrap()
+        .remove(0);
+        let cfg = serde_json::json!({ "agents": { "list": [{ "id": "x", "model": "openai/gpt-4o" }] } });
+        let err = ensure_profile_unused(&cfg, &profile).unwrap_err();
+        assert!(err.contains("agent x"), "{err}");
+        assert!(ensure_profile_unused(&serde_json::json!({}), &profile).is_ok());
     }
+}
+
+/// Model bindings (global, agent or channel) whose value resolves to `profile`.
+fn profile_usage_in(cfg: &Value, profile: &ModelProfile) -> Vec<ModelBinding> {
+    collect_model_bindings(cfg, std::slice::from_ref(profile))
+        .into_iter()
+        .filter(|binding| binding.model_profile_id.is_some())
+        .collect()
+}
+
+/// Every scope currently using the profile's model, e.g. to warn before
+/// deleting it.
+#[tauri::command]
+pub fn model_profile_usage(profile_id: String) -> Result<Vec<ModelBinding>, String> {
     let paths = resolve_paths();
+    let profile = load_model_profiles(&paths)
+        .into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| format!("Profile not found: {profile_id}"))?;
     let cfg = read_openclaw_config(&paths)?;
+    Ok(profile_usage_in(&cfg, &profile))
+}
 
-    // 1. Check openclaw config auth profiles
-    if let Some(auth_ref) = resolve_auth_ref_for_provider(&cfg, provider_trimmed) {
-        return Ok(ProviderAuthSuggestion {
-            auth_ref: Some(auth_ref),
-            has_key: true,
-            source: "openclaw auth profile".into(),
+#[cfg(test)]
+mod profile_usage_tests {
+    use super::*;
+
+    #[test]
+    fn profile_usage_lists_matching_scopes() {
+        let profile = parse_profile_bundle(
+            r#"[{"id": "a", "name": "A", "provider": "openai", "model": "gpt-4o", "enabled": true}]"#,
+        )
+        .unwrap()
+        .remove(0);
+        let cfg = serde_json::json!({
+            "agents": {
+                "defaults": { "model": "anthropic/claude" },
+                "list": [{ "id": "x", "model": "openai/gpt-4o" }, { "id": "y" }]
+            },
+            "channels": { "discord": { "guilds": { "g": { "model": "OpenAI/GPT-4o" } } } }
         });
+        let usage = profile_usage_in(&cfg, &profile);
+        let scopes: Vec<&str> = usage.iter().map(|b| b.scope_id.as_str()).collect();
+        assert_eq!(scopes, vec!["x", "channels.discord.guilds.g"]);
+        assert!(usage.iter().all(|b| b.model_profile_id.as_deref() == Some("a")));
     }
+}
 
-    // 2. Check env vars
-    let provider_upper = provider_trimmed.to_uppercase().replace('-', "_");
-    for suffix in ["_API_KEY", "_KEY", "_TOKEN"] {
-        let env_name = format!("{provider_upper}{suffix}");
-        if std::env::var(&env_name).map(|v| !v.trim().is_empty()).unwrap_or(false) {
-            return Ok(ProviderAuthSuggestion {
-                auth_ref: Some(env_name),
-                has_key: true,
-                source: "environment variable".into(),
-            });
-        }
+/// Profiles that resolve to the same `provider/model`, grouped; groups of one
+/// are left out.
+fn group_duplicate_profiles(profiles: &[ModelProfile]) -> Vec<Vec<ModelProfile>> {
+    let mut groups: BTreeMap<String, Vec<ModelProfile>> = BTreeMap::new();
+    for profile in profiles {
+        groups
+            .entry(normalize_model_ref(&profile_to_model_value(profile)))
+            .or_default()
+            .push(profile.clone());
     }
+    groups.into_values().filter(|group| group.len() > 1).collect()
+}
 
-    // 3. Check existing model profiles for this provider
-    let profiles = load_model_profiles(&paths);
-    for p in &profiles {
-        if p.provider.eq_ignore_ascii_case(provider_trimmed) {
-            let key = resolve_profile_api_key(p, &paths.base_dir);
-            if !key.is_empty() {
+#[tauri::command]
+pub fn find_duplicate_model_profiles() -> Result<Vec<Vec<ModelProfile>>, String> {
+    let paths = resolve_paths();
+    Ok(group_duplicate_profiles(&load_model_profiles(&paths)))
+}
+
+/// Point every `model` reference (plain string, `primary` or `fallbacks`)
+/// whose normalized value is in `from` at `to`. Returns how many changed.
+fn rewrite_model_refs(node: &mut Value, from: &HashSet<String>, to: &str) -> usize {
+    let rewrite = |slot: &mut Value| {
+        if slot.as_str().is_some_and(|s| s != to && from.contains(&normalize_model_ref(s))) {
+            *slot = Value::String(to.to_string());
+            1
+        } else {
+            0
+        }
+    };
+    let mut changed = 0;
+    match node {
+        Value::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                if key == "model" {
+                    changed += rewrite(child);
+                    if let Some(model_obj) = child.as_object_mut() {
+                        if let Some(primary) = model_obj.get_mut("primary") {
+                            changed += rewrite(primary);
+                        }
+                        if let Some(fallbacks) = model_obj.get_mut("fallbacks").and_then(Value::as_array_mut) {
+                            for fallback in fallbacks {
+                                changed += rewrite(fallback);
+                            }
+                        }
+                    }
+                } else {
+                    changed += rewrite_model_refs(child, from, to);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                changed += rewrite_model_refs(item, from, to);
+            }
+        }
+        _ => {}
+    }
+    changed
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelProfileMergeResult {
+    pub removed: usize,
+    pub rewritten_refs: usize,
+}
+
+/// Delete `remove_ids` in favour of `keep_id` and point config model
+/// references that used one of the removed profiles at the kept one. All
+/// profiles must resolve to the same model.
+#[tauri::command]
+pub fn merge_model_profiles(keep_id: String, remove_ids: Vec<String>) -> Result<ModelProfileMergeResult, String> {
+    let paths = resolve_paths();
+    let mut profiles = load_model_profiles(&paths);
+    let keep = profiles
+        .iter()
+        .find(|p| p.id == keep_id)
+        .cloned()
+        .ok_or_else(|| format!("Profile not found: {keep_id}"))?;
+    let keep_value = profile_to_model_value(&keep);
+    let keep_norm = normalize_model_ref(&keep_value);
+    let mut from = HashSet::new();
+    for id in &remove_ids {
+        if *id == keep_id {
+            return Err("Cannot merge a profile into itself".into());
+        }
+        let profile = profiles
+            .iter()
+            .find(|p| p.id == *id)
+            .ok_or_else(|| format!("Profile not found: {id}"))?;
+        let value = profile_to_model_value(profile);
+        if normalize_model_ref(&value) != keep_norm {
+            return Err(format!("Profile {id} uses {value}, not {keep_value}"));
+        }
+        from.insert(normalize_model_ref(&value));
+        from.insert(normalize_model_ref(&profile.model));
+    }
+
+    let mut cfg = read_openclaw_config(&paths)?;
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+    let rewritten_refs = rewrite_model_refs(&mut cfg, &from, &keep_value);
+    if rewritten_refs > 0 {
+        write_config_with_snapshot(&paths, &current, &cfg, "merge-model-profiles")?;
+    }
+
+    let before = profiles.len();
+    profiles.retain(|p| !remove_ids.contains(&p.id));
+    let removed = before - profiles.len();
+    save_model_profiles(&paths, &profiles)?;
+    Ok(ModelProfileMergeResult { removed, rewritten_refs })
+}
+
+#[cfg(test)]
+mod duplicate_profile_tests {
+    use super::*;
+
+    #[test]
+    fn groups_duplicates_and_rewrites_refs() {
+        let profiles = parse_profile_bundle(
+            r#"[{"id": "a", "name": "A", "provider": "openai", "model": "gpt-4o", "enabled": true},
+                {"id": "b", "name": "B", "provider": "OpenAI", "model": "GPT-4o", "enabled": true},
+                {"id": "c", "name": "C", "provider": "anthropic", "model": "claude", "enabled": true}]"#,
+        )
+        .unwrap();
+        let groups = group_duplicate_profiles(&profiles);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].iter().map(|p| p.id.as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+
+        let mut cfg = serde_json::json!({
+            "agents": {
+                "defaults": { "model": { "primary": "OpenAI/GPT-4o", "fallbacks": ["openai/gpt-4o", "anthropic/claude"] } },
+                "list": [{ "id": "x", "model": "OpenAI/GPT-4o" }]
+            }
+        });
+        let from: HashSet<String> = ["openai/gpt-4o".to_string()].into_iter().collect();
+        assert_eq!(rewrite_model_refs(&mut cfg, &from, "openai/gpt-4o"), 2);
+        assert_eq!(cfg.pointer("/agents/list/0/model").unwrap(), "openai/gpt-4o");
+        assert_eq!(cfg.pointer("/agents/defaults/model/fallbacks/1").unwrap(), "anthropic/claude");
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelProfileTestResult {
+    pub ok: bool,
+    /// The endpoint answered but the key could not be verified.
+    pub degraded: bool,
+    pub status_code: Option<u16>,
+    pub message: String,
+}
+
+/// Default API root for providers commonly configured without a base URL.
+fn default_provider_base_url(provider: &str) -> Option<&'static str> {
+    match provider.trim().to_ascii_lowercase().as_str() {
+        "openai" => Some("https://api.openai.com/v1"),
+        "openrouter" => Some("https://openrouter.ai/api/v1"),
+        "anthropic" => Some("https://api.anthropic.com/v1"),
+        "groq" => Some("https://api.groq.com/openai/v1"),
+        "mistral" => Some("https://api.mistral.ai/v1"),
+        "deepseek" => Some("https://api.deepseek.com/v1"),
+        _ => None,
+    }
+}
+
+/// Probe `GET {base}/models` with the profile's key. A 404/405 means the
+/// endpoint isn't OpenAI-compatible, so fall back to a HEAD on the base URL
+/// just to confirm it is reachable; that result is degraded rather than ok.
+fn probe_model_profile(profile: &ModelProfile, api_key: &str) -> ModelProfileTestResult {
+    let Some(base) = profile
+        .base_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .or_else(|| default_provider_base_url(&profile.provider))
+    else {
+        return ModelProfileTestResult {
+            ok: false,
+            degraded: false,
+            status_code: None,
+            message: format!("No base URL configured for provider {}", profile.provider),
+        };
+    };
+    let base = base.trim_end_matches('/');
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            return ModelProfileTestResult {
+                ok: false,
+                degraded: false,
+                status_code: None,
+                message: format!("HTTP client error: {e}"),
+            }
+        }
+    };
+    let with_auth = |req: reqwest::blocking::RequestBuilder| {
+        if api_key.is_empty() {
+            req
+        } else if profile.provider.eq_ignore_ascii_case("anthropic") {
+            req.header("x-api-key", api_key).header("anthropic-version", "2023-06-01")
+        } else {
+            req.bearer_auth(api_key)
+        }
+    };
+
+    let resp = match with_auth(client.get(format!("{base}/models"))).send() {
+        Ok(r) => r,
+        Err(e) => {
+            return ModelProfileTestResult {
+                ok: false,
+                degraded: false,
+                status_code: None,
+                message: format!("Request to {base} failed: {e}"),
+            }
+        }
+    };
+    let status = resp.status();
+    if status.is_success() {
+        return ModelProfileTestResult {
+            ok: true,
+            degraded: false,
+            status_code: Some(status.as_u16()),
+            message: "API key accepted".into(),
+        };
+    }
+    if status.as_u16() == 401 || status.as_u16() == 403 {
+        return ModelProfileTestResult {
+            ok: false,
+            degraded: false,
+            status_code: Some(status.as_u16()),
+            message: "API key rejected by provider".into(),
+        };
+    }
+    if status.as_u16() == 404 || status.as_u16() == 405 {
+        return match with_auth(client.head(base)).send() {
+            Ok(head) => ModelProfileTestResult {
+                ok: false,
+                degraded: head.status().as_u16() < 500,
+                status_code: Some(head.status().as_u16()),
+                message: "Endpoint reachable (no models listing to verify the key)".into(),
+            },
+            Err(e) => ModelProfileTestResult {
+                ok: false,
+                degraded: false,
+                status_code: None,
+                message: format!("Request to {base} failed: {e}"),
+            },
+        };
+    }
+    ModelProfileTestResult {
+        ok: false,
+        degraded: false,
+        status_code: Some(status.as_u16()),
+        message: format!("Provider returned status {status}"),
+    }
+}
+
+/// Check that a saved profile's endpoint is reachable and its key works.
+#[tauri::command]
+pub async fn test_model_profile(profile_id: String) -> Result<ModelProfileTestResult, String> {
+    let paths = resolve_paths();
+    let profile = load_model_profiles(&paths)
+        .into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| format!("Model profile not found: {profile_id}"))?;
+    let api_key = resolve_profile_api_key(&profile, &paths.base_dir);
+    // Must use spawn_blocking because reqwest::blocking panics in async context
+    tauri::async_runtime::spawn_blocking(move || probe_model_profile(&profile, &api_key))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn resolve_provider_auth(provider: String) -> Result<ProviderAuthSuggestion, String> {
+    let provider_trimmed = provider.trim();
+    if provider_trimmed.is_empty() {
+        return Ok(ProviderAuthSuggestion { auth_ref: None, has_key: false, source: String::new() });
+    }
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+
+    // 1. Check openclaw config auth profiles
+    if let Some(auth_ref) = resolve_auth_ref_for_provider(&cfg, provider_trimmed) {
+        return Ok(ProviderAuthSuggestion {
+            auth_ref: Some(auth_ref),
+            has_key: true,
+            source: "openclaw auth profile".into(),
+        });
+    }
+
+    // 2. Check env vars
+    let provider_upper = provider_trimmed.to_uppercase().replace('-', "_");
+    for suffix in ["_API_KEY", "_KEY", "_TOKEN"] {
+        let env_name = format!("{provider_upper}{suffix}");
+        if std::env::var(&env_name).map(|v| !v.trim().is_empty()).unwrap_or(false) {
+            return Ok(ProviderAuthSuggestion {
+                auth_ref: Some(env_name),
+                has_key: true,
+                source: "environment variable".into(),
+            });
+        }
+    }
+
+    // 3. Check existing model profiles for this provider
+    let profiles = load_model_profiles(&paths);
+    for p in &profiles {
+        if p.provider.eq_ignore_ascii_case(provider_trimmed) {
+            let key = resolve_profile_api_key(p, &paths.base_dir);
+            if !key.is_empty() {
                 let auth_ref = if !p.auth_ref.trim().is_empty() {
                     Some(p.auth_ref.clone())
                 } else {
@@ -717,13 +1588,43 @@ pub fn resolve_provider_auth(provider: String) -> Result<ProviderAuthSuggestion,
 
 #[tauri::command]
 pub async fn list_channels() -> Result<Vec<ChannelNode>, String> {
-    tauri::async_runtime::spawn_blocking(|| {
-        let paths = resolve_paths();
-        let cfg = read_openclaw_config(&paths)?;
-        let mut nodes = collect_channel_nodes(&cfg);
-        enrich_channel_display_names(&paths, &cfg, &mut nodes)?;
-        Ok(nodes)
-    }).await.map_err(|e| e.to_string())?
+    let paths = resolve_paths();
+    let cfg = {
+        let paths = paths.clone();
+        tauri::async_runtime::spawn_blocking(move || read_openclaw_config(&paths))
+            .await
+            .map_err(|e| e.to_string())??
+    };
+    let mut nodes = collect_channel_nodes(&cfg);
+    enrich_channel_display_names(&paths, &cfg, &mut nodes, None).await?;
+    Ok(nodes)
+}
+
+/// Re-resolve channel display names for the given platforms (every platform
+/// under `channels` when empty) and rewrite the unified name cache. Names for
+/// other platforms are carried over from the cache. "discord" additionally
+/// refreshes the guild/channel cache, which needs the Discord REST API.
+#[tauri::command]
+pub async fn refresh_channel_names(platforms: Vec<String>) -> Result<Vec<ChannelNode>, String> {
+    let paths = resolve_paths();
+    let cfg = {
+        let paths = paths.clone();
+        tauri::async_runtime::spawn_blocking(move || read_openclaw_config(&paths))
+            .await
+            .map_err(|e| e.to_string())??
+    };
+    let wanted: HashSet<String> = platforms
+        .iter()
+        .map(|p| p.trim().to_lowercase())
+        .filter(|p| !p.is_empty())
+        .collect();
+    let mut nodes = collect_channel_nodes(&cfg);
+    let filter = if wanted.is_empty() { None } else { Some(&wanted) };
+    enrich_channel_display_names(&paths, &cfg, &mut nodes, filter).await?;
+    if wanted.is_empty() || wanted.contains("discord") {
+        refresh_discord_guild_channels().await?;
+    }
+    Ok(nodes)
 }
 
 #[tauri::command]
@@ -926,6 +1827,137 @@ pub async fn refresh_discord_guild_channels() -> Result<Vec<DiscordGuildChannel>
     }).await.map_err(|e| e.to_string())?
 }
 
+/// Read Telegram chats from persistent cache. Fast, no subprocess.
+#[tauri::command]
+pub fn list_telegram_channels() -> Result<Vec<TelegramChannel>, String> {
+    let paths = resolve_paths();
+    Ok(read_json_cache_file(&paths.clawpal_dir.join("telegram-channels.json")))
+}
+
+/// Telegram chats among the resolved channel nodes (`groups` at the top
+/// level or per account), followed by chats that only telegram bindings
+/// reference. Names default to the chat id.
+fn collect_telegram_chats(cfg: &Value, nodes: &[ChannelNode]) -> Vec<TelegramChannel> {
+    let mut groups: Vec<(Option<&str>, &str, Option<&String>)> = nodes
+        .iter()
+        .filter_map(|node| {
+            let rest = node.path.strip_prefix("channels.telegram.")?;
+            let segments: Vec<&str> = rest.split('.').collect();
+            let (account_id, chat_id) = match segments.as_slice() {
+                ["groups", chat_id] => (None, *chat_id),
+                ["accounts", account_id, "groups", chat_id] => (Some(*account_id), *chat_id),
+                _ => return None,
+            };
+            Some((account_id, chat_id, node.display_name.as_ref()))
+        })
+        .collect();
+    // Top-level groups first, so a chat shared with an account keeps no account.
+    groups.sort_by_key(|(account_id, _, _)| account_id.is_some());
+
+    let mut entries: Vec<TelegramChannel> = Vec::new();
+    for (account_id, chat_id, name) in groups {
+        if let Some(existing) = entries.iter_mut().find(|e| e.chat_id == chat_id) {
+            if existing.chat_name == existing.chat_id {
+                if let Some(name) = name {
+                    existing.chat_name = name.clone();
+                }
+            }
+            continue;
+        }
+        entries.push(TelegramChannel {
+            account_id: account_id.map(str::to_string),
+            chat_id: chat_id.to_string(),
+            chat_name: name.cloned().unwrap_or_else(|| chat_id.to_string()),
+        });
+    }
+    for binding in cfg.get("bindings").and_then(Value::as_array).into_iter().flatten() {
+        let Some(m) = binding.get("match") else {
+            continue;
+        };
+        if m.get("channel").and_then(Value::as_str) != Some("telegram") {
+            continue;
+        }
+        let chat_id = match m.pointer("/peer/id") {
+            Some(Value::String(s)) => s.trim().to_string(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => continue,
+        };
+        if chat_id.is_empty() || entries.iter().any(|e| e.chat_id == chat_id) {
+            continue;
+        }
+        entries.push(TelegramChannel {
+            account_id: m.get("accountId").and_then(Value::as_str).map(str::to_string),
+            chat_name: chat_id.clone(),
+            chat_id,
+        });
+    }
+    entries
+}
+
+/// Resolve Telegram chat names through `refresh_channel_names`, resolve
+/// chats known only from bindings, and persist the list to cache.
+#[tauri::command]
+pub async fn refresh_telegram_channels() -> Result<Vec<TelegramChannel>, String> {
+    let nodes = refresh_channel_names(vec!["telegram".into()]).await?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let paths = resolve_paths();
+        ensure_dirs(&paths)?;
+        let cfg = read_openclaw_config(&paths)?;
+        let mut entries = collect_telegram_chats(&cfg, &nodes);
+
+        let unresolved: Vec<String> = entries
+            .iter()
+            .filter(|e| e.chat_name == e.chat_id)
+            .map(|e| e.chat_id.clone())
+            .collect();
+        if !unresolved.is_empty() {
+            if let Ok(Some(name_map)) = resolve_channel_names_for_plugin("telegram", "auto", &unresolved) {
+                for entry in &mut entries {
+                    if let Some((true, Some(name), _)) = name_map.get(&entry.chat_id) {
+                        entry.chat_name = name.clone();
+                    }
+                }
+            }
+        }
+
+        let cache_file = paths.clawpal_dir.join("telegram-channels.json");
+        let json = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+        write_text(&cache_file, &json)?;
+
+        Ok(entries)
+    }).await.map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod telegram_channel_tests {
+    use super::*;
+
+    #[test]
+    fn collects_groups_accounts_and_bindings_once() {
+        let cfg = serde_json::json!({
+            "channels": { "telegram": {
+                "groups": { "-1001": { "name": "Ops" } },
+                "accounts": { "bot2": { "groups": { "-1002": {}, "-1001": {} } } }
+            }},
+            "bindings": [
+                { "agentId": "main", "match": { "channel": "telegram", "peer": { "kind": "group", "id": -1003 } } },
+                { "agentId": "main", "match": { "channel": "discord", "peer": { "id": "42" } } }
+            ]
+        });
+        let mut nodes = collect_channel_nodes(&cfg);
+        for node in &mut nodes {
+            node.display_name = channel_node_local_name(&cfg, &node.path);
+        }
+        let chats = collect_telegram_chats(&cfg, &nodes);
+        let ids: Vec<&str> = chats.iter().map(|c| c.chat_id.as_str()).collect();
+        assert_eq!(ids, vec!["-1001", "-1002", "-1003"]);
+        assert_eq!(chats[0].chat_name, "Ops");
+        assert_eq!(chats[0].account_id, None);
+        assert_eq!(chats[1].account_id.as_deref(), Some("bot2"));
+        assert_eq!(chats[2].chat_name, "-1003");
+    }
+}
+
 #[tauri::command]
 pub fn update_channel_config(
     path: String,
@@ -997,6 +2029,56 @@ pub fn delete_channel_node(path: String) -> Result<bool, String> {
     Ok(true)
 }
 
+/// Per-channel override keys; everything else (type, tokens, nested
+/// guilds/accounts) is part of the channel's identity and is kept.
+const CHANNEL_OVERRIDE_KEYS: &[&str] = &[
+    "model",
+    "mode",
+    "policy",
+    "dmPolicy",
+    "groupPolicy",
+    "allowlist",
+    "allowFrom",
+    "groupAllowFrom",
+    "users",
+];
+
+#[tauri::command]
+pub fn reset_channel_to_default(path: String) -> Result<ChannelNode, String> {
+    if path.trim().is_empty() {
+        return Err("channel path is required".into());
+    }
+    let paths = resolve_paths();
+    let mut cfg = read_openclaw_config(&paths)?;
+    if channel_lookup_node(&cfg, &path).and_then(Value::as_object).is_none() {
+        return Err(format!("channel not found: {path}"));
+    }
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+    let before = cfg.to_string();
+    for key in CHANNEL_OVERRIDE_KEYS {
+        set_nested_value(&mut cfg, &format!("{path}.{key}"), None)?;
+    }
+    if cfg.to_string() != before {
+        write_config_with_snapshot(&paths, &current, &cfg, "reset-channel")?;
+    }
+    let node = collect_channel_nodes(&cfg)
+        .into_iter()
+        .find(|node| node.path == path)
+        .unwrap_or_else(|| ChannelNode {
+            path: path.clone(),
+            channel_type: channel_lookup_node(&cfg, &path)
+                .and_then(Value::as_object)
+                .and_then(|obj| resolve_channel_type(&path, obj)),
+            mode: None,
+            allowlist: Vec::new(),
+            model: None,
+            has_model_field: false,
+            display_name: None,
+            name_status: None,
+        });
+    Ok(node)
+}
+
 #[tauri::command]
 pub fn set_global_model(model_value: Option<String>) -> Result<bool, String> {
     let paths = resolve_paths();
@@ -1052,59 +2134,307 @@ pub fn set_channel_model(path: String, model_value: Option<String>) -> Result<bo
     Ok(true)
 }
 
+/// Apply several `(channel_path, profile_id)` model assignments under a single
+/// snapshot. Every path is validated before anything is written, so one bad
+/// entry fails the whole batch. A `None` profile clears the channel model.
 #[tauri::command]
-pub fn list_model_bindings() -> Result<Vec<ModelBinding>, String> {
+pub fn set_channel_models(mappings: Vec<(String, Option<String>)>) -> Result<usize, String> {
+    if mappings.is_empty() {
+        return Ok(0);
+    }
     let paths = resolve_paths();
-    let cfg = read_openclaw_config(&paths)?;
+    let mut cfg = read_openclaw_config(&paths)?;
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
     let profiles = load_model_profiles(&paths);
-    Ok(collect_model_bindings(&cfg, &profiles))
-}
 
-#[tauri::command]
-pub async fn list_agents_overview(
-    cache: tauri::State<'_, crate::cli_runner::CliCache>,
-) -> Result<Vec<AgentOverview>, String> {
-    let cache_key = "local:agents-list";
-    if let Some(cached) = cache.get(cache_key, None) {
-        return serde_json::from_str(&cached).map_err(|e| e.to_string());
+    let mut resolved: HashMap<String, String> = HashMap::new();
+    let mut updates = Vec::with_capacity(mappings.len());
+    for (path, profile_id) in mappings {
+        let path = path.trim().to_string();
+        if path.is_empty() {
+            return Err("channel path is required".into());
+        }
+        if !channel_lookup_node(&cfg, &path).is_some_and(Value::is_object) {
+            return Err(format!("channel path not found: {path}"));
+        }
+        let profile_id = profile_id.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let value = match profile_id {
+            Some(id) => {
+                if !resolved.contains_key(&id) {
+                    let profile = profiles
+                        .iter()
+                        .find(|p| p.id == id)
+                        .ok_or_else(|| format!("model profile not found: {id}"))?;
+                    resolved.insert(id.clone(), profile_to_model_value(profile));
+                }
+                resolved.get(&id).cloned()
+            }
+            None => None,
+        };
+        updates.push((path, value));
     }
-    let cache = cache.inner().clone();
-    tauri::async_runtime::spawn_blocking(move || {
-        let output = crate::cli_runner::run_openclaw(&["agents", "list", "--json"])?;
-        let json = crate::cli_runner::parse_json_output(&output)?;
-        let result = parse_agents_cli_output(&json, None)?;
-        if let Ok(serialized) = serde_json::to_string(&result) {
-            cache.set(cache_key.to_string(), serialized);
-        }
-        Ok(result)
-    }).await.map_err(|e| e.to_string())?
-}
 
-/// Check if an agent has active sessions by examining sessions/sessions.json.
-/// Returns true if the file exists and is larger than 2 bytes (i.e. not just "{}").
-fn agent_has_sessions(base_dir: &std::path::Path, agent_id: &str) -> bool {
-    let sessions_file = base_dir.join("agents").join(agent_id).join("sessions").join("sessions.json");
-    match std::fs::metadata(&sessions_file) {
-        Ok(m) => m.len() > 2, // "{}" is 2 bytes = empty
-        Err(_) => false,
+    let mut changed = 0;
+    for (path, value) in updates {
+        let existing = channel_lookup_node(&cfg, &path)
+            .and_then(|node| node.get("model"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        if existing == value {
+            continue;
+        }
+        set_nested_value(&mut cfg, &format!("{path}.model"), value.map(Value::String))?;
+        changed += 1;
     }
+    if changed > 0 {
+        write_config_with_snapshot(&paths, &current, &cfg, "set-channel-models")?;
+    }
+    Ok(changed)
 }
 
-/// Parse the JSON output of `openclaw agents list --json` into Vec<AgentOverview>.
-/// `online_set`: if Some, use it to determine online status; if None, check local sessions.
-fn parse_agents_cli_output(json: &Value, online_set: Option<&std::collections::HashSet<String>>) -> Result<Vec<AgentOverview>, String> {
-    let arr = json.as_array().ok_or("agents list output is not an array")?;
-    let paths = if online_set.is_none() { Some(resolve_paths()) } else { None };
-    let mut agents = Vec::new();
-    for entry in arr {
-        let id = entry.get("id").and_then(Value::as_str).unwrap_or("main").to_string();
-        let name = entry.get("identityName").and_then(Value::as_str).map(|s| s.to_string());
-        let emoji = entry.get("identityEmoji").and_then(Value::as_str).map(|s| s.to_string());
+/// Config paths of the channel nodes that route to `agent_id` through the
+/// `bindings` array: the peer's own node for peer bindings, the guild node for
+/// guild bindings, and the whole platform only for platform-wide bindings.
+/// Peer and guild ids may be strings or numbers.
+fn channel_paths_bound_to_agent(cfg: &Value, agent_id: &str) -> Vec<String> {
+    let nodes = collect_channel_nodes(cfg);
+    let mut out: Vec<String> = Vec::new();
+    let bindings = cfg.get("bindings").and_then(Value::as_array).cloned().unwrap_or_default();
+    for binding in bindings {
+        if binding.get("agentId").and_then(Value::as_str) != Some(agent_id) {
+            continue;
+        }
+        let Some(platform) = binding.pointer("/match/channel").and_then(Value::as_str) else {
+            continue;
+        };
+        let platform_path = format!("channels.{platform}");
+        let id_at = |pointer: &str| match binding.pointer(pointer) {
+            Some(Value::String(s)) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+        let peer = id_at("/match/peer/id");
+        let guild = id_at("/match/guildId");
+        let in_platform = |n: &&ChannelNode| n.path.starts_with(&format!("{platform_path}."));
+        let path = match (peer, guild) {
+            (Some(peer), _) => nodes
+                .iter()
+                .filter(in_platform)
+                .find(|n| n.path.ends_with(&format!(".{peer}")))
+                .map(|n| n.path.clone()),
+            (None, Some(guild)) => nodes
+                .iter()
+                .filter(in_platform)
+                .find(|n| n.path.ends_with(&format!(".guilds.{guild}")))
+                .map(|n| n.path.clone()),
+            (None, None) => channel_lookup_node(cfg, &platform_path)
+                .filter(|v| v.is_object())
+                .map(|_| platform_path),
+        };
+        if let Some(path) = path {
+            if !out.contains(&path) {
+                out.push(path);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod channel_binding_tests {
+    use super::*;
+
+    #[test]
+    fn bound_channel_paths_match_numeric_ids_without_platform_fallback() {
+        let cfg = serde_json::json!({
+            "channels": {
+                "discord": {
+                    "token": "t",
+                    "guilds": { "42": { "channels": { "7": { "model": "a/b" } } } }
+                }
+            },
+            "bindings": [
+                { "agentId": "ops", "match": { "channel": "discord", "peer": { "id": 7 } } },
+                { "agentId": "ops", "match": { "channel": "discord", "guildId": 42 } },
+                { "agentId": "ops", "match": { "channel": "discord", "peer": { "id": "999" } } },
+                { "agentId": "main", "match": { "channel": "discord" } }
+            ]
+        });
+        assert_eq!(
+            channel_paths_bound_to_agent(&cfg, "ops"),
+            vec![
+                "channels.discord.guilds.42.channels.7".to_string(),
+                "channels.discord.guilds.42".to_string(),
+            ]
+        );
+        assert_eq!(channel_paths_bound_to_agent(&cfg, "main"), vec!["channels.discord".to_string()]);
+    }
+}
+
+/// Set one model on an agent and on every channel bound to it, under a single
+/// snapshot. Returns the scopes that changed (`agent:<id>`, channel paths).
+#[tauri::command]
+pub fn set_agent_and_channels_model(agent_id: String, profile_id: Option<String>) -> Result<Vec<String>, String> {
+    let agent_id = agent_id.trim().to_string();
+    if agent_id.is_empty() {
+        return Err("agent id is required".into());
+    }
+    let paths = resolve_paths();
+    let mut cfg = read_openclaw_config(&paths)?;
+    if !collect_agent_ids(&cfg).contains(&agent_id) {
+        return Err(format!("agent not found: {agent_id}"));
+    }
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+    let model = match profile_id.map(|v| v.trim().to_string()).filter(|v| !v.is_empty()) {
+        Some(id) => {
+            let profiles = load_model_profiles(&paths);
+            let profile = profiles
+                .iter()
+                .find(|p| p.id == id)
+                .ok_or_else(|| format!("model profile not found: {id}"))?;
+            Some(profile_to_model_value(profile))
+        }
+        None => None,
+    };
+
+    let channel_paths = channel_paths_bound_to_agent(&cfg, &agent_id);
+    set_agent_model_value(&mut cfg, &agent_id, model.clone())?;
+    let mut scopes = vec![format!("agent:{agent_id}")];
+    for path in channel_paths {
+        set_nested_value(&mut cfg, &format!("{path}.model"), model.clone().map(Value::String))?;
+        scopes.push(path);
+    }
+    write_config_with_snapshot(&paths, &current, &cfg, "set-agent-and-channels-model")?;
+    Ok(scopes)
+}
+
+#[tauri::command]
+pub fn list_model_bindings() -> Result<Vec<ModelBinding>, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    let profiles = load_model_profiles(&paths);
+    Ok(collect_model_bindings(&cfg, &profiles))
+}
+
+#[tauri::command]
+pub async fn list_agents_overview(
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
+) -> Result<Vec<AgentOverview>, String> {
+    let cache_key = "local:agents-list";
+    if let Some(cached) = cache.get(cache_key, None) {
+        return serde_json::from_str(&cached).map_err(|e| e.to_string());
+    }
+    let cache = cache.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let cli_json = crate::cli_runner::run_openclaw(&["agents", "list", "--json"])
+            .and_then(|output| crate::cli_runner::parse_json_output(&output));
+        let result = match cli_json {
+            Ok(json) => parse_agents_cli_output(&json, None)?,
+            Err(_) => agents_overview_from_config(&resolve_paths())?,
+        };
+        if let Ok(serialized) = serde_json::to_string(&result) {
+            cache.set(cache_key.to_string(), serialized);
+        }
+        Ok(result)
+    }).await.map_err(|e| e.to_string())?
+}
+
+/// Check if an agent has active sessions by examining sessions/sessions.json.
+/// Returns true if the file exists and is larger than 2 bytes (i.e. not just "{}").
+fn agent_has_sessions(base_dir: &std::path::Path, agent_id: &str) -> bool {
+    let sessions_file = base_dir.join("agents").join(agent_id).join("sessions").join("sessions.json");
+    match std::fs::metadata(&sessions_file) {
+        Ok(m) => m.len() > 2, // "{}" is 2 bytes = empty
+        Err(_) => false,
+    }
+}
+
+/// An agent counts as online if a session file changed within this window.
+const AGENT_ACTIVE_WINDOW_SECS: u64 = 5 * 60;
+
+/// Newest mtime (unix secs) among the agent's session files.
+fn agent_last_activity(base_dir: &std::path::Path, agent_id: &str) -> Option<u64> {
+    let sessions_dir = base_dir.join("agents").join(agent_id).join("sessions");
+    fs::read_dir(&sessions_dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.ends_with(".jsonl") || name == "sessions.json"
+        })
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .filter_map(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .max()
+}
+
+/// Liveness from the gateway's own report when the CLI includes one,
+/// otherwise from session activity within `AGENT_ACTIVE_WINDOW_SECS`.
+fn local_agent_liveness(entry: &Value, base_dir: &std::path::Path, agent_id: &str) -> (bool, Option<String>) {
+    let last_activity = agent_last_activity(base_dir, agent_id);
+    let last_active = last_activity.map(format_timestamp_from_unix);
+    let reported = ["active", "online", "running"]
+        .iter()
+        .find_map(|key| entry.get(*key).and_then(Value::as_bool));
+    let online = reported.unwrap_or_else(|| {
+        last_activity.is_some_and(|ts| unix_timestamp_secs().saturating_sub(ts) <= AGENT_ACTIVE_WINDOW_SECS)
+    });
+    (online, last_active)
+}
+
+/// Overview built straight from openclaw.json, used when the CLI query
+/// fails. Online status falls back to the sessions.json heuristic.
+fn agents_overview_from_config(paths: &crate::models::OpenClawPaths) -> Result<Vec<AgentOverview>, String> {
+    let cfg = read_openclaw_config(paths)?;
+    let list = cfg.pointer("/agents/list").and_then(Value::as_array).cloned().unwrap_or_default();
+    let mut agents: Vec<AgentOverview> = list
+        .iter()
+        .filter_map(|agent| {
+            let id = agent.get("id").and_then(Value::as_str)?.to_string();
+            Some(AgentOverview {
+                online: agent_has_sessions(&paths.base_dir, &id),
+                last_active: agent_last_activity(&paths.base_dir, &id).map(format_timestamp_from_unix),
+                name: None,
+                emoji: None,
+                model: agent.get("model").and_then(read_model_value),
+                channels: Vec::new(),
+                workspace: agent.get("workspace").and_then(Value::as_str).map(str::to_string),
+                id,
+            })
+        })
+        .collect();
+    if agents.is_empty() {
+        agents.push(AgentOverview {
+            id: "main".into(),
+            name: None,
+            emoji: None,
+            model: None,
+            channels: Vec::new(),
+            online: agent_has_sessions(&paths.base_dir, "main"),
+            workspace: None,
+            last_active: agent_last_activity(&paths.base_dir, "main").map(format_timestamp_from_unix),
+        });
+    }
+    Ok(agents)
+}
+
+/// Parse the JSON output of `openclaw agents list --json` into Vec<AgentOverview>.
+/// `online_set`: if Some, use it to determine online status; if None, check local
+/// session activity.
+fn parse_agents_cli_output(json: &Value, online_set: Option<&std::collections::HashSet<String>>) -> Result<Vec<AgentOverview>, String> {
+    let arr = json.as_array().ok_or("agents list output is not an array")?;
+    let paths = if online_set.is_none() { Some(resolve_paths()) } else { None };
+    let mut agents = Vec::new();
+    for entry in arr {
+        let id = entry.get("id").and_then(Value::as_str).unwrap_or("main").to_string();
+        let name = entry.get("identityName").and_then(Value::as_str).map(|s| s.to_string());
+        let emoji = entry.get("identityEmoji").and_then(Value::as_str).map(|s| s.to_string());
         let model = entry.get("model").and_then(Value::as_str).map(|s| s.to_string());
         let workspace = entry.get("workspace").and_then(Value::as_str).map(|s| s.to_string());
-        let online = match online_set {
-            Some(set) => set.contains(&id),
-            None => agent_has_sessions(paths.as_ref().unwrap().base_dir.as_path(), &id),
+        let (online, last_active) = match online_set {
+            Some(set) => (set.contains(&id), None),
+            None => local_agent_liveness(entry, paths.as_ref().unwrap().base_dir.as_path(), &id),
         };
         agents.push(AgentOverview {
             id,
@@ -1114,6 +2444,7 @@ fn parse_agents_cli_output(json: &Value, online_set: Option<&std::collections::H
             channels: Vec::new(),
             online,
             workspace,
+            last_active,
         });
     }
     if agents.is_empty() {
@@ -1125,17 +2456,13 @@ fn parse_agents_cli_output(json: &Value, online_set: Option<&std::collections::H
             channels: Vec::new(),
             online: false,
             workspace: None,
+            last_active: None,
         });
     }
     Ok(agents)
 }
 
-#[tauri::command]
-pub fn create_agent(
-    agent_id: String,
-    model_value: Option<String>,
-    independent: Option<bool>,
-) -> Result<AgentOverview, String> {
+fn validate_new_agent_id(agent_id: &str) -> Result<String, String> {
     let agent_id = agent_id.trim().to_string();
     if agent_id.is_empty() {
         return Err("Agent ID is required".into());
@@ -1143,6 +2470,16 @@ pub fn create_agent(
     if !agent_id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
         return Err("Agent ID may only contain letters, numbers, hyphens, and underscores".into());
     }
+    Ok(agent_id)
+}
+
+#[tauri::command]
+pub fn create_agent(
+    agent_id: String,
+    model_value: Option<String>,
+    independent: Option<bool>,
+) -> Result<AgentOverview, String> {
+    let agent_id = validate_new_agent_id(&agent_id)?;
 
     let paths = resolve_paths();
     let mut cfg = read_openclaw_config(&paths)?;
@@ -1202,11 +2539,313 @@ pub fn create_agent(
         channels: vec![],
         online: false,
         workspace,
+        last_active: None,
+    })
+}
+
+#[tauri::command]
+pub fn delete_agent(agent_id: String, purge_data: Option<bool>) -> Result<DeleteAgentResult, String> {
+    let agent_id = validate_deletable_agent_id(&agent_id)?;
+
+    let paths = resolve_paths();
+    let mut cfg = read_openclaw_config(&paths)?;
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+    remove_agent_from_config(&mut cfg, &agent_id)?;
+    write_config_with_snapshot(&paths, &current, &cfg, "delete-agent")?;
+
+    let mut result = DeleteAgentResult {
+        agent_id: agent_id.clone(),
+        config_removed: true,
+        purged_path: None,
+        purged_bytes: 0,
+    };
+    if purge_data.unwrap_or(false) {
+        let agents_root = paths.base_dir.join("agents");
+        let agent_dir = agents_root.join(&agent_id);
+        if agent_dir.is_dir() {
+            // Guard against symlinks pointing outside the agents directory.
+            let canonical_root = agents_root.canonicalize().map_err(|e| e.to_string())?;
+            let canonical_dir = agent_dir.canonicalize().map_err(|e| e.to_string())?;
+            if !canonical_dir.starts_with(&canonical_root) || canonical_dir == canonical_root {
+                return Err(format!("Refusing to purge path outside agents directory: {}", canonical_dir.display()));
+            }
+            result.purged_bytes = dir_size(&canonical_dir);
+            fs::remove_dir_all(&canonical_dir).map_err(|e| format!("Failed to remove agent data: {e}"))?;
+            result.purged_path = Some(canonical_dir.to_string_lossy().to_string());
+        }
+    }
+    Ok(result)
+}
+
+/// Copy of an agent entry under `new_id`, without the fields that must stay
+/// unique to the source: the `default` flag and its private `agentDir`.
+fn cloned_agent_entry(source: &Value, new_id: &str) -> Value {
+    let mut entry = source.clone();
+    if let Some(obj) = entry.as_object_mut() {
+        obj.remove("default");
+        obj.remove("agentDir");
+        obj.insert("id".into(), Value::String(new_id.to_string()));
+    }
+    entry
+}
+
+/// Duplicate an agent's config entry under `new_id`. With `copy_identity`,
+/// the clone gets its own workspace seeded with the source's IDENTITY.md.
+/// Sessions are never copied.
+#[tauri::command]
+pub fn clone_agent(source_id: String, new_id: String, copy_identity: bool) -> Result<AgentOverview, String> {
+    let source_id = source_id.trim().to_string();
+    let new_id = validate_new_agent_id(&new_id)?;
+
+    let paths = resolve_paths();
+    let mut cfg = read_openclaw_config(&paths)?;
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+    if collect_agent_ids(&cfg).iter().any(|id| id.eq_ignore_ascii_case(&new_id)) {
+        return Err(format!("Agent '{}' already exists", new_id));
+    }
+
+    let source_workspace = crate::doctor::agent_workspaces(&cfg)
+        .into_iter()
+        .find(|(id, _)| *id == source_id)
+        .and_then(|(_, ws)| ws.map(expand_tilde));
+    let list = cfg
+        .pointer_mut("/agents/list")
+        .and_then(Value::as_array_mut)
+        .ok_or("agents.list not found")?;
+    let mut entry = list
+        .iter()
+        .find(|agent| agent.get("id").and_then(Value::as_str) == Some(source_id.as_str()))
+        .map(|agent| cloned_agent_entry(agent, &new_id))
+        .ok_or_else(|| format!("Agent '{}' not found", source_id))?;
+
+    if copy_identity {
+        let ws_dir = paths.base_dir.join("workspaces").join(&new_id);
+        fs::create_dir_all(&ws_dir).map_err(|e| e.to_string())?;
+        if let Some(source_ws) = &source_workspace {
+            let identity = Path::new(source_ws).join("IDENTITY.md");
+            if identity.is_file() {
+                fs::copy(&identity, ws_dir.join("IDENTITY.md"))
+                    .map_err(|e| format!("Failed to copy IDENTITY.md: {e}"))?;
+            }
+        }
+        entry["workspace"] = Value::String(ws_dir.to_string_lossy().to_string());
+    }
+
+    let model = entry.get("model").and_then(read_model_value);
+    let workspace = entry.get("workspace").and_then(Value::as_str).map(str::to_string).or(source_workspace);
+    list.push(entry);
+    write_config_with_snapshot(&paths, &current, &cfg, "clone-agent")?;
+    Ok(AgentOverview {
+        id: new_id,
+        name: None,
+        emoji: None,
+        model,
+        channels: vec![],
+        online: false,
+        workspace,
+        last_active: None,
     })
 }
 
+#[cfg(test)]
+mod agent_clone_tests {
+    use super::*;
+
+    #[test]
+    fn clone_drops_default_flag_and_agent_dir() {
+        let source = serde_json::json!({
+            "id": "main",
+            "default": true,
+            "agentDir": "~/.openclaw/agents/main/agent",
+            "model": "anthropic/claude"
+        });
+        let clone = cloned_agent_entry(&source, "helper");
+        assert_eq!(clone["id"], "helper");
+        assert_eq!(clone["model"], "anthropic/claude");
+        assert!(clone.get("default").is_none());
+        assert!(clone.get("agentDir").is_none());
+    }
+}
+
+/// Rename an agent and repoint its bindings. With `move_data`, the
+/// `agents/<old_id>` data directory is renamed too. Returns whether data moved.
 #[tauri::command]
-pub fn delete_agent(agent_id: String) -> Result<bool, String> {
+pub fn rename_agent(old_id: String, new_id: String, move_data: Option<bool>) -> Result<bool, String> {
+    let old_id = old_id.trim().to_string();
+    if old_id == "main" {
+        return Err("Cannot rename the main agent".into());
+    }
+    let new_id = validate_new_agent_id(&new_id)?;
+
+    let paths = resolve_paths();
+    let agents_root = paths.base_dir.join("agents");
+    let old_dir = agents_root.join(&old_id);
+    let new_dir = agents_root.join(&new_id);
+    let move_data = move_data.unwrap_or(false) && old_dir.is_dir();
+    if move_data && new_dir.exists() {
+        return Err(format!("Data directory for '{}' already exists", new_id));
+    }
+
+    let mut cfg = read_openclaw_config(&paths)?;
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+    rename_agent_in_config(&mut cfg, &old_id, &new_id)?;
+    if move_data {
+        rebase_agent_dir(&mut cfg, &new_id, &old_dir, &new_dir);
+    }
+    write_config_with_snapshot(&paths, &current, &cfg, "rename-agent")?;
+
+    if move_data {
+        move_agent_data(&old_dir, &new_dir, &old_id, &new_id)?;
+    }
+    Ok(move_data)
+}
+
+/// Point the agent's `agentDir` at `new_dir` when it lives under the data
+/// directory being moved. Other locations are left alone.
+fn rebase_agent_dir(cfg: &mut Value, agent_id: &str, old_dir: &Path, new_dir: &Path) {
+    let Some(agent) = cfg
+        .pointer_mut("/agents/list")
+        .and_then(Value::as_array_mut)
+        .and_then(|list| list.iter_mut().find(|a| a.get("id").and_then(Value::as_str) == Some(agent_id)))
+    else {
+        return;
+    };
+    let Some(raw) = agent.get("agentDir").and_then(Value::as_str) else {
+        return;
+    };
+    let Ok(rest) = Path::new(&expand_tilde(raw)).strip_prefix(old_dir).map(Path::to_path_buf) else {
+        return;
+    };
+    let rebased = if rest.as_os_str().is_empty() {
+        new_dir.to_path_buf()
+    } else {
+        new_dir.join(rest)
+    };
+    let rebased = rebased.to_string_lossy().to_string();
+    let rebased = match std::env::var("HOME") {
+        Ok(home) if raw.starts_with("~/") => rebased
+            .strip_prefix(&home)
+            .map(|r| format!("~{r}"))
+            .unwrap_or(rebased),
+        _ => rebased,
+    };
+    agent["agentDir"] = Value::String(rebased);
+}
+
+/// Move an agent's data directory and re-key its session indexes from
+/// `agent:<old_id>:…` to `agent:<new_id>:…`.
+fn move_agent_data(old_dir: &Path, new_dir: &Path, old_id: &str, new_id: &str) -> Result<(), String> {
+    fs::rename(old_dir, new_dir).map_err(|e| format!("Renamed agent but failed to move its data: {e}"))?;
+    let old_prefix = format!("agent:{old_id}:");
+    for sub in ["sessions", "sessions_archive"] {
+        let index = new_dir.join(sub).join("sessions.json");
+        let Some(meta) = fs::read_to_string(&index)
+            .ok()
+            .and_then(|text| serde_json::from_str::<serde_json::Map<String, Value>>(&text).ok())
+        else {
+            continue;
+        };
+        let rekeyed: serde_json::Map<String, Value> = meta
+            .into_iter()
+            .map(|(key, val)| match key.strip_prefix(&old_prefix) {
+                Some(rest) => (format!("agent:{new_id}:{rest}"), val),
+                None => (key, val),
+            })
+            .collect();
+        let text = serde_json::to_string(&rekeyed).map_err(|e| e.to_string())?;
+        write_text(&index, &text)?;
+    }
+    Ok(())
+}
+
+fn rename_agent_in_config(cfg: &mut Value, old_id: &str, new_id: &str) -> Result<(), String> {
+    if collect_agent_ids(cfg).iter().any(|id| id.eq_ignore_ascii_case(new_id)) {
+        return Err(format!("Agent '{}' already exists", new_id));
+    }
+    let agent = cfg
+        .pointer_mut("/agents/list")
+        .and_then(Value::as_array_mut)
+        .ok_or("agents.list not found")?
+        .iter_mut()
+        .find(|agent| agent.get("id").and_then(Value::as_str) == Some(old_id))
+        .ok_or_else(|| format!("Agent '{}' not found", old_id))?;
+    agent["id"] = Value::String(new_id.to_string());
+
+    if let Some(bindings) = cfg.pointer_mut("/bindings").and_then(Value::as_array_mut) {
+        for b in bindings.iter_mut() {
+            if b.get("agentId").and_then(Value::as_str) == Some(old_id) {
+                b["agentId"] = Value::String(new_id.to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod agent_rename_tests {
+    use super::*;
+
+    #[test]
+    fn rename_rewrites_id_and_bindings() {
+        let mut cfg = serde_json::json!({
+            "agents": { "list": [{ "id": "main" }, { "id": "helper" }, { "id": "other" }] },
+            "bindings": [
+                { "agentId": "helper", "match": { "channel": "discord" } },
+                { "agentId": "other", "match": { "channel": "telegram" } }
+            ]
+        });
+        assert!(rename_agent_in_config(&mut cfg.clone(), "helper", "Other").is_err());
+        assert!(rename_agent_in_config(&mut cfg.clone(), "missing", "x").is_err());
+
+        rename_agent_in_config(&mut cfg, "helper", "assistant").unwrap();
+        assert_eq!(cfg["agents"]["list"][1]["id"], "assistant");
+        assert_eq!(cfg["bindings"][0]["agentId"], "assistant");
+        assert_eq!(cfg["bindings"][1]["agentId"], "other");
+    }
+
+    #[test]
+    fn moving_data_rebases_agent_dir_and_session_keys() {
+        let root = std::env::temp_dir().join(format!("clawpal-rename-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let old_dir = root.join("agents").join("helper");
+        let new_dir = root.join("agents").join("assistant");
+        fs::create_dir_all(old_dir.join("sessions")).unwrap();
+        fs::write(
+            old_dir.join("sessions").join("sessions.json"),
+            r#"{"agent:helper:main":{"sessionId":"s1"},"other":{"sessionId":"s2"}}"#,
+        )
+        .unwrap();
+
+        let mut cfg = serde_json::json!({ "agents": { "list": [
+            { "id": "assistant", "agentDir": old_dir.join("agent").to_string_lossy() },
+            { "id": "main", "agentDir": "/elsewhere/agent" }
+        ] } });
+        rebase_agent_dir(&mut cfg, "assistant", &old_dir, &new_dir);
+        rebase_agent_dir(&mut cfg, "main", &old_dir, &new_dir);
+        assert_eq!(cfg["agents"]["list"][0]["agentDir"], &*new_dir.join("agent").to_string_lossy());
+        assert_eq!(cfg["agents"]["list"][1]["agentDir"], "/elsewhere/agent");
+
+        move_agent_data(&old_dir, &new_dir, "helper", "assistant").unwrap();
+        assert!(!old_dir.exists());
+        let text = fs::read_to_string(new_dir.join("sessions").join("sessions.json")).unwrap();
+        let meta: Map<String, Value> = serde_json::from_str(&text).unwrap();
+        assert_eq!(meta["agent:assistant:main"]["sessionId"], "s1");
+        assert!(meta.contains_key("other") && !meta.contains_key("agent:helper:main"));
+        let _ = fs::remove_dir_all(&root);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAgentResult {
+    pub agent_id: String,
+    pub config_removed: bool,
+    /// Agent data directory that was removed, if `purge_data` was set.
+    pub purged_path: Option<String>,
+    pub purged_bytes: u64,
+}
+
+fn validate_deletable_agent_id(agent_id: &str) -> Result<String, String> {
     let agent_id = agent_id.trim().to_string();
     if agent_id.is_empty() {
         return Err("Agent ID is required".into());
@@ -1214,11 +2853,14 @@ pub fn delete_agent(agent_id: String) -> Result<bool, String> {
     if agent_id == "main" {
         return Err("Cannot delete the main agent".into());
     }
+    if agent_id.contains("..") || agent_id.contains('/') || agent_id.contains('\\') {
+        return Err("invalid agent id".into());
+    }
+    Ok(agent_id)
+}
 
-    let paths = resolve_paths();
-    let mut cfg = read_openclaw_config(&paths)?;
-    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
-
+/// Drop the agent from `agents.list` and point its bindings back at "main".
+fn remove_agent_from_config(cfg: &mut Value, agent_id: &str) -> Result<(), String> {
     let list = cfg
         .pointer_mut("/agents/list")
         .and_then(Value::as_array_mut)
@@ -1226,7 +2868,7 @@ pub fn delete_agent(agent_id: String) -> Result<bool, String> {
 
     let before = list.len();
     list.retain(|agent| {
-        agent.get("id").and_then(Value::as_str) != Some(&agent_id)
+        agent.get("id").and_then(Value::as_str) != Some(agent_id)
     });
 
     if list.len() == before {
@@ -1237,27 +2879,64 @@ pub fn delete_agent(agent_id: String) -> Result<bool, String> {
     // so the channel doesn't lose its binding entry entirely.
     if let Some(bindings) = cfg.pointer_mut("/bindings").and_then(Value::as_array_mut) {
         for b in bindings.iter_mut() {
-            if b.get("agentId").and_then(Value::as_str) == Some(&agent_id) {
+            if b.get("agentId").and_then(Value::as_str) == Some(agent_id) {
                 if let Some(obj) = b.as_object_mut() {
                     obj.insert("agentId".into(), Value::String("main".into()));
                 }
             }
         }
     }
-
-    write_config_with_snapshot(&paths, &current, &cfg, "delete-agent")?;
-    Ok(true)
+    Ok(())
 }
 
 #[tauri::command]
-pub fn setup_agent_identity(
+pub async fn remote_delete_agent(
+    pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
+    host_id: String,
     agent_id: String,
-    name: String,
-    emoji: Option<String>,
-) -> Result<bool, String> {
-    let agent_id = agent_id.trim().to_string();
-    let name = name.trim().to_string();
-    if agent_id.is_empty() {
+    purge_data: Option<bool>,
+) -> Result<DeleteAgentResult, String> {
+    let agent_id = validate_deletable_agent_id(&agent_id)?;
+
+    let raw = pool.sftp_read(&host_id, "~/.openclaw/openclaw.json").await?;
+    let mut cfg: Value = serde_json::from_str(&raw).map_err(|e| format!("Failed to parse config: {e}"))?;
+    remove_agent_from_config(&mut cfg, &agent_id)?;
+    remote_write_config_with_snapshot(&pool, &host_id, &raw, &cfg, "delete-agent").await?;
+    cache.invalidate(&remote_agents_overview_cache_key(&host_id));
+
+    let mut result = DeleteAgentResult {
+        agent_id: agent_id.clone(),
+        config_removed: true,
+        purged_path: None,
+        purged_bytes: 0,
+    };
+    if purge_data.unwrap_or(false) {
+        let dir = format!("\"$HOME/.openclaw/agents/\"{}", shell_escape(&agent_id));
+        let cmd = format!(
+            "d={dir}; if [ -d \"$d\" ] && [ ! -L \"$d\" ]; then du -sk \"$d\" 2>/dev/null | cut -f1; rm -rf \"$d\"; fi"
+        );
+        let out = pool.exec(&host_id, &cmd).await?;
+        if out.exit_code != 0 {
+            return Err(format!("Failed to remove agent data: {}", out.stderr.trim()));
+        }
+        if let Ok(kb) = out.stdout.trim().parse::<u64>() {
+            result.purged_bytes = kb * 1024;
+            result.purged_path = Some(format!("~/.openclaw/agents/{agent_id}"));
+        }
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn setup_agent_identity(
+    agent_id: String,
+    name: String,
+    emoji: Option<String>,
+) -> Result<bool, String> {
+    let agent_id = agent_id.trim().to_string();
+    let name = name.trim().to_string();
+    if agent_id.is_empty() {
         return Err("Agent ID is required".into());
     }
     if name.is_empty() {
@@ -1266,14 +2945,87 @@ pub fn setup_agent_identity(
 
     let paths = resolve_paths();
     let cfg = read_openclaw_config(&paths)?;
+    let workspace = local_agent_workspace(&cfg, &agent_id)?;
+
+    let ws_path = std::path::Path::new(&workspace);
+    fs::create_dir_all(ws_path).map_err(|e| format!("Failed to create workspace dir: {}", e))?;
+    let identity_path = ws_path.join("IDENTITY.md");
+
+    // Never clobber a hand-written file; merge the fields into it instead.
+    let existing = fs::read_to_string(&identity_path).unwrap_or_default();
+    let emoji = emoji.as_deref().map(str::trim).unwrap_or("");
+    let content = if existing.trim().is_empty() {
+        let mut content = format!("- Name: {}\n", name);
+        if !emoji.is_empty() {
+            content.push_str(&format!("- Emoji: {}\n", emoji));
+        }
+        content
+    } else {
+        crate::doctor::update_identity_content(&existing, Some(&name), Some(emoji).filter(|e| !e.is_empty()))
+    };
+    fs::write(&identity_path, &content)
+        .map_err(|e| format!("Failed to write IDENTITY.md: {}", e))?;
+
+    Ok(true)
+}
+
+/// Edit the Name/Emoji lines of an agent's IDENTITY.md, keeping every other
+/// line. `emoji: None` leaves it as is; an empty string removes it.
+#[tauri::command]
+pub fn update_agent_identity(agent_id: String, name: Option<String>, emoji: Option<String>) -> Result<bool, String> {
+    let agent_id = agent_id.trim().to_string();
+    if agent_id.is_empty() {
+        return Err("Agent ID is required".into());
+    }
+    let name = name.map(|n| n.trim().to_string());
+    if name.as_deref() == Some("") {
+        return Err("Name cannot be empty".into());
+    }
+
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    let workspace = local_agent_workspace(&cfg, &agent_id)?;
+    let ws_path = std::path::Path::new(&workspace);
+    fs::create_dir_all(ws_path).map_err(|e| format!("Failed to create workspace dir: {}", e))?;
+    let identity_path = ws_path.join("IDENTITY.md");
+
+    let existing = fs::read_to_string(&identity_path).unwrap_or_default();
+    let emoji = emoji.map(|e| e.trim().to_string());
+    let updated = crate::doctor::update_identity_content(&existing, name.as_deref(), emoji.as_deref());
+    if updated == existing {
+        return Ok(false);
+    }
+    fs::write(&identity_path, &updated)
+        .map_err(|e| format!("Failed to write IDENTITY.md: {}", e))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod identity_update_tests {
+    use crate::doctor::{parse_identity_content, update_identity_content};
+
+    #[test]
+    fn update_keeps_other_lines() {
+        let text = "# Persona\n- Name: Old\nYou are terse.\n";
+        let updated = update_identity_content(text, Some("New"), Some("🦀"));
+        assert_eq!(updated, "# Persona\n- Name: New\n- Emoji: 🦀\nYou are terse.\n");
+        assert_eq!(
+            parse_identity_content(&updated),
+            (Some("New".into()), Some("🦀".into()))
+        );
+        let removed = update_identity_content(&updated, None, Some(""));
+        assert_eq!(removed, "# Persona\n- Name: New\nYou are terse.\n");
+    }
+}
 
-    // Find the agent's workspace
+/// Expanded workspace path of a configured agent (own or inherited default).
+fn local_agent_workspace(cfg: &Value, agent_id: &str) -> Result<String, String> {
     let agents_list = cfg.pointer("/agents/list")
         .and_then(Value::as_array)
         .ok_or("agents.list not found")?;
 
     let agent = agents_list.iter()
-        .find(|a| a.get("id").and_then(Value::as_str) == Some(&agent_id))
+        .find(|a| a.get("id").and_then(Value::as_str) == Some(agent_id))
         .ok_or_else(|| format!("Agent '{}' not found", agent_id))?;
 
     let default_workspace = cfg.pointer("/agents/defaults/workspace")
@@ -1281,33 +3033,17 @@ pub fn setup_agent_identity(
         .and_then(Value::as_str)
         .map(|s| expand_tilde(s));
 
-    let workspace = agent.get("workspace")
+    agent.get("workspace")
         .and_then(Value::as_str)
         .map(|s| expand_tilde(s))
         .or(default_workspace)
-        .ok_or_else(|| format!("Agent '{}' has no workspace configured", agent_id))?;
-
-    // Build IDENTITY.md content
-    let mut content = format!("- Name: {}\n", name);
-    if let Some(ref e) = emoji {
-        let e = e.trim();
-        if !e.is_empty() {
-            content.push_str(&format!("- Emoji: {}\n", e));
-        }
-    }
-
-    let ws_path = std::path::Path::new(&workspace);
-    fs::create_dir_all(ws_path).map_err(|e| format!("Failed to create workspace dir: {}", e))?;
-    let identity_path = ws_path.join("IDENTITY.md");
-    fs::write(&identity_path, &content)
-        .map_err(|e| format!("Failed to write IDENTITY.md: {}", e))?;
-
-    Ok(true)
+        .ok_or_else(|| format!("Agent '{}' has no workspace configured", agent_id))
 }
 
 #[tauri::command]
 pub async fn remote_setup_agent_identity(
     pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
     host_id: String,
     agent_id: String,
     name: String,
@@ -1343,24 +3079,43 @@ pub async fn remote_setup_agent_identity(
         .and_then(Value::as_str)
         .unwrap_or(default_workspace);
 
-    // Build IDENTITY.md content
-    let mut content = format!("- Name: {}\n", name);
-    if let Some(ref e) = emoji {
-        let e = e.trim();
-        if !e.is_empty() {
-            content.push_str(&format!("- Emoji: {}\n", e));
-        }
-    }
-
     // Write via SSH
     let ws = if workspace.starts_with("~/") { workspace.to_string() } else { format!("~/{workspace}") };
     pool.exec(&host_id, &format!("mkdir -p {}", shell_escape(&ws))).await?;
     let identity_path = format!("{}/IDENTITY.md", ws);
+
+    // Build IDENTITY.md content, merging into any existing hand-written file
+    let existing = pool.sftp_read(&host_id, &identity_path).await.unwrap_or_default();
+    let emoji = emoji.as_deref().map(str::trim).unwrap_or("");
+    let content = if existing.trim().is_empty() {
+        let mut content = format!("- Name: {}\n", name);
+        if !emoji.is_empty() {
+            content.push_str(&format!("- Emoji: {}\n", emoji));
+        }
+        content
+    } else {
+        crate::doctor::update_identity_content(&existing, Some(&name), Some(emoji).filter(|e| !e.is_empty()))
+    };
     pool.sftp_write(&host_id, &identity_path, &content).await?;
+    cache.invalidate(&remote_agents_overview_cache_key(&host_id));
 
     Ok(true)
 }
 
+#[tauri::command]
+pub fn audit_workspace_locations() -> Result<Vec<crate::doctor::WorkspaceLocationIssue>, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    Ok(crate::doctor::audit_workspace_locations(&cfg, &paths.base_dir))
+}
+
+#[tauri::command]
+pub fn validate_agent_identities() -> Result<Vec<crate::doctor::IdentityCheck>, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    Ok(crate::doctor::check_agent_identities(&cfg))
+}
+
 fn expand_tilde(path: &str) -> String {
     if path.starts_with("~/") {
         if let Some(home) = std::env::var("HOME").ok() {
@@ -1377,9 +3132,16 @@ pub fn list_session_files() -> Result<Vec<SessionFile>, String> {
 }
 
 #[tauri::command]
-pub fn clear_all_sessions() -> Result<usize, String> {
+pub fn clear_all_sessions(use_trash: Option<bool>) -> Result<usize, String> {
     let paths = resolve_paths();
-    clear_agent_and_global_sessions(&paths.base_dir.join("agents"), None)
+    let agents_root = paths.base_dir.join("agents");
+    if use_trash.unwrap_or(false) {
+        let mut trash = SessionTrash::create(&paths, "clear-all-sessions")?;
+        let moved = trash_agent_sessions(&agents_root, &mut trash)?;
+        trash.finish()?;
+        return Ok(moved);
+    }
+    clear_agent_and_global_sessions(&agents_root, None)
 }
 
 #[tauri::command]
@@ -1391,6 +3153,53 @@ pub async fn analyze_sessions() -> Result<Vec<AgentSessionAnalysis>, String> {
     .map_err(|e| e.to_string())?
 }
 
+/// Number of characters of user/assistant text in a JSONL session record.
+/// Content is either a plain string or an array of `{type: "text", text}` parts.
+fn message_text_chars(obj: &Value) -> usize {
+    if obj.get("type").and_then(Value::as_str) != Some("message") {
+        return 0;
+    }
+    let role = obj.pointer("/message/role").and_then(Value::as_str);
+    if !matches!(role, Some("user") | Some("assistant")) {
+        return 0;
+    }
+    match obj.pointer("/message/content") {
+        Some(Value::String(text)) => text.chars().count(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|p| p.get("text").and_then(Value::as_str))
+            .map(|t| t.chars().count())
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// Rough token estimate (~4 characters per token).
+fn estimate_tokens_from_chars(chars: usize) -> u64 {
+    (chars as u64).div_ceil(4)
+}
+
+#[cfg(test)]
+mod session_token_estimate_tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens_from_sample_jsonl() {
+        let jsonl = r#"{"type":"session","id":"abc"}
+{"type":"message","message":{"role":"user","content":"hello there!"}}
+{"type":"message","message":{"role":"assistant","content":[{"type":"text","text":"hi"},{"type":"toolCall","name":"x"}]}}
+{"type":"message","message":{"role":"toolResult","content":"ignored output"}}"#;
+        let chars: usize = jsonl
+            .lines()
+            .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+            .map(|v| message_text_chars(&v))
+            .sum();
+        assert_eq!(chars, 14);
+        assert_eq!(estimate_tokens_from_chars(chars), 4);
+        assert_eq!(estimate_tokens_from_chars(0), 0);
+    }
+}
+
 fn analyze_sessions_sync() -> Result<Vec<AgentSessionAnalysis>, String> {
     let paths = resolve_paths();
     let agents_root = paths.base_dir.join("agents");
@@ -1414,13 +3223,7 @@ fn analyze_sessions_sync() -> Result<Vec<AgentSessionAnalysis>, String> {
         let agent = entry.file_name().to_string_lossy().to_string();
 
         // Load sessions.json metadata for this agent
-        let sessions_json_path = entry_path.join("sessions").join("sessions.json");
-        let sessions_meta: HashMap<String, Value> = if sessions_json_path.exists() {
-            let text = fs::read_to_string(&sessions_json_path).unwrap_or_default();
-            serde_json::from_str(&text).unwrap_or_default()
-        } else {
-            HashMap::new()
-        };
+        let sessions_meta = read_sessions_meta(&entry_path);
 
         // Build sessionId -> metadata lookup
         let mut meta_by_id: HashMap<String, &Value> = HashMap::new();
@@ -1462,6 +3265,7 @@ fn analyze_sessions_sync() -> Result<Vec<AgentSessionAnalysis>, String> {
                 let mut user_message_count = 0usize;
                 let mut assistant_message_count = 0usize;
                 let mut last_activity: Option<String> = None;
+                let mut text_chars = 0usize;
 
                 if let Ok(file) = fs::File::open(&file_path) {
                     let reader = BufReader::new(file);
@@ -1479,6 +3283,7 @@ fn analyze_sessions_sync() -> Result<Vec<AgentSessionAnalysis>, String> {
                         };
                         if obj.get("type").and_then(Value::as_str) == Some("message") {
                             message_count += 1;
+                            text_chars += message_text_chars(&obj);
                             if let Some(ts) = obj.get("timestamp").and_then(Value::as_str) {
                                 last_activity = Some(ts.to_string());
                             }
@@ -1501,10 +3306,14 @@ fn analyze_sessions_sync() -> Result<Vec<AgentSessionAnalysis>, String> {
                 };
                 let meta = meta_by_id.get(base_id);
 
-                let total_tokens = meta
+                let mut total_tokens = meta
                     .and_then(|m| m.get("totalTokens"))
                     .and_then(Value::as_u64)
                     .unwrap_or(0);
+                let tokens_estimated = total_tokens == 0 && message_count > 0;
+                if tokens_estimated {
+                    total_tokens = estimate_tokens_from_chars(text_chars);
+                }
                 let model = meta
                     .and_then(|m| m.get("model"))
                     .and_then(Value::as_str)
@@ -1544,9 +3353,16 @@ fn analyze_sessions_sync() -> Result<Vec<AgentSessionAnalysis>, String> {
                     last_activity,
                     age_days,
                     total_tokens,
+                    tokens_estimated,
                     model,
                     category: category.to_string(),
                     kind: kind_name.to_string(),
+                    mtime: metadata
+                        .modified()
+                        .ok()
+                        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0),
                 });
             }
         }
@@ -1586,15 +3402,262 @@ fn analyze_sessions_sync() -> Result<Vec<AgentSessionAnalysis>, String> {
 }
 
 #[tauri::command]
-pub async fn delete_sessions_by_ids(agent_id: String, session_ids: Vec<String>) -> Result<usize, String> {
+pub async fn delete_sessions_by_ids(
+    agent_id: String,
+    session_ids: Vec<String>,
+    use_trash: Option<bool>,
+) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let paths = resolve_paths();
+        if use_trash.unwrap_or(false) {
+            let mut trash = SessionTrash::create(&paths, "delete-sessions")?;
+            let deleted = delete_sessions_by_ids_sync(&agent_id, &session_ids, Some(&mut trash))?;
+            trash.finish()?;
+            Ok(deleted)
+        } else {
+            delete_sessions_by_ids_sync(&agent_id, &session_ids, None)
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Reject empty ids and ids that could escape the agent's session dirs.
+fn validate_session_move_ids(agent_id: &str, session_id: &str) -> Result<(), String> {
+    if agent_id.trim().is_empty() || session_id.trim().is_empty() {
+        return Err("agent id and session id are required".into());
+    }
+    for id in [agent_id, session_id] {
+        if id.contains("..") || id.contains('/') || id.contains('\\') {
+            return Err("invalid agent or session id".into());
+        }
+    }
+    Ok(())
+}
+
+/// Source and destination session dir names for an (un)archive move.
+fn session_move_dirs(archive: bool) -> (&'static str, &'static str) {
+    if archive {
+        ("sessions", "sessions_archive")
+    } else {
+        ("sessions_archive", "sessions")
+    }
+}
+
+/// Move the `sessions.json`-style metadata entries for `session_id` from one
+/// map to the other. Returns whether anything moved.
+fn move_session_meta(
+    from: &mut serde_json::Map<String, Value>,
+    to: &mut serde_json::Map<String, Value>,
+    session_id: &str,
+) -> bool {
+    let keys: Vec<String> = from
+        .iter()
+        .filter(|(_, val)| val.get("sessionId").and_then(Value::as_str) == Some(session_id))
+        .map(|(key, _)| key.clone())
+        .collect();
+    for key in &keys {
+        if let Some(val) = from.remove(key) {
+            to.insert(key.clone(), val);
+        }
+    }
+    !keys.is_empty()
+}
+
+/// Move a session transcript (plus topic files) between `sessions` and
+/// `sessions_archive`, carrying its metadata entry along.
+fn move_session_sync(agent_id: &str, session_id: &str, archive: bool) -> Result<bool, String> {
+    validate_session_move_ids(agent_id, session_id)?;
+    let paths = resolve_paths();
+    let agent_dir = paths.base_dir.join("agents").join(agent_id);
+    let (from_name, to_name) = session_move_dirs(archive);
+    let from_dir = agent_dir.join(from_name);
+    let to_dir = agent_dir.join(to_name);
+
+    let main_file = format!("{session_id}.jsonl");
+    if !from_dir.join(&main_file).exists() {
+        return Err(format!("Session '{}' not found in {}", session_id, from_name));
+    }
+    if to_dir.join(&main_file).exists() {
+        return Err(format!("Session '{}' already exists in {}", session_id, to_name));
+    }
+    fs::create_dir_all(&to_dir).map_err(|e| e.to_string())?;
+
+    let entries = fs::read_dir(&from_dir).map_err(|e| e.to_string())?;
+    for entry in entries.flatten() {
+        let fname = entry.file_name().to_string_lossy().to_string();
+        let related = fname == main_file || fname.starts_with(&format!("{session_id}-topic-"));
+        if related {
+            fs::rename(entry.path(), to_dir.join(&fname)).map_err(|e| format!("Failed to move {fname}: {e}"))?;
+        }
+    }
+
+    let read_meta = |dir: &Path| -> serde_json::Map<String, Value> {
+        fs::read_to_string(dir.join("sessions.json"))
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    };
+    let mut from_meta = read_meta(&from_dir);
+    let mut to_meta = read_meta(&to_dir);
+    if move_session_meta(&mut from_meta, &mut to_meta, session_id) {
+        for (dir, meta) in [(&from_dir, &from_meta), (&to_dir, &to_meta)] {
+            let text = serde_json::to_string(meta).map_err(|e| e.to_string())?;
+            write_text(&dir.join("sessions.json"), &text)?;
+        }
+    }
+    Ok(true)
+}
+
+/// Move a session out of the active list into `sessions_archive`.
+#[tauri::command]
+pub fn archive_session(agent_id: String, session_id: String) -> Result<bool, String> {
+    move_session_sync(&agent_id, &session_id, true)
+}
+
+/// Move an archived session back into the active `sessions` dir.
+#[tauri::command]
+pub fn unarchive_session(agent_id: String, session_id: String) -> Result<bool, String> {
+    move_session_sync(&agent_id, &session_id, false)
+}
+
+#[cfg(test)]
+mod session_archive_tests {
+    use super::*;
+
+    #[test]
+    fn moves_only_matching_metadata() {
+        let mut from: serde_json::Map<String, Value> = serde_json::from_value(serde_json::json!({
+            "agent:main:a": { "sessionId": "s1" },
+            "agent:main:b": { "sessionId": "s2" }
+        }))
+        .unwrap();
+        let mut to = serde_json::Map::new();
+        assert!(move_session_meta(&mut from, &mut to, "s1"));
+        assert!(from.contains_key("agent:main:b") && !from.contains_key("agent:main:a"));
+        assert_eq!(to["agent:main:a"]["sessionId"], "s1");
+        assert!(!move_session_meta(&mut from, &mut to, "missing"));
+        assert!(validate_session_move_ids("main", "../x").is_err());
+    }
+}
+
+async fn remote_move_session(
+    pool: &SshConnectionPool,
+    host_id: &str,
+    agent_id: &str,
+    session_id: &str,
+    archive: bool,
+) -> Result<bool, String> {
+    validate_session_move_ids(agent_id, session_id)?;
+    let (from_name, to_name) = session_move_dirs(archive);
+    let agent_dir = format!("~/.openclaw/agents/{agent_id}");
+    let cmd = format!(
+        concat!(
+            "set -e; cd ~/.openclaw/agents/{agent}; ",
+            "[ -f {from}/{sid}.jsonl ] || {{ echo 'Session not found' >&2; exit 1; }}; ",
+            "[ ! -e {to}/{sid}.jsonl ] || {{ echo 'Session already exists in destination' >&2; exit 1; }}; ",
+            "mkdir -p {to}; ",
+            "mv {from}/{sid}.jsonl {to}/; ",
+            "for f in {from}/{sid}-topic-*; do [ -e \"$f\" ] && mv \"$f\" {to}/; done; true"
+        ),
+        agent = shell_escape(agent_id),
+        from = from_name,
+        to = to_name,
+        sid = shell_escape(session_id),
+    );
+    let result = pool.exec(host_id, &cmd).await?;
+    if result.exit_code != 0 {
+        return Err(format!("Failed to move session: {}", result.stderr.trim()));
+    }
+
+    let from_path = format!("{agent_dir}/{from_name}/sessions.json");
+    let to_path = format!("{agent_dir}/{to_name}/sessions.json");
+    let parse = |text: Result<String, String>| -> serde_json::Map<String, Value> {
+        text.ok().and_then(|t| serde_json::from_str(&t).ok()).unwrap_or_default()
+    };
+    let mut from_meta = parse(pool.sftp_read(host_id, &from_path).await);
+    let mut to_meta = parse(pool.sftp_read(host_id, &to_path).await);
+    if move_session_meta(&mut from_meta, &mut to_meta, session_id) {
+        for (path, meta) in [(&from_path, &from_meta), (&to_path, &to_meta)] {
+            let text = serde_json::to_string(meta).map_err(|e| e.to_string())?;
+            pool.sftp_write(host_id, path, &text).await?;
+        }
+    }
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn remote_archive_session(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    agent_id: String,
+    session_id: String,
+) -> Result<bool, String> {
+    remote_move_session(&pool, &host_id, &agent_id, &session_id, true).await
+}
+
+#[tauri::command]
+pub async fn remote_unarchive_session(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    agent_id: String,
+    session_id: String,
+) -> Result<bool, String> {
+    remote_move_session(&pool, &host_id, &agent_id, &session_id, false).await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCleanupResult {
+    pub deleted: usize,
+    pub bytes_reclaimed: u64,
+}
+
+/// Delete every session of an agent that `analyze_sessions_sync` puts in
+/// `category` ("empty" or "low_value"). "valuable" is always refused.
+#[tauri::command]
+pub async fn delete_sessions_by_category(
+    agent_id: String,
+    category: String,
+    use_trash: Option<bool>,
+) -> Result<SessionCleanupResult, String> {
+    if category != "empty" && category != "low_value" {
+        return Err(format!("refusing to bulk-delete category '{category}' (allowed: empty, low_value)"));
+    }
     tauri::async_runtime::spawn_blocking(move || {
-        delete_sessions_by_ids_sync(&agent_id, &session_ids)
+        let matched: Vec<SessionAnalysis> = analyze_sessions_sync()?
+            .into_iter()
+            .filter(|a| a.agent == agent_id)
+            .flat_map(|a| a.sessions)
+            .filter(|s| s.category == category)
+            .collect();
+        if matched.is_empty() {
+            return Ok(SessionCleanupResult { deleted: 0, bytes_reclaimed: 0 });
+        }
+        let ids: Vec<String> = matched.iter().map(|s| s.session_id.clone()).collect();
+        let bytes_reclaimed = matched.iter().map(|s| s.size_bytes).sum();
+
+        let paths = resolve_paths();
+        let deleted = if use_trash.unwrap_or(false) {
+            let mut trash = SessionTrash::create(&paths, "delete-sessions")?;
+            let deleted = delete_sessions_by_ids_sync(&agent_id, &ids, Some(&mut trash))?;
+            trash.finish()?;
+            deleted
+        } else {
+            delete_sessions_by_ids_sync(&agent_id, &ids, None)?
+        };
+        Ok(SessionCleanupResult { deleted, bytes_reclaimed })
     })
     .await
     .map_err(|e| e.to_string())?
 }
 
-fn delete_sessions_by_ids_sync(agent_id: &str, session_ids: &[String]) -> Result<usize, String> {
+/// Delete session files, or move them into `trash` when one is given.
+fn delete_sessions_by_ids_sync(
+    agent_id: &str,
+    session_ids: &[String],
+    mut trash: Option<&mut SessionTrash>,
+) -> Result<usize, String> {
     if agent_id.trim().is_empty() {
         return Err("agent id is required".into());
     }
@@ -1620,7 +3683,11 @@ fn delete_sessions_by_ids_sync(agent_id: &str, session_ids: &[String]) -> Result
             }
             let jsonl_path = dir.join(format!("{}.jsonl", sid));
             if jsonl_path.exists() {
-                if fs::remove_file(&jsonl_path).is_ok() {
+                let removed = match trash.as_deref_mut() {
+                    Some(trash) => trash.discard(&jsonl_path).is_ok(),
+                    None => fs::remove_file(&jsonl_path).is_ok(),
+                };
+                if removed {
                     deleted += 1;
                 }
             }
@@ -1629,7 +3696,10 @@ fn delete_sessions_by_ids_sync(agent_id: &str, session_ids: &[String]) -> Result
                 for entry in entries.flatten() {
                     let fname = entry.file_name().to_string_lossy().to_string();
                     if fname.starts_with(sid.as_str()) && fname != format!("{}.jsonl", sid) {
-                        let _ = fs::remove_file(entry.path());
+                        match trash.as_deref_mut() {
+                            Some(trash) => { let _ = trash.discard(&entry.path()); }
+                            None => { let _ = fs::remove_file(entry.path()); }
+                        }
                     }
                 }
             }
@@ -1642,9 +3712,15 @@ fn delete_sessions_by_ids_sync(agent_id: &str, session_ids: &[String]) -> Result
         if let Ok(text) = fs::read_to_string(&sessions_json_path) {
             if let Ok(mut data) = serde_json::from_str::<serde_json::Map<String, Value>>(&text) {
                 let id_set: HashSet<&str> = session_ids.iter().map(String::as_str).collect();
-                data.retain(|_key, val| {
+                data.retain(|key, val| {
                     let sid = val.get("sessionId").and_then(Value::as_str).unwrap_or("");
-                    !id_set.contains(sid)
+                    let keep = !id_set.contains(sid);
+                    if !keep {
+                        if let Some(trash) = trash.as_deref_mut() {
+                            trash.record_meta(agent_id, key, val.clone());
+                        }
+                    }
+                    keep
                 });
                 let _ = fs::write(&sessions_json_path, serde_json::to_string(&data).unwrap_or_default());
             }
@@ -1655,2129 +3731,5353 @@ fn delete_sessions_by_ids_sync(agent_id: &str, session_ids: &[String]) -> Result
 }
 
 #[tauri::command]
-pub async fn preview_session(agent_id: String, session_id: String) -> Result<Vec<Value>, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        preview_session_sync(&agent_id, &session_id)
-    })
-    .await
-    .map_err(|e| e.to_string())?
-}
-
-fn preview_session_sync(agent_id: &str, session_id: &str) -> Result<Vec<Value>, String> {
-    if agent_id.contains("..") || agent_id.contains('/') || agent_id.contains('\\') {
-        return Err("invalid agent id".into());
-    }
-    if session_id.contains("..") || session_id.contains('/') || session_id.contains('\\') {
-        return Err("invalid session id".into());
+pub fn session_consistency_check(agent_id: Option<String>) -> Result<Vec<crate::doctor::SessionConsistency>, String> {
+    if let Some(ref id) = agent_id {
+        if id.contains("..") || id.contains('/') || id.contains('\\') {
+            return Err("invalid agent id".into());
+        }
     }
     let paths = resolve_paths();
-    let agent_dir = paths.base_dir.join("agents").join(agent_id);
-    let jsonl_name = format!("{}.jsonl", session_id);
+    Ok(crate::doctor::check_session_consistency(
+        &paths.base_dir.join("agents"),
+        agent_id.as_deref(),
+    ))
+}
 
-    // Search in both sessions and sessions_archive
-    let file_path = ["sessions", "sessions_archive"]
-        .iter()
-        .map(|dir| agent_dir.join(dir).join(&jsonl_name))
-        .find(|p| p.exists());
+// ---- Session locks ----
 
-    let file_path = match file_path {
-        Some(p) => p,
-        None => return Ok(Vec::new()),
-    };
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLock {
+    pub agent: String,
+    pub session_id: String,
+    /// "sessions" or "archive"
+    pub kind: String,
+    pub lock_file: String,
+    pub age_seconds: u64,
+    /// PID recorded in the lock file, if any.
+    pub pid: Option<u32>,
+    /// Whether that PID is running; `None` without a PID or on platforms
+    /// where liveness can't be probed, where the lock counts as stale.
+    pub pid_alive: Option<bool>,
+}
+
+/// Session id for a lock file name: `<sid>.jsonl.lock` or `<sid>.lock`.
+fn session_id_from_lock_name(name: &str) -> Option<&str> {
+    let stem = name.strip_suffix(".lock")?;
+    let sid = stem.strip_suffix(".jsonl").unwrap_or(stem);
+    (!sid.is_empty()).then_some(sid)
+}
+
+/// Lock files are either a bare PID or JSON with a `pid` field.
+fn parse_lock_pid(content: &str) -> Option<u32> {
+    let trimmed = content.trim();
+    if let Ok(pid) = trimmed.parse::<u32>() {
+        return Some(pid);
+    }
+    serde_json::from_str::<Value>(trimmed)
+        .ok()?
+        .get("pid")?
+        .as_u64()
+        .and_then(|p| u32::try_from(p).ok())
+}
+
+#[cfg(unix)]
+fn pid_is_alive(pid: u32) -> Option<bool> {
+    Some(
+        std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false),
+    )
+}
 
-    let file = fs::File::open(&file_path).map_err(|e| e.to_string())?;
-    let reader = BufReader::new(file);
-    let mut messages: Vec<Value> = Vec::new();
+#[cfg(not(unix))]
+fn pid_is_alive(_pid: u32) -> Option<bool> {
+    None
+}
 
-    for line in reader.lines() {
-        let line = match line {
-            Ok(l) => l,
-            Err(_) => continue,
-        };
-        if line.trim().is_empty() {
+fn collect_session_locks(agents_root: &Path, agent_filter: Option<&str>) -> Vec<SessionLock> {
+    let mut out = Vec::new();
+    let Ok(agents) = fs::read_dir(agents_root) else {
+        return out;
+    };
+    let now = std::time::SystemTime::now();
+    for agent_entry in agents.flatten() {
+        let agent = agent_entry.file_name().to_string_lossy().to_string();
+        if agent_filter.is_some_and(|f| f != agent) {
             continue;
         }
-        let obj: Value = match serde_json::from_str(&line) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        if obj.get("type").and_then(Value::as_str) == Some("message") {
-            let role = obj.pointer("/message/role").and_then(Value::as_str).unwrap_or("unknown");
-            let content = obj.pointer("/message/content")
-                .map(|c| {
-                    if let Some(arr) = c.as_array() {
-                        arr.iter()
-                            .filter_map(|item| item.get("text").and_then(Value::as_str))
-                            .collect::<Vec<_>>()
-                            .join("\n")
-                    } else if let Some(s) = c.as_str() {
-                        s.to_string()
-                    } else {
-                        String::new()
-                    }
-                })
-                .unwrap_or_default();
-            messages.push(serde_json::json!({
-                "role": role,
-                "content": content,
-            }));
+        for (kind, dir_name) in [("sessions", "sessions"), ("archive", "sessions_archive")] {
+            let Ok(entries) = fs::read_dir(agent_entry.path().join(dir_name)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Some(sid) = session_id_from_lock_name(&name) else {
+                    continue;
+                };
+                let age_seconds = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|m| now.duration_since(m).ok())
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                let pid = fs::read_to_string(entry.path())
+                    .ok()
+                    .and_then(|c| parse_lock_pid(&c));
+                out.push(SessionLock {
+                    agent: agent.clone(),
+                    session_id: sid.to_string(),
+                    kind: kind.to_string(),
+                    lock_file: entry.path().to_string_lossy().to_string(),
+                    age_seconds,
+                    pid,
+                    pid_alive: pid.and_then(pid_is_alive),
+                });
+            }
         }
     }
-
-    Ok(messages)
+    out.sort_by(|a, b| b.age_seconds.cmp(&a.age_seconds));
+    out
 }
 
+/// List session lock files, oldest first. A lock whose owning process is
+/// gone usually means a crash left the session stuck.
 #[tauri::command]
-pub fn list_recipes(source: Option<String>) -> Result<Vec<crate::recipe::Recipe>, String> {
+pub fn list_locked_sessions(agent_id: Option<String>) -> Result<Vec<SessionLock>, String> {
+    if let Some(ref id) = agent_id {
+        if id.contains("..") || id.contains('/') || id.contains('\\') {
+            return Err("invalid agent id".into());
+        }
+    }
     let paths = resolve_paths();
-    let default_path = paths.clawpal_dir.join("recipes").join("recipes.json");
-    Ok(load_recipes_with_fallback(source, &default_path))
+    Ok(collect_session_locks(&paths.base_dir.join("agents"), agent_id.as_deref()))
 }
 
+/// Remove the lock file(s) for a session. Refuses while the process that
+/// holds the lock is known to be running.
 #[tauri::command]
-pub fn apply_config_patch(
-    patch_template: String,
-    params: Map<String, Value>,
-) -> Result<ApplyResult, String> {
+pub fn clear_session_lock(agent_id: String, session_id: String) -> Result<usize, String> {
+    for id in [&agent_id, &session_id] {
+        if id.trim().is_empty() || id.contains("..") || id.contains('/') || id.contains('\\') {
+            return Err(format!("invalid id: {id}"));
+        }
+    }
     let paths = resolve_paths();
-    ensure_dirs(&paths)?;
-    let current = read_openclaw_config(&paths)?;
-    let current_text = serde_json::to_string_pretty(&current).map_err(|e| e.to_string())?;
-    let snapshot = add_snapshot(
-        &paths.history_dir,
-        &paths.metadata_path,
-        Some("config-patch".into()),
-        "apply",
-        true,
-        &current_text,
-        None,
-    )?;
-    let (candidate, _changes) = build_candidate_config_from_template(&current, &patch_template, &params)?;
-    write_json(&paths.config_path, &candidate)?;
-    Ok(ApplyResult {
-        ok: true,
-        snapshot_id: Some(snapshot.id),
-        config_path: paths.config_path.to_string_lossy().to_string(),
-        backup_path: Some(snapshot.config_path),
-        warnings: Vec::new(),
-        errors: Vec::new(),
-    })
+    let locks: Vec<SessionLock> = collect_session_locks(&paths.base_dir.join("agents"), Some(&agent_id))
+        .into_iter()
+        .filter(|l| l.session_id == session_id)
+        .collect();
+    if locks.is_empty() {
+        return Err(format!("No lock file for session {session_id}"));
+    }
+    if let Some(live) = locks.iter().find(|l| l.pid_alive == Some(true)) {
+        return Err(format!(
+            "Session {session_id} is locked by running process {}; stop it first",
+            live.pid.unwrap_or_default()
+        ));
+    }
+    let mut removed = 0usize;
+    for lock in &locks {
+        fs::remove_file(&lock.lock_file)
+            .map_err(|e| format!("Failed to remove {}: {e}", lock.lock_file))?;
+        removed += 1;
+    }
+    Ok(removed)
 }
 
-#[tauri::command]
-pub async fn restart_gateway() -> Result<bool, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        run_openclaw_raw(&["gateway", "restart"])?;
-        Ok(true)
-    }).await.map_err(|e| e.to_string())?
-}
+// ---- Session trash ----
 
-#[tauri::command]
-pub fn list_history(limit: usize, offset: usize) -> Result<HistoryPage, String> {
-    let paths = resolve_paths();
-    let index = list_snapshots(&paths.metadata_path)?;
-    let items = index
-        .items
-        .into_iter()
-        .skip(offset)
-        .take(limit)
-        .map(|item| HistoryItem {
-            id: item.id,
-            recipe_id: item.recipe_id,
-            created_at: item.created_at,
-            source: item.source,
-            can_rollback: item.can_rollback,
-            rollback_of: item.rollback_of,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTrashManifest {
+    pub id: String,
+    pub operation: String,
+    pub created_at: String,
+    /// Trashed paths, relative to the agents root.
+    pub files: Vec<String>,
+    /// sessions.json entries removed alongside the files, per agent.
+    #[serde(default)]
+    pub removed_meta: BTreeMap<String, Map<String, Value>>,
+}
+
+/// A timestamped `~/.clawpal/session-trash/<id>/` directory that session
+/// files are moved into instead of being deleted.
+struct SessionTrash {
+    dir: PathBuf,
+    agents_root: PathBuf,
+    manifest: SessionTrashManifest,
+}
+
+fn session_trash_root(paths: &crate::models::OpenClawPaths) -> PathBuf {
+    paths.clawpal_dir.join("session-trash")
+}
+
+impl SessionTrash {
+    fn create(paths: &crate::models::OpenClawPaths, operation: &str) -> Result<Self, String> {
+        Self::create_in(&session_trash_root(paths), paths.base_dir.join("agents"), operation)
+    }
+
+    fn create_in(root: &Path, agents_root: PathBuf, operation: &str) -> Result<Self, String> {
+        let now_secs = unix_timestamp_secs();
+        let base = chrono::DateTime::<chrono::Utc>::from_timestamp(now_secs as i64, 0)
+            .map(|dt| dt.format("%Y-%m-%d_%H%M%S").to_string())
+            .unwrap_or_else(|| format!("{now_secs}"));
+        let mut id = base.clone();
+        let mut n = 1;
+        while root.join(&id).exists() {
+            id = format!("{base}-{n}");
+            n += 1;
+        }
+        let dir = root.join(&id);
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create session trash: {e}"))?;
+        Ok(Self {
+            dir,
+            agents_root,
+            manifest: SessionTrashManifest {
+                id,
+                operation: operation.to_string(),
+                created_at: format_timestamp_from_unix(now_secs),
+                files: Vec::new(),
+                removed_meta: BTreeMap::new(),
+            },
         })
-        .collect();
-    Ok(HistoryPage { items })
-}
+    }
 
-#[tauri::command]
-pub fn preview_rollback(snapshot_id: String) -> Result<PreviewResult, String> {
-    let paths = resolve_paths();
-    let index = list_snapshots(&paths.metadata_path)?;
-    let target = index
-        .items
-        .into_iter()
-        .find(|s| s.id == snapshot_id)
-        .ok_or_else(|| "snapshot not found".to_string())?;
-    if !target.can_rollback {
-        return Err("snapshot is not rollbackable".to_string());
+    /// Move a file or directory under the agents root into the trash. The
+    /// manifest is rewritten after every move, so a batch that fails halfway
+    /// still lists what was already moved and can be restored.
+    fn discard(&mut self, path: &Path) -> Result<(), String> {
+        let rel = path
+            .strip_prefix(&self.agents_root)
+            .map_err(|_| format!("{} is outside the agents directory", path.display()))?
+            .to_path_buf();
+        let dest = self.dir.join(&rel);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        move_path(path, &dest)?;
+        self.manifest.files.push(rel.to_string_lossy().replace('\\', "/"));
+        self.save()
     }
 
-    let current = read_openclaw_config(&paths)?;
-    let target_text = read_snapshot(&target.config_path)?;
-    let target_json: Value = json5::from_str(&target_text).unwrap_or(Value::Object(Default::default()));
-    let before_text = serde_json::to_string_pretty(&current).unwrap_or_else(|_| "{}".into());
-    let after_text = serde_json::to_string_pretty(&target_json).unwrap_or_else(|_| "{}".into());
-    Ok(PreviewResult {
-        recipe_id: "rollback".into(),
-        diff: format_diff(&current, &target_json),
-        config_before: before_text,
-        config_after: after_text,
-        changes: collect_change_paths(&current, &target_json),
-        overwrites_existing: true,
-        can_rollback: true,
-        impact_level: "medium".into(),
-        warnings: vec!["Rollback will replace current configuration".into()],
-    })
+    fn record_meta(&mut self, agent_id: &str, key: &str, value: Value) {
+        self.manifest
+            .removed_meta
+            .entry(agent_id.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+        let _ = self.save();
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(&self.manifest).map_err(|e| e.to_string())?;
+        write_text(&self.dir.join("manifest.json"), &text)
+    }
+
+    fn finish(self) -> Result<(), String> {
+        if self.manifest.files.is_empty() && self.manifest.removed_meta.is_empty() {
+            let _ = fs::remove_dir_all(&self.dir);
+            return Ok(());
+        }
+        self.save()
+    }
 }
 
-#[tauri::command]
-pub fn rollback(snapshot_id: String) -> Result<ApplyResult, String> {
-    let paths = resolve_paths();
-    ensure_dirs(&paths)?;
-    let index = list_snapshots(&paths.metadata_path)?;
-    let target = index
-        .items
-        .into_iter()
-        .find(|s| s.id == snapshot_id)
-        .ok_or_else(|| "snapshot not found".to_string())?;
-    if !target.can_rollback {
-        return Err("snapshot is not rollbackable".to_string());
+/// Rename, falling back to copy + delete across filesystems.
+fn move_path(src: &Path, dst: &Path) -> Result<(), String> {
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    if src.is_dir() {
+        let mut total = 0u64;
+        fs::create_dir_all(dst).map_err(|e| e.to_string())?;
+        copy_dir_recursive(src, dst, &HashSet::new(), &mut total)?;
+        fs::remove_dir_all(src).map_err(|e| e.to_string())
+    } else {
+        fs::copy(src, dst).map_err(|e| e.to_string())?;
+        fs::remove_file(src).map_err(|e| e.to_string())
     }
-    let target_text = read_snapshot(&target.config_path)?;
-    let backup = read_openclaw_config(&paths)?;
-    let backup_text = serde_json::to_string_pretty(&backup).map_err(|e| e.to_string())?;
-    let _ = add_snapshot(
-        &paths.history_dir,
-        &paths.metadata_path,
-        target.recipe_id.clone(),
-        "rollback",
-        true,
-        &backup_text,
-        Some(target.id.clone()),
-    )?;
-    write_text(&paths.config_path, &target_text)?;
-    Ok(ApplyResult {
-        ok: true,
-        snapshot_id: Some(target.id),
-        config_path: paths.config_path.to_string_lossy().to_string(),
-        backup_path: None,
-        warnings: vec!["rolled back".into()],
-        errors: Vec::new(),
-    })
 }
 
-#[tauri::command]
-pub fn run_doctor_command() -> Result<DoctorReport, String> {
-    let paths = resolve_paths();
-    Ok(run_doctor(&paths))
+/// Move every session/archive entry of every agent into the trash.
+fn trash_agent_sessions(agents_root: &Path, trash: &mut SessionTrash) -> Result<usize, String> {
+    if !agents_root.exists() {
+        return Ok(0);
+    }
+    let mut moved = 0usize;
+    for agent in fs::read_dir(agents_root).map_err(|e| e.to_string())?.flatten() {
+        if !agent.path().is_dir() {
+            continue;
+        }
+        for dir_name in ["sessions", "sessions_archive"] {
+            let dir = agent.path().join(dir_name);
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                trash.discard(&entry.path())?;
+                moved += 1;
+            }
+        }
+    }
+    Ok(moved)
+}
+
+fn read_session_trash_manifest(dir: &Path) -> Result<SessionTrashManifest, String> {
+    let text = fs::read_to_string(dir.join("manifest.json"))
+        .map_err(|e| format!("Failed to read trash manifest: {e}"))?;
+    serde_json::from_str(&text).map_err(|e| format!("Invalid trash manifest: {e}"))
 }
 
 #[tauri::command]
-pub fn fix_issues(ids: Vec<String>) -> Result<FixResult, String> {
+pub fn list_session_trash() -> Result<Vec<SessionTrashManifest>, String> {
     let paths = resolve_paths();
-    let issues = run_doctor(&paths);
-    let mut fixable = Vec::new();
-    for issue in issues.issues {
-        if ids.contains(&issue.id) && issue.auto_fixable {
-            fixable.push(issue.id);
-        }
-    }
-    let auto_applied = apply_auto_fixes(&paths, &fixable);
-    let mut remaining = Vec::new();
-    let mut applied = Vec::new();
-    for id in ids {
-        if fixable.contains(&id) && auto_applied.iter().any(|x| x == &id) {
-            applied.push(id);
-        } else {
-            remaining.push(id);
-        }
+    let root = session_trash_root(&paths);
+    if !root.exists() {
+        return Ok(Vec::new());
     }
-    Ok(FixResult {
-        ok: true,
-        applied,
-        remaining_issues: remaining,
-    })
+    let mut items: Vec<SessionTrashManifest> = fs::read_dir(&root)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| read_session_trash_manifest(&entry.path()).ok())
+        .collect();
+    items.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(items)
 }
 
+/// Move trashed session files back into place and re-add their
+/// sessions.json entries. Files that already exist again are left alone.
 #[tauri::command]
-pub async fn remote_fix_issues(pool: State<'_, SshConnectionPool>, host_id: String, ids: Vec<String>) -> Result<FixResult, String> {
-    let raw = pool.sftp_read(&host_id, "~/.openclaw/openclaw.json").await?;
-    let mut cfg: Value = json5::from_str(&raw).unwrap_or_else(|_| Value::Object(Default::default()));
-    let mut applied = Vec::new();
+pub fn restore_session_trash(trash_id: String) -> Result<usize, String> {
+    if trash_id.trim().is_empty() || trash_id.contains("..") || trash_id.contains('/') || trash_id.contains('\\') {
+        return Err("invalid trash id".into());
+    }
+    let paths = resolve_paths();
+    restore_session_trash_dir(&session_trash_root(&paths).join(&trash_id), &paths.base_dir.join("agents"))
+}
 
-    for id in &ids {
-        match id.as_str() {
-            "field.agents" if cfg.get("agents").is_none() => {
-                let mut agents = serde_json::Map::new();
-                let mut defaults = serde_json::Map::new();
-                defaults.insert("model".into(), Value::String("anthropic/claude-sonnet-4-5".into()));
-                agents.insert("defaults".into(), Value::Object(defaults));
-                if let Value::Object(map) = &mut cfg {
-                    map.insert("agents".into(), Value::Object(agents));
-                }
-                applied.push(id.clone());
-            }
-            "json.syntax" => {
-                // If we got here, json5 already parsed it or fell back to empty object
-                applied.push(id.clone());
-            }
-            "field.port" => {
-                let mut gateway = cfg
-                    .get("gateway")
-                    .and_then(|v| v.as_object())
-                    .cloned()
+fn restore_session_trash_dir(trash_dir: &Path, agents_root: &Path) -> Result<usize, String> {
+    let manifest = read_session_trash_manifest(trash_dir)?;
+    let mut restored = 0usize;
+    for rel in &manifest.files {
+        if rel.split('/').any(|part| part == "..") {
+            continue;
+        }
+        let src = trash_dir.join(rel);
+        let dest = agents_root.join(rel);
+        if !src.exists() {
+            continue;
+        }
+        if dest.exists() {
+            // A fresh sessions.json was created since; merge the old entries in.
+            if dest.file_name().and_then(|n| n.to_str()) == Some("sessions.json") {
+                let old: Map<String, Value> = fs::read_to_string(&src)
+                    .ok()
+                    .and_then(|t| serde_json::from_str(&t).ok())
                     .unwrap_or_default();
-                gateway.insert("port".into(), Value::Number(serde_json::Number::from(18789_u64)));
-                if let Value::Object(map) = &mut cfg {
-                    map.insert("gateway".into(), Value::Object(gateway));
-                }
-                applied.push(id.clone());
+                merge_sessions_meta(&dest, old)?;
+                restored += 1;
             }
-            _ => {}
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
         }
+        move_path(&src, &dest)?;
+        restored += 1;
     }
-
-    if !applied.is_empty() {
-        let new_text = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
-        remote_write_config_with_snapshot(&pool, &host_id, &raw, &cfg, "doctor-fix").await?;
-        let _ = new_text; // written by remote_write_config_with_snapshot
+    for (agent, entries) in manifest.removed_meta {
+        merge_sessions_meta(&agents_root.join(&agent).join("sessions").join("sessions.json"), entries)?;
     }
 
-    let remaining: Vec<String> = ids.into_iter().filter(|id| !applied.contains(id)).collect();
-    Ok(FixResult {
-        ok: true,
-        applied,
-        remaining_issues: remaining,
-    })
+    fs::remove_dir_all(trash_dir).map_err(|e| e.to_string())?;
+    Ok(restored)
 }
 
-fn collect_model_summary(cfg: &Value) -> ModelSummary {
-    let global_default_model = cfg
-        .pointer("/agents/defaults/model")
-        .and_then(|value| read_model_value(value))
-        .or_else(|| cfg.pointer("/agents/default/model").and_then(|value| read_model_value(value)));
-
-    let mut agent_overrides = Vec::new();
-    if let Some(agents) = cfg.pointer("/agents/list").and_then(Value::as_array) {
-        for agent in agents {
-            if let Some(model_value) = agent.get("model").and_then(read_model_value) {
-                let should_emit = global_default_model
-                    .as_ref()
-                    .map(|global| global != &model_value)
-                    .unwrap_or(true);
-                if should_emit {
-                    let id = agent
-                        .get("id")
-                        .and_then(Value::as_str)
-                        .unwrap_or("agent");
-                    agent_overrides.push(format!("{id} => {model_value}"));
-                }
-            }
-        }
+/// Add entries to a sessions.json without overwriting keys already present.
+fn merge_sessions_meta(path: &Path, entries: Map<String, Value>) -> Result<(), String> {
+    if entries.is_empty() {
+        return Ok(());
     }
-    ModelSummary {
-        global_default_model,
-        agent_overrides,
-        channel_overrides: collect_channel_model_overrides(cfg),
+    let mut data: Map<String, Value> = fs::read_to_string(path)
+        .ok()
+        .and_then(|t| serde_json::from_str(&t).ok())
+        .unwrap_or_default();
+    for (key, value) in entries {
+        data.entry(key).or_insert(value);
     }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, serde_json::to_string(&data).unwrap_or_default()).map_err(|e| e.to_string())
 }
 
-
-fn run_openclaw_raw(args: &[&str]) -> Result<OpenclawCommandOutput, String> {
-    run_openclaw_raw_timeout(args, None)
-}
-
-fn run_openclaw_raw_timeout(args: &[&str], timeout_secs: Option<u64>) -> Result<OpenclawCommandOutput, String> {
-    let mut child = Command::new(resolve_openclaw_bin())
-        .args(args)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .map_err(|error| format!("failed to run openclaw: {error}"))?;
-
-    if let Some(secs) = timeout_secs {
-        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(secs);
-        loop {
-            match child.try_wait().map_err(|e| e.to_string())? {
-                Some(status) => {
-                    let mut stdout_buf = Vec::new();
-                    let mut stderr_buf = Vec::new();
-                    if let Some(mut out) = child.stdout.take() {
-                        std::io::Read::read_to_end(&mut out, &mut stdout_buf).ok();
-                    }
-                    if let Some(mut err) = child.stderr.take() {
-                        std::io::Read::read_to_end(&mut err, &mut stderr_buf).ok();
-                    }
-                    let exit_code = status.code().unwrap_or(-1);
-                    let result = OpenclawCommandOutput {
-                        stdout: String::from_utf8_lossy(&stdout_buf).trim_end().to_string(),
-                        stderr: String::from_utf8_lossy(&stderr_buf).trim_end().to_string(),
-                        exit_code,
-                    };
-                    if exit_code != 0 {
-                        let details = if !result.stderr.is_empty() {
-                            result.stderr.clone()
-                        } else {
-                            result.stdout.clone()
-                        };
-                        return Err(format!("openclaw command failed ({exit_code}): {details}"));
-                    }
-                    return Ok(result);
-                }
-                None => {
-                    if std::time::Instant::now() >= deadline {
-                        let _ = child.kill();
-                        return Err(format!(
-                            "Command timed out after {secs}s. The gateway may still be restarting in the background."
-                        ));
-                    }
-                    std::thread::sleep(std::time::Duration::from_millis(250));
-                }
-            }
-        }
-    } else {
-        let output = child
-            .wait_with_output()
-            .map_err(|error| format!("failed to run openclaw: {error}"))?;
-        let exit_code = output.status.code().unwrap_or(-1);
-        let result = OpenclawCommandOutput {
-            stdout: String::from_utf8_lossy(&output.stdout).trim_end().to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).trim_end().to_string(),
-            exit_code,
-        };
-        if exit_code != 0 {
-            let details = if !result.stderr.is_empty() {
-                result.stderr.clone()
-            } else {
-                result.stdout.clone()
-            };
-            return Err(format!("openclaw command failed ({exit_code}): {details}"));
+/// Permanently delete everything in the session trash. Returns the number of batches removed.
+#[tauri::command]
+pub fn empty_session_trash() -> Result<usize, String> {
+    let paths = resolve_paths();
+    let root = session_trash_root(&paths);
+    if !root.exists() {
+        return Ok(0);
+    }
+    let mut removed = 0usize;
+    for entry in fs::read_dir(&root).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+            removed += 1;
         }
-        Ok(result)
     }
+    Ok(removed)
 }
 
-/// Strip leading non-JSON lines from CLI output (plugin logs, ANSI codes, etc.)
-fn extract_json_from_output(raw: &str) -> Option<&str> {
-    let start = raw.find('{').or_else(|| raw.find('['))?;
-    Some(&raw[start..])
+#[cfg(test)]
+mod session_trash_tests {
+    use super::*;
+
+    #[test]
+    fn trash_batch_failing_halfway_stays_restorable() {
+        let root = std::env::temp_dir().join(format!("clawpal-trash-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let agents_root = root.join("agents");
+        let sessions = agents_root.join("main").join("sessions");
+        fs::create_dir_all(&sessions).unwrap();
+        fs::write(sessions.join("s1.jsonl"), "{}").unwrap();
+
+        let mut trash = SessionTrash::create_in(&root.join("trash"), agents_root.clone(), "test").unwrap();
+        trash.discard(&sessions.join("s1.jsonl")).unwrap();
+        assert!(trash.discard(&root.join("elsewhere.jsonl")).is_err());
+        let trash_dir = trash.dir.clone();
+        drop(trash); // the failing batch never reaches finish()
+
+        let manifest = read_session_trash_manifest(&trash_dir).unwrap();
+        assert_eq!(manifest.files, vec!["main/sessions/s1.jsonl".to_string()]);
+        assert_eq!(restore_session_trash_dir(&trash_dir, &agents_root).unwrap(), 1);
+        assert!(sessions.join("s1.jsonl").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
 }
 
-/// Extract the last JSON array from CLI output that may contain ANSI codes and plugin logs.
-/// Scans from the end to find the last `]`, then finds its matching `[`.
-fn extract_last_json_array(raw: &str) -> Option<&str> {
-    let bytes = raw.as_bytes();
-    let end = bytes.iter().rposition(|&b| b == b']')?;
-    let mut depth = 0;
-    for i in (0..=end).rev() {
-        match bytes[i] {
-            b']' => depth += 1,
-            b'[' => {
-                depth -= 1;
-                if depth == 0 {
-                    return Some(&raw[i..=end]);
-                }
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPreviewPage {
+    /// `{role, content}` objects for the requested slice.
+    pub messages: Vec<Value>,
+    /// Number of messages in the whole session.
+    pub total_messages: usize,
+    pub offset: usize,
+}
+
+/// Text of a JSONL message record: a plain string, or the `text` parts of a
+/// content array joined by newlines.
+fn message_content_text(obj: &Value) -> String {
+    obj.pointer("/message/content")
+        .map(|c| {
+            if let Some(arr) = c.as_array() {
+                arr.iter()
+                    .filter_map(|item| item.get("text").and_then(Value::as_str))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            } else if let Some(s) = c.as_str() {
+                s.to_string()
+            } else {
+                String::new()
             }
-            _ => {}
-        }
-    }
-    None
+        })
+        .unwrap_or_default()
 }
 
-/// Parse `openclaw channels resolve --json` output into a map of id -> name.
-fn parse_resolve_name_map(stdout: &str) -> Option<HashMap<String, String>> {
-    let json_str = extract_last_json_array(stdout)?;
-    let parsed: Vec<Value> = serde_json::from_str(json_str).ok()?;
-    let mut map = HashMap::new();
-    for item in parsed {
-        let resolved = item.get("resolved").and_then(Value::as_bool).unwrap_or(false);
-        if !resolved {
+/// Stream JSONL lines and keep only the messages in `[offset, offset+limit)`,
+/// while still counting every message for `total_messages`.
+fn session_preview_page<S: AsRef<str>>(
+    lines: impl Iterator<Item = S>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> SessionPreviewPage {
+    let offset = offset.unwrap_or(0);
+    let end = limit.map(|l| offset.saturating_add(l));
+    let mut messages: Vec<Value> = Vec::new();
+    let mut total_messages = 0usize;
+
+    for line in lines {
+        let line = line.as_ref();
+        if line.trim().is_empty() {
             continue;
         }
-        if let (Some(input), Some(name)) = (
-            item.get("input").and_then(Value::as_str),
-            item.get("name").and_then(Value::as_str),
-        ) {
-            let name = name.trim().to_string();
-            if !name.is_empty() {
-                map.insert(input.to_string(), name);
-            }
+        let obj: Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if obj.get("type").and_then(Value::as_str) != Some("message") {
+            continue;
         }
+        let index = total_messages;
+        total_messages += 1;
+        if index < offset || end.is_some_and(|e| index >= e) {
+            continue;
+        }
+        let role = obj.pointer("/message/role").and_then(Value::as_str).unwrap_or("unknown");
+        messages.push(serde_json::json!({
+            "role": role,
+            "content": message_content_text(&obj),
+        }));
     }
-    Some(map)
-}
 
-fn extract_version_from_text(input: &str) -> Option<String> {
-    let re = regex::Regex::new(r"\d+\.\d+(?:\.\d+){1,3}(?:[-+._a-zA-Z0-9]*)?").ok()?;
-    re.find(input).map(|mat| mat.as_str().to_string())
+    SessionPreviewPage {
+        messages,
+        total_messages,
+        offset,
+    }
 }
 
-fn compare_semver(installed: &str, latest: Option<&str>) -> bool {
-    let installed = normalize_semver_components(installed);
-    let latest = latest.and_then(normalize_semver_components);
-    let (mut installed, mut latest) = match (installed, latest) {
-        (Some(installed), Some(latest)) => (installed, latest),
-        _ => return false,
-    };
-
-    let len = installed.len().max(latest.len());
-    while installed.len() < len {
-        installed.push(0);
-    }
-    while latest.len() < len {
-        latest.push(0);
-    }
-    installed < latest
+/// Preview a session transcript. `offset`/`limit` page through long
+/// sessions; leaving both unset returns every message.
+#[tauri::command]
+pub async fn preview_session(
+    agent_id: String,
+    session_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<SessionPreviewPage, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        preview_session_sync(&agent_id, &session_id, offset, limit)
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
-fn normalize_semver_components(raw: &str) -> Option<Vec<u32>> {
-    let mut parts = Vec::new();
-    for bit in raw.split('.') {
-        let filtered = bit.trim_start_matches(|c: char| c == 'v' || c == 'V');
-        let head = filtered.split(|c: char| !c.is_ascii_digit()).next().unwrap_or("");
-        if head.is_empty() {
-            continue;
-        }
-        parts.push(head.parse::<u32>().ok()?);
+/// The JSONL file of a session, searched in `sessions` then `sessions_archive`.
+fn find_session_file(agent_id: &str, session_id: &str) -> Result<Option<PathBuf>, String> {
+    if agent_id.contains("..") || agent_id.contains('/') || agent_id.contains('\\') {
+        return Err("invalid agent id".into());
     }
-    if parts.is_empty() {
-        return None;
+    if session_id.contains("..") || session_id.contains('/') || session_id.contains('\\') {
+        return Err("invalid session id".into());
     }
-    Some(parts)
-}
-
-fn unix_timestamp_secs() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_or(0, |delta| delta.as_secs())
+    let paths = resolve_paths();
+    let agent_dir = paths.base_dir.join("agents").join(agent_id);
+    let jsonl_name = format!("{}.jsonl", session_id);
+    Ok(["sessions", "sessions_archive"]
+        .iter()
+        .map(|dir| agent_dir.join(dir).join(&jsonl_name))
+        .find(|p| p.exists()))
 }
 
-fn format_timestamp_from_unix(timestamp: u64) -> String {
-    let Some(utc) = chrono::DateTime::<chrono::Utc>::from_timestamp(timestamp as i64, 0) else {
-        return "unknown".into();
+fn preview_session_sync(
+    agent_id: &str,
+    session_id: &str,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<SessionPreviewPage, String> {
+    let file_path = match find_session_file(agent_id, session_id)? {
+        Some(p) => p,
+        None => return Ok(session_preview_page(std::iter::empty::<&str>(), offset, limit)),
     };
-    utc.to_rfc3339()
-}
 
-fn openclaw_update_cache_path(paths: &crate::models::OpenClawPaths) -> PathBuf {
-    paths.clawpal_dir.join("openclaw-update-cache.json")
+    let file = fs::File::open(&file_path).map_err(|e| e.to_string())?;
+    Ok(session_preview_page(read_session_lines(file), offset, limit))
 }
 
-fn read_openclaw_update_cache(
-    path: &Path,
-) -> Option<OpenclawUpdateCache> {
-    let text = fs::read_to_string(path).ok()?;
-    serde_json::from_str::<OpenclawUpdateCache>(&text).ok()
+/// Lines of a session file, decoded lossily so one undecodable line is left
+/// to the JSON parse to skip instead of ending the read early. Only an I/O
+/// error stops the iteration.
+fn read_session_lines(file: fs::File) -> impl Iterator<Item = String> {
+    BufReader::new(file)
+        .split(b'\n')
+        .map_while(Result::ok)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
 }
 
-fn save_openclaw_update_cache(
-    path: &Path,
-    cache: &OpenclawUpdateCache,
-) -> Result<(), String> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|error| error.to_string())?;
-    }
-    let text = serde_json::to_string_pretty(cache).map_err(|error| error.to_string())?;
-    write_text(path, &text)
-}
+#[cfg(test)]
+mod session_preview_tests {
+    use super::*;
 
-fn read_model_catalog_cache(path: &Path) -> Option<ModelCatalogProviderCache> {
-    let text = fs::read_to_string(path).ok()?;
-    serde_json::from_str::<ModelCatalogProviderCache>(&text).ok()
-}
+    #[test]
+    fn test_session_preview_page_slices_and_counts() {
+        let lines: Vec<String> = (0..5)
+            .map(|i| format!(r#"{{"type":"message","message":{{"role":"user","content":"m{i}"}}}}"#))
+            .chain(std::iter::once(r#"{"type":"session"}"#.to_string()))
+            .collect();
+        let page = session_preview_page(lines.iter(), Some(1), Some(2));
+        assert_eq!(page.total_messages, 5);
+        assert_eq!(page.offset, 1);
+        let contents: Vec<&str> = page.messages.iter().filter_map(|m| m["content"].as_str()).collect();
+        assert_eq!(contents, vec!["m1", "m2"]);
 
-fn save_model_catalog_cache(
-    path: &Path,
-    cache: &ModelCatalogProviderCache,
-) -> Result<(), String> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+        let all = session_preview_page(lines.iter(), None, None);
+        assert_eq!(all.messages.len(), 5);
     }
-    let text = serde_json::to_string_pretty(cache).map_err(|error| error.to_string())?;
-    write_text(path, &text)
-}
 
-fn model_catalog_cache_path(paths: &crate::models::OpenClawPaths) -> PathBuf {
-    paths.clawpal_dir.join("model-catalog-cache.json")
+    #[test]
+    fn test_session_lines_continue_past_invalid_utf8() {
+        let path = std::env::temp_dir().join(format!("clawpal-session-lines-{}.jsonl", std::process::id()));
+        let mut bytes = br#"{"type":"message","message":{"role":"user","content":"a"}}"#.to_vec();
+        bytes.extend_from_slice(b"\n\xff\xfe broken\n");
+        bytes.extend_from_slice(br#"{"type":"message","message":{"role":"user","content":"b"}}"#);
+        fs::write(&path, bytes).unwrap();
+        let page = session_preview_page(read_session_lines(fs::File::open(&path).unwrap()), None, None);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(page.total_messages, 2);
+    }
 }
 
-fn remote_model_catalog_cache_path(paths: &crate::models::OpenClawPaths, host_id: &str) -> PathBuf {
-    let safe_host_id: String = host_id
-        .chars()
-        .map(|ch| {
-            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
-                ch
-            } else {
-                '_'
-            }
-        })
+const SESSION_SEARCH_LIMIT: usize = 200;
+const SESSION_SEARCH_SNIPPET_CHARS: usize = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSearchHit {
+    pub agent: String,
+    pub session_id: String,
+    /// "sessions" or "archive"
+    pub kind: String,
+    pub role: String,
+    pub snippet: String,
+}
+
+/// Up to `SESSION_SEARCH_SNIPPET_CHARS` characters either side of the match
+/// starting at byte `start`, with ellipses where the text was cut.
+fn search_snippet(text: &str, start: usize, len: usize) -> String {
+    let before: String = text[..start]
+        .chars()
+        .rev()
+        .take(SESSION_SEARCH_SNIPPET_CHARS)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
         .collect();
-    paths
-        .clawpal_dir
-        .join("remote-model-catalog")
-        .join(format!("{safe_host_id}.json"))
+    let after: String = text[start + len..].chars().take(SESSION_SEARCH_SNIPPET_CHARS).collect();
+    let mut snippet = String::new();
+    if before.len() < start {
+        snippet.push('…');
+    }
+    snippet.push_str(&before);
+    snippet.push_str(&text[start..start + len]);
+    snippet.push_str(&after);
+    if start + len + after.len() < text.len() {
+        snippet.push('…');
+    }
+    snippet.replace('\n', " ")
+}
+
+/// Byte offset and length of the first match of `query` in `text`.
+fn find_match(text: &str, query: &str, case_sensitive: bool) -> Option<(usize, usize)> {
+    if case_sensitive {
+        return text.find(query).map(|i| (i, query.len()));
+    }
+    let needle: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    let indices: Vec<(usize, char)> = text.char_indices().collect();
+    for (pos, &(start, _)) in indices.iter().enumerate() {
+        let mut matched = 0usize;
+        let mut end = start;
+        for &(i, ch) in &indices[pos..] {
+            let lower: Vec<char> = ch.to_lowercase().collect();
+            if needle.len() < matched + lower.len() || needle[matched..matched + lower.len()] != lower[..] {
+                break;
+            }
+            matched += lower.len();
+            end = i + ch.len_utf8();
+            if matched == needle.len() {
+                return Some((start, end - start));
+            }
+        }
+    }
+    None
 }
 
-fn normalize_model_ref(raw: &str) -> String {
-    raw.trim().to_lowercase().replace('\\', "/")
+fn search_sessions_sync(
+    query: &str,
+    agent_filter: Option<&str>,
+    case_sensitive: bool,
+) -> Result<Vec<SessionSearchHit>, String> {
+    let paths = resolve_paths();
+    let agents_root = paths.base_dir.join("agents");
+    let Ok(agents) = fs::read_dir(&agents_root) else {
+        return Ok(Vec::new());
+    };
+    let mut hits = Vec::new();
+    for entry in agents.flatten() {
+        let agent = entry.file_name().to_string_lossy().to_string();
+        if agent_filter.is_some_and(|f| f != agent) {
+            continue;
+        }
+        for (kind_name, dir_name) in [("sessions", "sessions"), ("archive", "sessions_archive")] {
+            let Ok(files) = fs::read_dir(entry.path().join(dir_name)) else {
+                continue;
+            };
+            for file_entry in files.flatten() {
+                let fname = file_entry.file_name().to_string_lossy().to_string();
+                if !fname.ends_with(".jsonl") {
+                    continue;
+                }
+                let Ok(file) = fs::File::open(file_entry.path()) else {
+                    continue;
+                };
+                let session_id = fname.trim_end_matches(".jsonl").to_string();
+                for line in read_session_lines(file) {
+                    let Ok(obj) = serde_json::from_str::<Value>(&line) else {
+                        continue;
+                    };
+                    if obj.get("type").and_then(Value::as_str) != Some("message") {
+                        continue;
+                    }
+                    let text = message_content_text(&obj);
+                    let Some((start, len)) = find_match(&text, query, case_sensitive) else {
+                        continue;
+                    };
+                    hits.push(SessionSearchHit {
+                        agent: agent.clone(),
+                        session_id: session_id.clone(),
+                        kind: kind_name.to_string(),
+                        role: obj
+                            .pointer("/message/role")
+                            .and_then(Value::as_str)
+                            .unwrap_or("unknown")
+                            .to_string(),
+                        snippet: search_snippet(&text, start, len),
+                    });
+                    if hits.len() >= SESSION_SEARCH_LIMIT {
+                        return Ok(hits);
+                    }
+                }
+            }
+        }
+    }
+    Ok(hits)
 }
 
-fn resolve_openclaw_version() -> String {
-    use std::sync::OnceLock;
-    static VERSION: OnceLock<String> = OnceLock::new();
-    VERSION.get_or_init(|| {
-        match run_openclaw_raw(&["--version"]) {
-            Ok(output) => extract_version_from_text(&output.stdout).unwrap_or_else(|| "unknown".into()),
-            Err(_) => "unknown".into(),
+/// Find messages containing `query` across session transcripts. Results
+/// are capped at `SESSION_SEARCH_LIMIT`.
+#[tauri::command]
+pub async fn search_sessions(
+    query: String,
+    agent_id: Option<String>,
+    case_sensitive: Option<bool>,
+) -> Result<Vec<SessionSearchHit>, String> {
+    if query.trim().is_empty() {
+        return Err("search query is required".into());
+    }
+    if let Some(ref id) = agent_id {
+        if id.contains("..") || id.contains('/') || id.contains('\\') {
+            return Err("invalid agent id".into());
         }
-    }).clone()
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        search_sessions_sync(&query, agent_id.as_deref(), case_sensitive.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
-fn check_openclaw_update_cached(paths: &crate::models::OpenClawPaths, force: bool) -> Result<OpenclawUpdateCheck, String> {
-    let cache_path = openclaw_update_cache_path(paths);
-    let now = unix_timestamp_secs();
-    if !force {
-        if let Some(cached) = read_openclaw_update_cache(&cache_path) {
-            if now.saturating_sub(cached.checked_at) < cached.ttl_seconds {
-                let installed_version = cached.installed_version.unwrap_or_else(resolve_openclaw_version);
-                let upgrade_available = compare_semver(&installed_version, cached.latest_version.as_deref());
-                return Ok(OpenclawUpdateCheck {
-                    installed_version,
-                    latest_version: cached.latest_version,
-                    upgrade_available,
-                    channel: cached.channel,
-                    details: cached.details,
-                    source: cached.source,
-                    checked_at: format_timestamp_from_unix(now),
-                });
+#[cfg(test)]
+mod session_search_tests {
+    use super::*;
+
+    #[test]
+    fn test_find_match_case_insensitive() {
+        assert_eq!(find_match("Hello World", "world", false), Some((6, 5)));
+        assert_eq!(find_match("Hello World", "world", true), None);
+        let text = "a".repeat(100) + "needle" + &"b".repeat(100);
+        let (start, len) = find_match(&text, "NEEDLE", false).unwrap();
+        let snippet = search_snippet(&text, start, len);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("needle"));
+    }
+}
+
+/// Render preview messages as `markdown` (role headings) or `text`
+/// (`role: content` blocks). Tool calls carry no text parts, so messages that
+/// end up empty are skipped.
+fn render_session_transcript(messages: &[Value], format: &str) -> Result<String, String> {
+    if format != "markdown" && format != "text" {
+        return Err(format!("unknown export format: {format} (expected markdown or text)"));
+    }
+    let mut out = String::new();
+    for msg in messages {
+        let role = msg.get("role").and_then(Value::as_str).unwrap_or("unknown");
+        let content = msg.get("content").and_then(Value::as_str).unwrap_or("").trim();
+        if content.is_empty() {
+            continue;
+        }
+        if format == "markdown" {
+            let mut heading = role.to_string();
+            if let Some(first) = heading.get_mut(0..1) {
+                first.make_ascii_uppercase();
             }
+            out.push_str(&format!("### {heading}\n\n{content}\n\n"));
+        } else {
+            out.push_str(&format!("{role}: {content}\n\n"));
         }
     }
+    Ok(out)
+}
 
-    let installed_version = resolve_openclaw_version();
-    let (latest_version, channel, details, source, upgrade_available) = detect_openclaw_update_cached(&installed_version)
-        .unwrap_or((None, None, Some("failed to detect update status".into()), "openclaw-command".into(), false));
-    let checked_at = format_timestamp_from_unix(now);
-    let cache = OpenclawUpdateCache {
-        checked_at: now,
-        latest_version: latest_version.clone(),
-        channel,
-        details: details.clone(),
-        source: source.clone(),
-        installed_version: Some(installed_version.clone()),
-        ttl_seconds: 60 * 60 * 6,
-    };
-    save_openclaw_update_cache(&cache_path, &cache)?;
-    let upgrade = compare_semver(&installed_version, latest_version.as_deref());
-    Ok(OpenclawUpdateCheck {
-        installed_version,
-        latest_version,
-        upgrade_available: upgrade || upgrade_available,
-        channel: cache.channel,
-        details,
-        source,
-        checked_at,
+/// Render a session transcript as Markdown or plain text.
+#[tauri::command]
+pub async fn export_session(agent_id: String, session_id: String, format: String) -> Result<String, String> {
+    let page = tauri::async_runtime::spawn_blocking(move || {
+        preview_session_sync(&agent_id, &session_id, None, None)
     })
+    .await
+    .map_err(|e| e.to_string())??;
+    render_session_transcript(&page.messages, &format)
 }
 
-fn detect_openclaw_update_cached(installed_version: &str) -> Option<(Option<String>, Option<String>, Option<String>, String, bool)> {
-    let output = run_openclaw_raw(&["update", "status"]).ok()?;
-    if let Some((latest_version, channel, details, upgrade_available)) =
-        parse_openclaw_update_json(&output.stdout, installed_version)
-    {
-        return Some((latest_version, Some(channel), Some(details), "openclaw update status --json".into(), upgrade_available));
+#[cfg(test)]
+mod session_export_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_session_transcript() {
+        let messages = vec![
+            serde_json::json!({"role": "user", "content": "hi"}),
+            serde_json::json!({"role": "assistant", "content": ""}),
+            serde_json::json!({"role": "assistant", "content": "hello"}),
+        ];
+        assert_eq!(
+            render_session_transcript(&messages, "markdown").unwrap(),
+            "### User\n\nhi\n\n### Assistant\n\nhello\n\n"
+        );
+        assert_eq!(
+            render_session_transcript(&messages, "text").unwrap(),
+            "user: hi\n\nassistant: hello\n\n"
+        );
+        assert!(render_session_transcript(&messages, "html").is_err());
     }
-    let parsed = parse_openclaw_update_text(&output.stdout);
-    if let Some((latest_version, channel, details)) = parsed {
-        let source = "openclaw update status".into();
-        let available = latest_version
-            .as_ref()
-            .is_some_and(|latest| compare_semver(installed_version, Some(latest)));
-        return Some((latest_version, Some(channel), Some(details), source, available));
+}
+
+/// Scrub emails, URLs and key-like tokens from free text. Keys keep the
+/// same prefix/suffix hint `mask_api_key` shows elsewhere.
+fn scrub_sensitive_text(text: &str) -> String {
+    static PATTERNS: std::sync::OnceLock<[regex::Regex; 3]> = std::sync::OnceLock::new();
+    let [email, url, key] = PATTERNS.get_or_init(|| {
+        [
+            regex::Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap(),
+            regex::Regex::new(r"https?://[^\s)\]>]+").unwrap(),
+            regex::Regex::new(r"\b(?:sk|pk|xox[abp]|ghp|gho|AKIA)[-_A-Za-z0-9]{12,}\b|\b[A-Za-z0-9_-]{32,}\b").unwrap(),
+        ]
+    });
+    let text = email.replace_all(text, "[email]");
+    let text = url.replace_all(&text, "[url]");
+    key.replace_all(&text, |caps: &regex::Captures| mask_api_key(&caps[0]))
+        .into_owned()
+}
+
+/// Keys whose string values describe a message's shape rather than its
+/// content, so redaction leaves them alone.
+const SESSION_STRUCTURE_KEYS: &[&str] = &[
+    "type", "role", "id", "parentId", "timestamp", "model", "provider", "api",
+    "stopReason", "toolCallId", "toolName", "customType",
+];
+
+/// Redact the string leaves of a session record. `structure` replaces every
+/// content string with its length; `scrub` only masks emails, URLs and keys.
+/// Roles, ids and the whole `usage` object (token counts) are kept intact.
+fn redact_session_value(value: &Value, key: Option<&str>, mode: &str) -> Value {
+    match value {
+        Value::String(s) if key.is_some_and(|k| SESSION_STRUCTURE_KEYS.contains(&k)) => Value::String(s.clone()),
+        Value::String(s) if mode == "structure" => Value::String(format!("[redacted {} chars]", s.chars().count())),
+        Value::String(s) => Value::String(scrub_sensitive_text(s)),
+        Value::Array(items) => Value::Array(items.iter().map(|v| redact_session_value(v, key, mode)).collect()),
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .map(|(k, v)| {
+                    let v = if k == "usage" { v.clone() } else { redact_session_value(v, Some(k), mode) };
+                    (k.clone(), v)
+                })
+                .collect(),
+        ),
+        other => other.clone(),
     }
-    let latest_version = query_openclaw_latest_npm().ok().flatten();
-    let details = latest_version
-        .as_ref()
-        .map(|value| format!("npm latest {value}"))
-        .unwrap_or_else(|| "update status not available".into());
-    let upgrade = latest_version
-        .as_ref()
-        .is_some_and(|latest| compare_semver(installed_version, Some(latest.as_str())));
-    Some((latest_version, None, Some(details), "npm".into(), upgrade))
 }
 
-fn parse_openclaw_update_json(raw: &str, installed_version: &str) -> Option<(Option<String>, String, String, bool)> {
-    let json_str = extract_json_from_output(raw)?;
-    let payload: Value = serde_json::from_str(json_str).ok()?;
-    let channel = payload
-        .pointer("/channel/value")
-        .and_then(Value::as_str)
-        .unwrap_or("unknown")
-        .to_string();
+/// Export a session with its content removed (`structure`) or scrubbed of
+/// emails, URLs and keys (`scrub`). Message records keep their structure,
+/// roles and token usage. Returns the written file path.
+#[tauri::command]
+pub async fn export_session_redacted(
+    agent_id: String,
+    session_id: String,
+    mode: String,
+) -> Result<String, String> {
+    if mode != "structure" && mode != "scrub" {
+        return Err(format!("unknown mode: {mode} (expected structure or scrub)"));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let file_path = find_session_file(&agent_id, &session_id)?
+            .ok_or_else(|| "session not found or empty".to_string())?;
+        let file = fs::File::open(&file_path).map_err(|e| e.to_string())?;
+        let redacted: Vec<Value> = read_session_lines(file)
+            .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+            .filter(|obj| obj.get("type").and_then(Value::as_str) == Some("message"))
+            .map(|obj| redact_session_value(&obj, None, &mode))
+            .collect();
+        if redacted.is_empty() {
+            return Err("session not found or empty".into());
+        }
 
-    let latest_from_update = payload
-        .pointer("/update/registry/latestVersion")
-        .and_then(Value::as_str)
-        .map(|value| value.to_string());
-    let latest = payload
-        .pointer("/availability/latestVersion")
-        .and_then(Value::as_str)
-        .map(|value| value.to_string())
-        .or(latest_from_update);
-    let has_update = payload
-        .pointer("/availability/available")
-        .and_then(Value::as_bool)
-        .unwrap_or(false);
+        let paths = resolve_paths();
+        let export_dir = paths.clawpal_dir.join("exports");
+        fs::create_dir_all(&export_dir).map_err(|e| e.to_string())?;
+        let ts = chrono::Utc::now().format("%Y-%m-%d_%H%M%S");
+        let path = export_dir.join(format!("{agent_id}-{session_id}-{mode}-{ts}.json"));
+        let payload = serde_json::json!({
+            "agentId": agent_id,
+            "sessionId": session_id,
+            "redaction": mode,
+            "messages": redacted,
+        });
+        let text = serde_json::to_string_pretty(&payload).map_err(|e| e.to_string())?;
+        write_text(&path, &text)?;
+        Ok(path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-    let details = payload
-        .pointer("/availability/latestVersion")
-        .and_then(Value::as_str)
-        .map(|value| format!("npm latest {value}"))
-        .or_else(|| {
-            if has_update {
-                Some("update available".into())
-            } else {
-                Some("up to date".into())
+#[cfg(test)]
+mod session_redaction_tests {
+    use super::*;
+
+    fn record() -> Value {
+        serde_json::json!({
+            "type": "message",
+            "id": "m1",
+            "message": {
+                "role": "assistant",
+                "content": [{ "type": "text", "text": "mail bob@example.com the key sk-abcdefghijklmnop" }],
+                "usage": { "input": 120, "output": 45, "totalTokens": 165 }
             }
         })
-        .unwrap_or_else(|| "update status unavailable".into());
+    }
 
-    let upgrade_available = if let Some(latest_version) = latest.as_deref() {
-        compare_semver(installed_version, Some(latest_version))
-    } else {
-        has_update
-    };
+    #[test]
+    fn structure_mode_keeps_roles_and_token_counts() {
+        let out = redact_session_value(&record(), None, "structure");
+        assert_eq!(out["message"]["role"], "assistant");
+        assert_eq!(out["message"]["usage"], record()["message"]["usage"]);
+        assert_eq!(out["message"]["content"][0]["type"], "text");
+        assert_eq!(out["message"]["content"][0]["text"], "[redacted 48 chars]");
+    }
 
-    Some((latest, channel, details, upgrade_available))
+    #[test]
+    fn scrub_mode_only_masks_sensitive_substrings() {
+        let out = redact_session_value(&record(), None, "scrub");
+        assert_eq!(out["message"]["usage"]["totalTokens"], 165);
+        assert_eq!(out["message"]["content"][0]["text"], "mail [email] the key sk-a...mnop");
+    }
 }
 
-fn parse_openclaw_update_text(raw: &str) -> Option<(Option<String>, String, String)> {
-    let mut channel = String::from("unknown");
-    for line in raw.lines() {
-        if line.contains("Channel") {
-            let right = line.split('│').last().or_else(|| line.split('|').last())?;
-            channel = right.trim().to_string();
-        }
-        if line.to_lowercase().contains("update") && line.contains("npm latest") {
-            if let Some(token) = extract_version_from_text(line) {
-                return Some((Some(token), channel, line.trim().to_string()));
-            }
-            return Some((None, channel, line.trim().to_string()));
-        }
-        if line.to_lowercase().contains("update") && line.contains("unknown") {
-            return Some((None, channel, line.trim().to_string()));
-        }
+/// Read an agent's `sessions/sessions.json` (session key -> metadata).
+fn read_sessions_meta(agent_dir: &Path) -> HashMap<String, Value> {
+    let sessions_json_path = agent_dir.join("sessions").join("sessions.json");
+    if !sessions_json_path.exists() {
+        return HashMap::new();
     }
-    None
+    let text = fs::read_to_string(&sessions_json_path).unwrap_or_default();
+    serde_json::from_str(&text).unwrap_or_default()
 }
 
-fn query_openclaw_latest_npm() -> Result<Option<String>, String> {
-    // Query npm registry directly via HTTP — no local npm CLI needed
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("HTTP client error: {e}"))?;
-    let resp = client
-        .get("https://registry.npmjs.org/openclaw/latest")
-        .header("Accept", "application/json")
-        .send()
-        .map_err(|e| format!("npm registry request failed: {e}"))?;
-    if !resp.status().is_success() {
-        return Ok(None);
-    }
-    let body: Value = resp.json().map_err(|e| format!("npm registry parse failed: {e}"))?;
-    let version = body.get("version").and_then(Value::as_str).map(String::from);
-    Ok(version)
+/// The model an agent currently resolves to: its own override, else the global default.
+fn resolve_agent_effective_model(cfg: &Value, agent_id: &str) -> Option<String> {
+    let agent_model = cfg
+        .pointer("/agents/list")
+        .and_then(Value::as_array)
+        .and_then(|agents| {
+            agents
+                .iter()
+                .find(|agent| agent.get("id").and_then(Value::as_str) == Some(agent_id))
+        })
+        .and_then(|agent| agent.get("model"))
+        .and_then(read_model_value);
+    agent_model.or_else(|| collect_model_summary(cfg).global_default_model)
 }
 
-/// Fetch a Discord guild name via the Discord REST API using a bot token.
-fn fetch_discord_guild_name(bot_token: &str, guild_id: &str) -> Result<String, String> {
-    let url = format!("https://discord.com/api/v10/guilds/{guild_id}");
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(3))
-        .build()
-        .map_err(|e| format!("Discord HTTP client error: {e}"))?;
-    let resp = client
-        .get(&url)
-        .header("Authorization", format!("Bot {bot_token}"))
-        .send()
-        .map_err(|e| format!("Discord API request failed: {e}"))?;
-    if !resp.status().is_success() {
-        return Err(format!("Discord API returned status {}", resp.status()));
-    }
-    let body: Value = resp.json().map_err(|e| format!("Failed to parse Discord response: {e}"))?;
-    body.get("name")
-        .and_then(Value::as_str)
-        .map(|s| s.to_string())
-        .ok_or_else(|| "No name field in Discord guild response".to_string())
+/// Sessions may record a bare model id while config uses `provider/model`.
+fn model_refs_match(a: &str, b: &str) -> bool {
+    let a = normalize_model_ref(a);
+    let b = normalize_model_ref(b);
+    a == b || a.ends_with(&format!("/{b}")) || b.ends_with(&format!("/{a}"))
 }
 
-fn collect_channel_summary(cfg: &Value) -> ChannelSummary {
-    let examples = collect_channel_model_overrides_list(cfg);
-    let configured_channels = cfg
-        .get("channels")
-        .and_then(|v| v.as_object())
-        .map(|channels| channels.len())
-        .unwrap_or(0);
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelTokenSession {
+    pub agent: String,
+    pub session_id: String,
+    pub kind: String,
+    pub tokens: u64,
+    pub age_days: f64,
+}
 
-    ChannelSummary {
-        configured_channels,
-        channel_model_overrides: examples.len(),
-        channel_examples: examples,
-    }
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelTokenUsage {
+    pub model: String,
+    pub total_tokens: u64,
+    pub sessions: Vec<ModelTokenSession>,
 }
 
-fn read_model_value(value: &Value) -> Option<String> {
-    if let Some(value) = value.as_str() {
-        return Some(value.to_string());
+/// Sessions that ran on `model`, heaviest token users first.
+#[tauri::command]
+pub async fn sessions_for_model_tokens(model: String) -> Result<ModelTokenUsage, String> {
+    let model = model.trim().to_string();
+    if model.is_empty() {
+        return Err("model is required".into());
     }
+    let analysis = tauri::async_runtime::spawn_blocking(analyze_sessions_sync)
+        .await
+        .map_err(|e| e.to_string())??;
+    let mut sessions: Vec<ModelTokenSession> = analysis
+        .into_iter()
+        .flat_map(|agent| agent.sessions)
+        .filter(|s| s.model.as_deref().is_some_and(|m| model_refs_match(m, &model)))
+        .map(|s| ModelTokenSession {
+            agent: s.agent,
+            session_id: s.session_id,
+            kind: s.kind,
+            tokens: s.total_tokens,
+            age_days: s.age_days,
+        })
+        .collect();
+    sessions.sort_by(|a, b| b.tokens.cmp(&a.tokens));
+    Ok(ModelTokenUsage {
+        total_tokens: sessions.iter().map(|s| s.tokens).sum(),
+        model,
+        sessions,
+    })
+}
 
-    if let Some(model_obj) = value.as_object() {
-        if let Some(primary) = model_obj.get("primary").and_then(Value::as_str) {
-            return Some(primary.to_string());
-        }
-        if let Some(name) = model_obj.get("name").and_then(Value::as_str) {
-            return Some(name.to_string());
-        }
-        if let Some(model) = model_obj.get("model").and_then(Value::as_str) {
-            return Some(model.to_string());
-        }
-        if let Some(model) = model_obj.get("default").and_then(Value::as_str) {
-            return Some(model.to_string());
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionModelDrift {
+    pub agent: String,
+    pub session_key: String,
+    pub session_id: String,
+    pub session_model: String,
+    pub effective_model: Option<String>,
+    pub updated_at: Option<f64>,
+}
+
+#[tauri::command]
+pub fn session_model_drift(agent_id: Option<String>) -> Result<Vec<SessionModelDrift>, String> {
+    if let Some(ref id) = agent_id {
+        if id.contains("..") || id.contains('/') || id.contains('\\') {
+            return Err("invalid agent id".into());
         }
-        if let Some(v) = model_obj.get("provider").and_then(Value::as_str) {
-            if let Some(inner) = model_obj.get("id").and_then(Value::as_str) {
-                return Some(format!("{v}/{inner}"));
+    }
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    let agents_root = paths.base_dir.join("agents");
+    if !agents_root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let agent_ids: Vec<String> = match agent_id {
+        Some(id) => vec![id],
+        None => fs::read_dir(&agents_root)
+            .map_err(|e| e.to_string())?
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect(),
+    };
+
+    let mut drift = Vec::new();
+    for agent in agent_ids {
+        let effective_model = resolve_agent_effective_model(&cfg, &agent);
+        let sessions_meta = read_sessions_meta(&agents_root.join(&agent));
+        for (key, meta) in sessions_meta {
+            let Some(session_model) = meta.get("model").and_then(Value::as_str) else {
+                continue;
+            };
+            let matches = effective_model
+                .as_deref()
+                .map(|model| model_refs_match(model, session_model))
+                .unwrap_or(false);
+            if matches {
+                continue;
             }
+            drift.push(SessionModelDrift {
+                agent: agent.clone(),
+                session_key: key,
+                session_id: meta.get("sessionId").and_then(Value::as_str).unwrap_or_default().to_string(),
+                session_model: session_model.to_string(),
+                effective_model: effective_model.clone(),
+                updated_at: meta.get("updatedAt").and_then(Value::as_f64),
+            });
         }
     }
-    None
+    drift.sort_by(|a, b| a.agent.cmp(&b.agent).then_with(|| a.session_key.cmp(&b.session_key)));
+    Ok(drift)
 }
 
-fn collect_channel_model_overrides(cfg: &Value) -> Vec<String> {
-    collect_channel_model_overrides_list(cfg)
+#[tauri::command]
+pub fn list_recipes(source: Option<String>) -> Result<Vec<crate::recipe::Recipe>, String> {
+    let paths = resolve_paths();
+    let default_path = paths.clawpal_dir.join("recipes").join("recipes.json");
+    Ok(load_recipes_with_fallback(source, &default_path))
 }
 
-fn collect_channel_model_overrides_list(cfg: &Value) -> Vec<String> {
-    let mut out = Vec::new();
-    if let Some(channels) = cfg.get("channels").and_then(Value::as_object) {
-        for (name, entry) in channels {
-            let mut branch = Vec::new();
-            collect_channel_paths(name, entry, &mut branch);
-            out.extend(branch);
-        }
+/// Strictly validate a recipes file, unlike `list_recipes` which silently
+/// falls back to the built-in set when the file is malformed.
+#[tauri::command]
+pub async fn validate_recipes(source: Option<String>) -> Result<crate::recipe::RecipesValidationReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let paths = resolve_paths();
+        let default_path = paths.clawpal_dir.join("recipes").join("recipes.json");
+        let source = crate::recipe::primary_recipes_source(source, &default_path);
+        Ok(crate::recipe::validate_recipes_source(&source))
+    }).await.map_err(|e| e.to_string())?
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeHealthImpact {
+    pub resolved: Vec<crate::doctor::DoctorIssue>,
+    pub introduced: Vec<crate::doctor::DoctorIssue>,
+    pub unchanged: usize,
+}
+
+/// Run the doctor's config checks against the current config and the
+/// candidate a recipe would produce, and report the difference.
+#[tauri::command]
+pub fn recipe_health_impact(
+    patch_template: String,
+    params: Map<String, Value>,
+) -> Result<RecipeHealthImpact, String> {
+    let paths = resolve_paths();
+    let current = read_openclaw_config(&paths)?;
+    let (candidate, _changes) = build_candidate_config_from_template(&current, &patch_template, &params)?;
+    Ok(diff_health_issues(
+        crate::doctor::check_config(&current),
+        crate::doctor::check_config(&candidate),
+    ))
+}
+
+/// Compare issues by id and message, so a new offender in an existing issue
+/// category (same id, different message) counts as introduced.
+fn diff_health_issues(
+    before: Vec<crate::doctor::DoctorIssue>,
+    after: Vec<crate::doctor::DoctorIssue>,
+) -> RecipeHealthImpact {
+    let key = |i: &crate::doctor::DoctorIssue| (i.id.clone(), i.message.clone());
+    let before_keys: HashSet<(String, String)> = before.iter().map(key).collect();
+    let after_keys: HashSet<(String, String)> = after.iter().map(key).collect();
+    let unchanged = before_keys.intersection(&after_keys).count();
+    RecipeHealthImpact {
+        resolved: before.into_iter().filter(|i| !after_keys.contains(&key(i))).collect(),
+        introduced: after.into_iter().filter(|i| !before_keys.contains(&key(i))).collect(),
+        unchanged,
     }
-    out
 }
 
-fn collect_channel_paths(prefix: &str, node: &Value, out: &mut Vec<String>) {
-    if let Some(obj) = node.as_object() {
-        if let Some(model) = obj.get("model").and_then(read_model_value) {
-            out.push(format!("{prefix} => {model}"));
-        }
-        for (key, child) in obj {
-            if key == "model" {
-                continue;
-            }
-            let next = format!("{prefix}.{key}");
-            collect_channel_paths(&next, child, out);
-        }
+#[cfg(test)]
+mod recipe_health_impact_tests {
+    use super::*;
+
+    #[test]
+    fn a_new_offender_in_an_existing_category_is_introduced() {
+        let before = serde_json::json!({
+            "agents": { "list": [{ "id": "ops" }] },
+            "bindings": [{ "agentId": "ghost", "match": { "channel": "discord" } }]
+        });
+        let mut after = before.clone();
+        after["bindings"].as_array_mut().unwrap().push(
+            serde_json::json!({ "agentId": "phantom", "match": { "channel": "discord" } }),
+        );
+        let impact = diff_health_issues(
+            crate::doctor::check_config(&before),
+            crate::doctor::check_config(&after),
+        );
+        assert!(impact.introduced.iter().any(|i| i.id == "bindings.missing_agent"));
+        assert!(impact.resolved.iter().any(|i| i.id == "bindings.missing_agent"));
     }
 }
 
-fn collect_memory_overview(base_dir: &Path) -> MemorySummary {
-    let memory_root = base_dir.join("memory");
-    collect_file_inventory(&memory_root, Some(80))
+#[tauri::command]
+pub fn apply_config_patch(
+    patch_template: String,
+    params: Map<String, Value>,
+) -> Result<ApplyResult, String> {
+    let paths = resolve_paths();
+    ensure_dirs(&paths)?;
+    let current = read_openclaw_config(&paths)?;
+    let current_text = serde_json::to_string_pretty(&current).map_err(|e| e.to_string())?;
+    let snapshot = add_snapshot(
+        &paths.history_dir,
+        &paths.metadata_path,
+        Some("config-patch".into()),
+        "apply",
+        true,
+        &current_text,
+        None,
+    )?;
+    let (candidate, _changes) = build_candidate_config_from_template(&current, &patch_template, &params)?;
+    write_json(&paths.config_path, &candidate)?;
+    Ok(ApplyResult {
+        ok: true,
+        snapshot_id: Some(snapshot.id),
+        config_path: paths.config_path.to_string_lossy().to_string(),
+        backup_path: Some(snapshot.config_path),
+        warnings: Vec::new(),
+        errors: Vec::new(),
+    })
 }
 
-fn collect_file_inventory(path: &Path, max_files: Option<usize>) -> MemorySummary {
-    let mut queue = VecDeque::new();
-    let mut file_count = 0usize;
-    let mut total_bytes = 0u64;
-    let mut files = Vec::new();
+/// Locate the node at a JSON pointer, creating empty objects for missing
+/// segments along the way.
+fn pointer_target_mut<'a>(root: &'a mut Value, pointer: &str) -> Result<&'a mut Value, String> {
+    if pointer.is_empty() {
+        return Ok(root);
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(format!("invalid JSON pointer: {pointer}"));
+    };
+    let mut cur = root;
+    for raw in rest.split('/') {
+        let key = raw.replace("~1", "/").replace("~0", "~");
+        let obj = cur
+            .as_object_mut()
+            .ok_or_else(|| format!("cannot descend into non-object at {key}"))?;
+        cur = obj.entry(key).or_insert_with(|| Value::Object(Default::default()));
+    }
+    Ok(cur)
+}
 
-    if !path.exists() {
-        return MemorySummary {
-            file_count: 0,
-            total_bytes: 0,
-            files,
-        };
+/// Apply `fragment` at `pointer` and list one change per path it actually
+/// touches, so an additive merge reports only `add` items.
+fn splice_config_fragment(
+    cfg: &Value,
+    pointer: &str,
+    fragment: Value,
+    strategy: &str,
+) -> Result<(Value, Vec<crate::recipe::ChangeItem>), String> {
+    let prefix = pointer
+        .split('/')
+        .skip(1)
+        .map(|raw| raw.replace("~1", "/").replace("~0", "~"))
+        .collect::<Vec<_>>()
+        .join(".");
+    let existed = cfg.pointer(pointer).is_some();
+    let mut next = cfg.clone();
+    let target = pointer_target_mut(&mut next, pointer)?;
+    let mut ops = Vec::new();
+    match strategy {
+        "merge" => crate::recipe::apply_merge_patch(target, &fragment, &prefix, &mut ops),
+        "replace" => {
+            *target = fragment;
+            ops.push(crate::recipe::ChangeItem {
+                path: prefix,
+                op: if existed { "replace".into() } else { "add".into() },
+                risk: "medium".into(),
+                reason: None,
+            });
+        }
+        other => return Err(format!("unknown strategy: {other} (expected merge or replace)")),
     }
 
-    queue.push_back(path.to_path_buf());
-    while let Some(current) = queue.pop_front() {
-        let entries = match fs::read_dir(&current) {
-            Ok(entries) => entries,
-            Err(_) => continue,
-        };
-        for entry in entries.flatten() {
-            let entry_path = entry.path();
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_dir() {
-                    queue.push_back(entry_path);
-                    continue;
-                }
-                if metadata.is_file() {
-                    file_count += 1;
-                    total_bytes = total_bytes.saturating_add(metadata.len());
-                    if max_files.is_none_or(|limit| files.len() < limit) {
-                        files.push(MemoryFileSummary {
-                            path: entry_path.to_string_lossy().to_string(),
-                            size_bytes: metadata.len(),
-                        });
-                    }
-                }
-            }
+    // Merge-patch records a replace even when the value is unchanged; keep
+    // only ops at or above a path that really differs.
+    let changed = crate::recipe::collect_changed_config_paths(cfg, &next);
+    let mut changes = Vec::new();
+    for mut op in ops {
+        if op.path.is_empty() {
+            op.path = "root".into();
+        }
+        let nested = format!("{}.", op.path);
+        let touched = op.path == "root" || changed.iter().any(|p| *p == op.path || p.starts_with(&nested));
+        if touched && !changed.is_empty() {
+            changes.push(op);
         }
     }
+    Ok((next, changes))
+}
 
-    files.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
-    MemorySummary {
-        file_count,
-        total_bytes,
-        files,
+/// Splice `fragment` into the config at `pointer`. `merge` deep-merges with
+/// JSON merge-patch semantics (null removes a key); `replace` overwrites.
+#[tauri::command]
+pub fn merge_config_fragment(
+    pointer: String,
+    fragment: Value,
+    strategy: String,
+) -> Result<PreviewResult, String> {
+    let pointer = pointer.trim().to_string();
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+
+    let (next, changes) = splice_config_fragment(&cfg, &pointer, fragment, &strategy)?;
+    if !next.is_object() {
+        return Err("resulting config must be a JSON object".into());
+    }
+    let after_text = serde_json::to_string_pretty(&next).map_err(|e| e.to_string())?;
+    serde_json::from_str::<Map<String, Value>>(&after_text).map_err(|e| e.to_string())?;
+
+    let overwrites_existing = changes.iter().any(|c| c.op != "add");
+    if !changes.is_empty() {
+        write_config_with_snapshot(&paths, &current, &next, "merge-fragment")?;
     }
+    Ok(PreviewResult {
+        recipe_id: "merge-fragment".into(),
+        diff: format_diff(&cfg, &next),
+        config_before: current,
+        config_after: after_text,
+        changes,
+        overwrites_existing,
+        can_rollback: true,
+        impact_level: if strategy == "replace" { "medium".into() } else { "low".into() },
+        warnings: Vec::new(),
+    })
 }
 
-fn collect_session_overview(base_dir: &Path) -> SessionSummary {
-    let agents_dir = base_dir.join("agents");
-    let mut by_agent = Vec::new();
-    let mut total_session_files = 0usize;
-    let mut total_archive_files = 0usize;
-    let mut total_bytes = 0u64;
+#[cfg(test)]
+mod config_fragment_tests {
+    use super::*;
 
-    if !agents_dir.exists() {
-        return SessionSummary {
-            total_session_files,
-            total_archive_files,
-            total_bytes,
-            by_agent,
-        };
+    #[test]
+    fn additive_merge_does_not_overwrite() {
+        let cfg = serde_json::json!({ "agents": { "defaults": { "model": "a" } } });
+        let fragment = serde_json::json!({ "workspace": "~/w", "model": "a" });
+        let (next, changes) = splice_config_fragment(&cfg, "/agents/defaults", fragment, "merge").unwrap();
+        assert_eq!(next["agents"]["defaults"]["workspace"], "~/w");
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["agents.defaults.workspace"]);
+        assert!(!changes.iter().any(|c| c.op != "add"), "additive merge must not overwrite");
     }
 
-    if let Ok(entries) = fs::read_dir(agents_dir) {
-        for entry in entries.flatten() {
-            let agent_path = entry.path();
-            if !agent_path.is_dir() {
-                continue;
-            }
-            let agent = entry.file_name().to_string_lossy().to_string();
-            let sessions_dir = agent_path.join("sessions");
-            let archive_dir = agent_path.join("sessions_archive");
+    #[test]
+    fn merge_over_an_existing_value_is_an_overwrite() {
+        let cfg = serde_json::json!({ "agents": { "defaults": { "model": "a" } } });
+        let fragment = serde_json::json!({ "model": "b" });
+        let (_, changes) = splice_config_fragment(&cfg, "/agents/defaults", fragment, "merge").unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "agents.defaults.model");
+        assert_eq!(changes[0].op, "replace");
+    }
+}
 
-            let session_info = collect_file_inventory_with_limit(&sessions_dir);
-            let archive_info = collect_file_inventory_with_limit(&archive_dir);
+#[tauri::command]
+pub async fn restart_gateway() -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        run_openclaw_raw(&["gateway", "restart"])?;
+        Ok(true)
+    }).await.map_err(|e| e.to_string())?
+}
 
-            if session_info.files > 0 || archive_info.files > 0 {
-                by_agent.push(AgentSessionSummary {
-                    agent: agent.clone(),
-                    session_files: session_info.files,
-                    archive_files: archive_info.files,
-                    total_bytes: session_info.total_bytes.saturating_add(archive_info.total_bytes),
-                });
-            }
+/// Top-level subcommands `run_openclaw` may invoke.
+const OPENCLAW_SUBCOMMAND_ALLOWLIST: &[&str] = &[
+    "agents", "channels", "config", "cron", "doctor", "gateway", "health", "logs",
+    "memory", "models", "plugins", "sessions", "status", "update", "--version", "--help",
+];
 
-            total_session_files = total_session_files.saturating_add(session_info.files);
-            total_archive_files = total_archive_files.saturating_add(archive_info.files);
-            total_bytes = total_bytes
-                .saturating_add(session_info.total_bytes)
-                .saturating_add(archive_info.total_bytes);
-        }
+fn validate_openclaw_args(args: &[String]) -> Result<(), String> {
+    let Some(sub) = args.first() else {
+        return Err("openclaw subcommand is required".into());
+    };
+    if !OPENCLAW_SUBCOMMAND_ALLOWLIST.contains(&sub.as_str()) {
+        return Err(format!(
+            "openclaw subcommand not allowed: {sub} (allowed: {})",
+            OPENCLAW_SUBCOMMAND_ALLOWLIST.join(", ")
+        ));
     }
-
-    by_agent.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
-    SessionSummary {
-        total_session_files,
-        total_archive_files,
-        total_bytes,
-        by_agent,
+    if args.iter().any(|a| a.contains('\0')) {
+        return Err("openclaw arguments must not contain NUL bytes".into());
     }
+    Ok(())
 }
 
-struct InventorySummary {
-    files: usize,
-    total_bytes: u64,
+/// Run an allowlisted openclaw subcommand that ClawPal has no dedicated
+/// wrapper for. Args are passed straight to the binary, never via a shell.
+#[tauri::command]
+pub async fn run_openclaw(args: Vec<String>, timeout_secs: Option<u64>) -> Result<OpenclawCommandOutput, String> {
+    validate_openclaw_args(&args)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        run_openclaw_raw_timeout(&refs, timeout_secs)
+    }).await.map_err(|e| e.to_string())?
 }
 
-fn collect_file_inventory_with_limit(path: &Path) -> InventorySummary {
-    if !path.exists() {
-        return InventorySummary {
-            files: 0,
-            total_bytes: 0,
-        };
-    }
-    let mut queue = VecDeque::new();
-    let mut files = 0usize;
-    let mut total_bytes = 0u64;
-    queue.push_back(path.to_path_buf());
-    while let Some(current) = queue.pop_front() {
-        let entries = match fs::read_dir(&current) {
-            Ok(entries) => entries,
-            Err(_) => continue,
-        };
-        for entry in entries.flatten() {
-            if let Ok(metadata) = entry.metadata() {
-                let p = entry.path();
-                if metadata.is_dir() {
-                    queue.push_back(p);
-                } else if metadata.is_file() {
-                    files += 1;
-                    total_bytes = total_bytes.saturating_add(metadata.len());
-                }
-            }
-        }
-    }
-    InventorySummary {
-        files,
-        total_bytes,
-    }
-}
+#[cfg(test)]
+mod openclaw_passthrough_tests {
+    use super::*;
 
-fn list_session_files_detailed(base_dir: &Path) -> Result<Vec<SessionFile>, String> {
-    let agents_root = base_dir.join("agents");
-    if !agents_root.exists() {
-        return Ok(Vec::new());
+    #[test]
+    fn only_allowlisted_subcommands_run() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(validate_openclaw_args(&args(&["plugins", "list", "--json"])).is_ok());
+        assert!(validate_openclaw_args(&args(&["--version"])).is_ok());
+        assert!(validate_openclaw_args(&[]).is_err());
+        assert!(validate_openclaw_args(&args(&["rm", "-rf", "/"])).is_err());
+        assert!(validate_openclaw_args(&args(&["status", "a\0b"])).is_err());
     }
-    let mut out = Vec::new();
-    let entries = fs::read_dir(&agents_root).map_err(|e| e.to_string())?;
-    for entry in entries.flatten() {
-        let entry_path = entry.path();
-        if !entry_path.is_dir() {
-            continue;
-        }
-        let agent = entry.file_name().to_string_lossy().to_string();
-        let sessions_root = entry_path.join("sessions");
-        let archive_root = entry_path.join("sessions_archive");
+}
 
-        collect_session_files_in_scope(&sessions_root, &agent, "sessions", base_dir, &mut out)?;
-        collect_session_files_in_scope(&archive_root, &agent, "archive", base_dir, &mut out)?;
-    }
-    out.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
-    Ok(out)
+#[tauri::command]
+pub fn list_history(limit: usize, offset: usize) -> Result<HistoryPage, String> {
+    let paths = resolve_paths();
+    let index = list_snapshots(&paths.metadata_path)?;
+    let items = index
+        .items
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|item| HistoryItem {
+            id: item.id,
+            recipe_id: item.recipe_id,
+            created_at: item.created_at,
+            source: item.source,
+            can_rollback: item.can_rollback,
+            rollback_of: item.rollback_of,
+            label: item.label,
+        })
+        .collect();
+    Ok(HistoryPage { items })
 }
 
-fn collect_session_files_in_scope(
-    scope_root: &Path,
-    agent: &str,
-    kind: &str,
-    base_dir: &Path,
-    out: &mut Vec<SessionFile>,
-) -> Result<(), String> {
-    if !scope_root.exists() {
-        return Ok(());
-    }
-    let mut queue = VecDeque::new();
-    queue.push_back(scope_root.to_path_buf());
-    while let Some(current) = queue.pop_front() {
-        let entries = match fs::read_dir(&current) {
-            Ok(entries) => entries,
-            Err(_) => continue,
-        };
-        for entry in entries.flatten() {
-            let entry_path = entry.path();
-            let metadata = match entry.metadata() {
-                Ok(meta) => meta,
-                Err(_) => continue,
-            };
-            if metadata.is_dir() {
-                queue.push_back(entry_path);
-                continue;
-            }
-            if metadata.is_file() {
-                let relative_path = entry_path
-                    .strip_prefix(base_dir)
-                    .unwrap_or(&entry_path)
-                    .to_string_lossy()
-                    .to_string();
-                out.push(SessionFile {
-                    path: entry_path.to_string_lossy().to_string(),
-                    relative_path,
-                    agent: agent.to_string(),
-                    kind: kind.to_string(),
-                    size_bytes: metadata.len(),
-                });
-            }
+#[tauri::command]
+pub fn set_snapshot_label(snapshot_id: String, label: Option<String>) -> Result<bool, String> {
+    let paths = resolve_paths();
+    crate::history::set_snapshot_label(&paths.metadata_path, &snapshot_id, label)
+}
+
+/// Snapshot ids that fall outside the retention window. `entries` are
+/// `(id, unix_ts, rollback_of)` sorted newest first; an entry is pruned when
+/// it is past `keep_last` or older than `older_than_days`. Snapshots that a
+/// kept snapshot rolled back from, directly or through a chain of rollbacks,
+/// are always kept.
+fn snapshots_outside_retention(
+    entries: &[(String, Option<i64>, Option<String>)],
+    keep_last: Option<usize>,
+    older_than_days: Option<u64>,
+) -> Vec<String> {
+    let cutoff = older_than_days
+        .map(|days| unix_timestamp_secs().saturating_sub(days.saturating_mul(86_400)) as i64);
+    let mut prune: Vec<bool> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, (_, ts, _))| {
+            keep_last.is_some_and(|n| i >= n)
+                || cutoff.is_some_and(|c| ts.is_some_and(|ts| ts < c))
+        })
+        .collect();
+    // Follow each kept snapshot's `rollback_of` chain back to its origin.
+    let index_of: HashMap<&str, usize> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, (id, _, _))| (id.as_str(), i))
+        .collect();
+    let mut pending: Vec<usize> = (0..entries.len()).filter(|&i| !prune[i]).collect();
+    while let Some(i) = pending.pop() {
+        let target = entries[i].2.as_deref().and_then(|of| index_of.get(of)).copied();
+        if let Some(j) = target.filter(|&j| prune[j]) {
+            prune[j] = false;
+            pending.push(j);
         }
     }
-    Ok(())
+    entries
+        .iter()
+        .zip(prune)
+        .filter(|(_, pruned)| *pruned)
+        .map(|((id, _, _), _)| id.clone())
+        .collect()
 }
 
-fn clear_agent_and_global_sessions(agents_root: &Path, agent_id: Option<&str>) -> Result<usize, String> {
-    if !agents_root.exists() {
-        return Ok(0);
-    }
-    let mut total = 0usize;
-    let mut targets = Vec::new();
-
-    match agent_id {
-        Some(agent) => targets.push(agents_root.join(agent)),
-        None => {
-            for entry in fs::read_dir(agents_root).map_err(|e| e.to_string())? {
-                let entry = entry.map_err(|e| e.to_string())?;
-                if entry
-                    .file_type()
-                    .map_err(|e| e.to_string())?
-                    .is_dir()
-                {
-                    targets.push(entry.path());
-                }
-            }
-        }
-    }
+#[cfg(test)]
+mod snapshot_retention_tests {
+    use super::*;
 
-    for agent_path in targets {
-        let sessions = agent_path.join("sessions");
-        let archive = agent_path.join("sessions_archive");
-        total = total.saturating_add(clear_directory_contents(&sessions)?);
-        total = total.saturating_add(clear_directory_contents(&archive)?);
-        fs::create_dir_all(&sessions).map_err(|e| e.to_string())?;
-        fs::create_dir_all(&archive).map_err(|e| e.to_string())?;
+    #[test]
+    fn kept_snapshots_protect_their_whole_rollback_chain() {
+        let entry = |id: &str, of: Option<&str>| (id.to_string(), Some(0), of.map(str::to_string));
+        let entries = vec![
+            entry("s4", Some("s3")),
+            entry("s3", Some("s2")),
+            entry("s2", None),
+            entry("s1", None),
+        ];
+        assert_eq!(snapshots_outside_retention(&entries, Some(1), None), vec!["s1".to_string()]);
+        assert_eq!(snapshots_outside_retention(&entries, Some(0), None).len(), 4);
     }
-    Ok(total)
 }
 
-fn clear_directory_contents(target: &Path) -> Result<usize, String> {
-    if !target.exists() {
+/// Delete history snapshots outside the retention window. The index is
+/// rewritten first so an interrupted prune only leaves orphaned files.
+#[tauri::command]
+pub fn prune_snapshots(keep_last: Option<usize>, older_than_days: Option<u64>) -> Result<usize, String> {
+    if keep_last.is_none() && older_than_days.is_none() {
+        return Err("specify keep_last and/or older_than_days".into());
+    }
+    let paths = resolve_paths();
+    let mut index = list_snapshots(&paths.metadata_path)?;
+    index.items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    let entries: Vec<(String, Option<i64>, Option<String>)> = index
+        .items
+        .iter()
+        .map(|item| {
+            let ts = chrono::NaiveDateTime::parse_from_str(&item.created_at, "%Y-%m-%dT%H-%M-%S")
+                .ok()
+                .map(|dt| dt.and_utc().timestamp());
+            (item.id.clone(), ts, item.rollback_of.clone())
+        })
+        .collect();
+    let doomed: HashSet<String> = snapshots_outside_retention(&entries, keep_last, older_than_days)
+        .into_iter()
+        .collect();
+    if doomed.is_empty() {
         return Ok(0);
     }
-    let mut total = 0usize;
-    let entries = fs::read_dir(target).map_err(|e| e.to_string())?;
-    for entry in entries {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-        let metadata = entry
-            .metadata()
-            .map_err(|e| e.to_string())?;
-        if metadata.is_dir() {
-            total = total.saturating_add(clear_directory_contents(&path)?);
-            fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
-            continue;
-        }
-        if metadata.is_file() || metadata.is_symlink() {
-            fs::remove_file(&path).map_err(|e| e.to_string())?;
-            total = total.saturating_add(1);
+    let (removed, kept): (Vec<_>, Vec<_>) = index.items.into_iter().partition(|item| doomed.contains(&item.id));
+    index.items = kept;
+    crate::history::write_snapshots(&paths.metadata_path, &index)?;
+    for item in &removed {
+        let path = PathBuf::from(&item.config_path);
+        if path.starts_with(&paths.history_dir) {
+            let _ = fs::remove_file(&path);
         }
     }
-    Ok(total)
+    Ok(removed.len())
 }
 
-fn model_profiles_path(paths: &crate::models::OpenClawPaths) -> std::path::PathBuf {
-    paths.clawpal_dir.join("model-profiles.json")
+/// Delete one snapshot and its index entry. Refuses while another snapshot
+/// records it as `rollback_of`, so rollback chains stay intact.
+#[tauri::command]
+pub fn delete_snapshot(snapshot_id: String) -> Result<bool, String> {
+    let paths = resolve_paths();
+    let mut index = list_snapshots(&paths.metadata_path)?;
+    let Some(pos) = index.items.iter().position(|item| item.id == snapshot_id) else {
+        return Ok(false);
+    };
+    if let Some(dependent) = index
+        .items
+        .iter()
+        .find(|item| item.rollback_of.as_deref() == Some(snapshot_id.as_str()))
+    {
+        return Err(format!("snapshot is referenced by rollback {}", dependent.id));
+    }
+    let removed = index.items.remove(pos);
+    crate::history::write_snapshots(&paths.metadata_path, &index)?;
+    let path = PathBuf::from(&removed.config_path);
+    if path.starts_with(&paths.history_dir) {
+        let _ = fs::remove_file(&path);
+    }
+    Ok(true)
 }
 
-
-
-fn profile_to_model_value(profile: &ModelProfile) -> String {
-    if profile.model.contains('/') {
-        profile.model.clone()
+/// Stored config text of a snapshot, for inspection without rolling back.
+#[tauri::command]
+pub fn get_snapshot_config(snapshot_id: String) -> Result<String, String> {
+    let paths = resolve_paths();
+    let index = list_snapshots(&paths.metadata_path)?;
+    let meta = index
+        .items
+        .iter()
+        .find(|item| item.id == snapshot_id)
+        .ok_or_else(|| "snapshot not found".to_string())?;
+    read_snapshot(&meta.config_path)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChangeLogEntry {
+    pub snapshot_id: String,
+    pub created_at: String,
+    pub source: String,
+    pub recipe_id: Option<String>,
+    pub changed_paths: Vec<String>,
+}
+
+fn read_snapshot_value(meta: &crate::history::SnapshotMeta) -> Option<Value> {
+    let text = read_snapshot(&meta.config_path).ok()?;
+    json5::from_str(&text).ok()
+}
+
+/// Timeline of the newest `limit` snapshots, each diffed against the one
+/// before it. The oldest snapshot in the window is diffed against an empty config.
+#[tauri::command]
+pub fn config_change_log(limit: usize) -> Result<Vec<ConfigChangeLogEntry>, String> {
+    let paths = resolve_paths();
+    let index = list_snapshots(&paths.metadata_path)?;
+    // Index is sorted newest first; take one extra so the oldest entry in
+    // the window still has a predecessor when one exists.
+    let window: Vec<_> = index.items.into_iter().take(limit.saturating_add(1)).collect();
+    let values: Vec<Option<Value>> = window.iter().map(read_snapshot_value).collect();
+
+    let empty = Value::Object(Default::default());
+    let mut entries = Vec::new();
+    for (i, meta) in window.iter().enumerate().take(limit) {
+        let current = values[i].as_ref().unwrap_or(&empty);
+        let previous = values.get(i + 1).and_then(Option::as_ref).unwrap_or(&empty);
+        entries.push(ConfigChangeLogEntry {
+            snapshot_id: meta.id.clone(),
+            created_at: meta.created_at.clone(),
+            source: meta.source.clone(),
+            recipe_id: meta.recipe_id.clone(),
+            changed_paths: crate::recipe::collect_changed_config_paths(previous, current),
+        });
+    }
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn preview_rollback(snapshot_id: String) -> Result<PreviewResult, String> {
+    let paths = resolve_paths();
+    let index = list_snapshots(&paths.metadata_path)?;
+    let target = index
+        .items
+        .into_iter()
+        .find(|s| s.id == snapshot_id)
+        .ok_or_else(|| "snapshot not found".to_string())?;
+    if !target.can_rollback {
+        return Err("snapshot is not rollbackable".to_string());
+    }
+
+    let current = read_openclaw_config(&paths)?;
+    let target_text = read_snapshot(&target.config_path)?;
+    let target_json: Value = json5::from_str(&target_text).unwrap_or(Value::Object(Default::default()));
+    let before_text = serde_json::to_string_pretty(&current).unwrap_or_else(|_| "{}".into());
+    let after_text = serde_json::to_string_pretty(&target_json).unwrap_or_else(|_| "{}".into());
+    Ok(PreviewResult {
+        recipe_id: "rollback".into(),
+        diff: format_diff(&current, &target_json),
+        config_before: before_text,
+        config_after: after_text,
+        changes: collect_change_paths(&current, &target_json),
+        overwrites_existing: true,
+        can_rollback: true,
+        impact_level: "medium".into(),
+        warnings: vec!["Rollback will replace current configuration".into()],
+    })
+}
+
+/// Diff two history snapshots. The result describes the changes going from
+/// `from_id` to `to_id`, so swapping the ids reverses the diff.
+#[tauri::command]
+pub fn diff_snapshots(from_id: String, to_id: String) -> Result<PreviewResult, String> {
+    let paths = resolve_paths();
+    let index = list_snapshots(&paths.metadata_path)?;
+    let load = |id: &str| -> Result<Value, String> {
+        let meta = index
+            .items
+            .iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| format!("snapshot not found: {id}"))?;
+        let text = read_snapshot(&meta.config_path)?;
+        json5::from_str(&text).map_err(|e| format!("snapshot {id} is not valid JSON: {e}"))
+    };
+    let from = load(&from_id)?;
+    let to = load(&to_id)?;
+    Ok(PreviewResult {
+        recipe_id: "snapshot-diff".into(),
+        diff: format_diff(&from, &to),
+        config_before: serde_json::to_string_pretty(&from).unwrap_or_else(|_| "{}".into()),
+        config_after: serde_json::to_string_pretty(&to).unwrap_or_else(|_| "{}".into()),
+        changes: collect_change_paths(&from, &to),
+        overwrites_existing: false,
+        can_rollback: false,
+        impact_level: "low".into(),
+        warnings: Vec::new(),
+    })
+}
+
+#[tauri::command]
+pub fn rollback(snapshot_id: String) -> Result<ApplyResult, String> {
+    let paths = resolve_paths();
+    ensure_dirs(&paths)?;
+    let index = list_snapshots(&paths.metadata_path)?;
+    let target = index
+        .items
+        .into_iter()
+        .find(|s| s.id == snapshot_id)
+        .ok_or_else(|| "snapshot not found".to_string())?;
+    if !target.can_rollback {
+        return Err("snapshot is not rollbackable".to_string());
+    }
+    let target_text = read_snapshot(&target.config_path)?;
+    let backup = read_openclaw_config(&paths)?;
+    let backup_text = serde_json::to_string_pretty(&backup).map_err(|e| e.to_string())?;
+    let _ = add_snapshot(
+        &paths.history_dir,
+        &paths.metadata_path,
+        target.recipe_id.clone(),
+        "rollback",
+        true,
+        &backup_text,
+        Some(target.id.clone()),
+    )?;
+    write_text(&paths.config_path, &target_text)?;
+    Ok(ApplyResult {
+        ok: true,
+        snapshot_id: Some(target.id),
+        config_path: paths.config_path.to_string_lossy().to_string(),
+        backup_path: None,
+        warnings: vec!["rolled back".into()],
+        errors: Vec::new(),
+    })
+}
+
+#[tauri::command]
+pub async fn gateway_loaded_config_path() -> Result<crate::doctor::GatewayConfigCheck, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let paths = resolve_paths();
+        crate::doctor::check_gateway_config(&paths)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn run_doctor_command() -> Result<DoctorReport, String> {
+    let paths = resolve_paths();
+    Ok(run_doctor(&paths))
+}
+
+#[tauri::command]
+pub fn fix_issues(ids: Vec<String>) -> Result<FixResult, String> {
+    let paths = resolve_paths();
+    let issues = run_doctor(&paths);
+    let mut fixable = Vec::new();
+    for issue in issues.issues {
+        if ids.contains(&issue.id) && issue.auto_fixable {
+            fixable.push(issue.id);
+        }
+    }
+    let auto_applied = apply_auto_fixes(&paths, &fixable);
+    let mut remaining = Vec::new();
+    let mut applied = Vec::new();
+    for id in ids {
+        if fixable.contains(&id) && auto_applied.iter().any(|x| x == &id) {
+            applied.push(id);
+        } else {
+            remaining.push(id);
+        }
+    }
+    Ok(FixResult {
+        ok: true,
+        applied,
+        remaining_issues: remaining,
+    })
+}
+
+#[tauri::command]
+pub async fn remote_fix_issues(
+    pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
+    host_id: String,
+    ids: Vec<String>,
+) -> Result<FixResult, String> {
+    let raw = pool.sftp_read(&host_id, "~/.openclaw/openclaw.json").await?;
+    let mut cfg: Value = json5::from_str(&raw).unwrap_or_else(|_| Value::Object(Default::default()));
+    let mut applied = Vec::new();
+
+    for id in &ids {
+        match id.as_str() {
+            "field.agents" if cfg.get("agents").is_none() => {
+                let mut agents = serde_json::Map::new();
+                let mut defaults = serde_json::Map::new();
+                defaults.insert("model".into(), Value::String("anthropic/claude-sonnet-4-5".into()));
+                agents.insert("defaults".into(), Value::Object(defaults));
+                if let Value::Object(map) = &mut cfg {
+                    map.insert("agents".into(), Value::Object(agents));
+                }
+                applied.push(id.clone());
+            }
+            "json.syntax" => {
+                // If we got here, json5 already parsed it or fell back to empty object
+                applied.push(id.clone());
+            }
+            "field.port" => {
+                let mut gateway = cfg
+                    .get("gateway")
+                    .and_then(|v| v.as_object())
+                    .cloned()
+                    .unwrap_or_default();
+                gateway.insert("port".into(), Value::Number(serde_json::Number::from(18789_u64)));
+                if let Value::Object(map) = &mut cfg {
+                    map.insert("gateway".into(), Value::Object(gateway));
+                }
+                applied.push(id.clone());
+            }
+            _ => {}
+        }
+    }
+
+    if !applied.is_empty() {
+        let new_text = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+        remote_write_config_with_snapshot(&pool, &host_id, &raw, &cfg, "doctor-fix").await?;
+        cache.invalidate(&remote_agents_overview_cache_key(&host_id));
+        let _ = new_text; // written by remote_write_config_with_snapshot
+    }
+
+    let remaining: Vec<String> = ids.into_iter().filter(|id| !applied.contains(id)).collect();
+    Ok(FixResult {
+        ok: true,
+        applied,
+        remaining_issues: remaining,
+    })
+}
+
+fn collect_model_summary(cfg: &Value) -> ModelSummary {
+    let global_default_model = cfg
+        .pointer("/agents/defaults/model")
+        .and_then(|value| read_model_value(value))
+        .or_else(|| cfg.pointer("/agents/default/model").and_then(|value| read_model_value(value)));
+
+    let mut agent_overrides = Vec::new();
+    if let Some(agents) = cfg.pointer("/agents/list").and_then(Value::as_array) {
+        for agent in agents {
+            if let Some(model_value) = agent.get("model").and_then(read_model_value) {
+                let should_emit = global_default_model
+                    .as_ref()
+                    .map(|global| global != &model_value)
+                    .unwrap_or(true);
+                if should_emit {
+                    let id = agent
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .unwrap_or("agent");
+                    agent_overrides.push(format!("{id} => {model_value}"));
+                }
+            }
+        }
+    }
+    ModelSummary {
+        global_default_model,
+        agent_overrides,
+        channel_overrides: collect_channel_model_overrides(cfg),
+    }
+}
+
+
+fn run_openclaw_raw(args: &[&str]) -> Result<OpenclawCommandOutput, String> {
+    run_openclaw_raw_timeout(args, None)
+}
+
+fn run_openclaw_raw_timeout(args: &[&str], timeout_secs: Option<u64>) -> Result<OpenclawCommandOutput, String> {
+    let mut child = Command::new(resolve_openclaw_bin())
+        .args(args)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("failed to run openclaw: {error}"))?;
+
+    if let Some(secs) = timeout_secs {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(secs);
+        loop {
+            match child.try_wait().map_err(|e| e.to_string())? {
+                Some(status) => {
+                    let mut stdout_buf = Vec::new();
+                    let mut stderr_buf = Vec::new();
+                    if let Some(mut out) = child.stdout.take() {
+                        std::io::Read::read_to_end(&mut out, &mut stdout_buf).ok();
+                    }
+                    if let Some(mut err) = child.stderr.take() {
+                        std::io::Read::read_to_end(&mut err, &mut stderr_buf).ok();
+                    }
+                    let exit_code = status.code().unwrap_or(-1);
+                    let result = OpenclawCommandOutput {
+                        stdout: String::from_utf8_lossy(&stdout_buf).trim_end().to_string(),
+                        stderr: String::from_utf8_lossy(&stderr_buf).trim_end().to_string(),
+                        exit_code,
+                    };
+                    if exit_code != 0 {
+                        let details = if !result.stderr.is_empty() {
+                            result.stderr.clone()
+                        } else {
+                            result.stdout.clone()
+                        };
+                        return Err(format!("openclaw command failed ({exit_code}): {details}"));
+                    }
+                    return Ok(result);
+                }
+                None => {
+                    if std::time::Instant::now() >= deadline {
+                        let _ = child.kill();
+                        return Err(format!(
+                            "Command timed out after {secs}s. The gateway may still be restarting in the background."
+                        ));
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(250));
+                }
+            }
+        }
+    } else {
+        let output = child
+            .wait_with_output()
+            .map_err(|error| format!("failed to run openclaw: {error}"))?;
+        let exit_code = output.status.code().unwrap_or(-1);
+        let result = OpenclawCommandOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).trim_end().to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim_end().to_string(),
+            exit_code,
+        };
+        if exit_code != 0 {
+            let details = if !result.stderr.is_empty() {
+                result.stderr.clone()
+            } else {
+                result.stdout.clone()
+            };
+            return Err(format!("openclaw command failed ({exit_code}): {details}"));
+        }
+        Ok(result)
+    }
+}
+
+/// Strip leading non-JSON lines from CLI output (plugin logs, ANSI codes, etc.)
+fn extract_json_from_output(raw: &str) -> Option<&str> {
+    let start = raw.find('{').or_else(|| raw.find('['))?;
+    Some(&raw[start..])
+}
+
+/// Extract the last JSON array from CLI output that may contain ANSI codes and plugin logs.
+/// Scans from the end to find the last `]`, then finds its matching `[`.
+fn extract_last_json_array(raw: &str) -> Option<&str> {
+    let bytes = raw.as_bytes();
+    let end = bytes.iter().rposition(|&b| b == b']')?;
+    let mut depth = 0;
+    for i in (0..=end).rev() {
+        match bytes[i] {
+            b']' => depth += 1,
+            b'[' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&raw[i..=end]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse `openclaw channels resolve --json` output into a map of id -> name.
+fn parse_resolve_name_map(stdout: &str) -> Option<HashMap<String, String>> {
+    let json_str = extract_last_json_array(stdout)?;
+    let parsed: Vec<Value> = serde_json::from_str(json_str).ok()?;
+    let mut map = HashMap::new();
+    for item in parsed {
+        let resolved = item.get("resolved").and_then(Value::as_bool).unwrap_or(false);
+        if !resolved {
+            continue;
+        }
+        if let (Some(input), Some(name)) = (
+            item.get("input").and_then(Value::as_str),
+            item.get("name").and_then(Value::as_str),
+        ) {
+            let name = name.trim().to_string();
+            if !name.is_empty() {
+                map.insert(input.to_string(), name);
+            }
+        }
+    }
+    Some(map)
+}
+
+fn extract_version_from_text(input: &str) -> Option<String> {
+    let re = regex::Regex::new(r"\d+\.\d+(?:\.\d+){1,3}(?:[-+._a-zA-Z0-9]*)?").ok()?;
+    re.find(input).map(|mat| mat.as_str().to_string())
+}
+
+fn compare_semver(installed: &str, latest: Option<&str>) -> bool {
+    latest.and_then(|latest| semver_ordering(installed, latest)) == Some(std::cmp::Ordering::Less)
+}
+
+fn semver_ordering(left: &str, right: &str) -> Option<std::cmp::Ordering> {
+    let mut left = normalize_semver_components(left)?;
+    let mut right = normalize_semver_components(right)?;
+
+    let len = left.len().max(right.len());
+    while left.len() < len {
+        left.push(0);
+    }
+    while right.len() < len {
+        right.push(0);
+    }
+    Some(left.cmp(&right))
+}
+
+fn normalize_semver_components(raw: &str) -> Option<Vec<u32>> {
+    let mut parts = Vec::new();
+    for bit in raw.split('.') {
+        let filtered = bit.trim_start_matches(|c: char| c == 'v' || c == 'V');
+        let head = filtered.split(|c: char| !c.is_ascii_digit()).next().unwrap_or("");
+        if head.is_empty() {
+            continue;
+        }
+        parts.push(head.parse::<u32>().ok()?);
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    Some(parts)
+}
+
+fn unix_timestamp_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |delta| delta.as_secs())
+}
+
+fn format_timestamp_from_unix(timestamp: u64) -> String {
+    let Some(utc) = chrono::DateTime::<chrono::Utc>::from_timestamp(timestamp as i64, 0) else {
+        return "unknown".into();
+    };
+    utc.to_rfc3339()
+}
+
+const OPENCLAW_UPDATE_CACHE_TTL_SECS: u64 = 60 * 60 * 6;
+
+fn openclaw_update_cache_path(paths: &crate::models::OpenClawPaths) -> PathBuf {
+    paths.clawpal_dir.join("openclaw-update-cache.json")
+}
+
+fn read_openclaw_update_cache(
+    path: &Path,
+) -> Option<OpenclawUpdateCache> {
+    let text = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<OpenclawUpdateCache>(&text).ok()
+}
+
+fn save_openclaw_update_cache(
+    path: &Path,
+    cache: &OpenclawUpdateCache,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let text = serde_json::to_string_pretty(cache).map_err(|error| error.to_string())?;
+    write_text(path, &text)
+}
+
+fn read_model_catalog_cache(path: &Path) -> Option<ModelCatalogProviderCache> {
+    let text = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<ModelCatalogProviderCache>(&text).ok()
+}
+
+fn save_model_catalog_cache(
+    path: &Path,
+    cache: &ModelCatalogProviderCache,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let text = serde_json::to_string_pretty(cache).map_err(|error| error.to_string())?;
+    write_text(path, &text)
+}
+
+fn model_catalog_cache_path(paths: &crate::models::OpenClawPaths) -> PathBuf {
+    paths.clawpal_dir.join("model-catalog-cache.json")
+}
+
+fn remote_model_catalog_cache_path(paths: &crate::models::OpenClawPaths, host_id: &str) -> PathBuf {
+    let safe_host_id: String = host_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    paths
+        .clawpal_dir
+        .join("remote-model-catalog")
+        .join(format!("{safe_host_id}.json"))
+}
+
+fn normalize_model_ref(raw: &str) -> String {
+    raw.trim().to_lowercase().replace('\\', "/")
+}
+
+fn resolve_openclaw_version() -> String {
+    use std::sync::OnceLock;
+    static VERSION: OnceLock<String> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        match run_openclaw_raw(&["--version"]) {
+            Ok(output) => extract_version_from_text(&output.stdout).unwrap_or_else(|| "unknown".into()),
+            Err(_) => "unknown".into(),
+        }
+    }).clone()
+}
+
+fn check_openclaw_update_cached(paths: &crate::models::OpenClawPaths, force: bool) -> Result<OpenclawUpdateCheck, String> {
+    let cache_path = openclaw_update_cache_path(paths);
+    let now = unix_timestamp_secs();
+    if !force {
+        if let Some(cached) = read_openclaw_update_cache(&cache_path) {
+            if now.saturating_sub(cached.checked_at) < cached.ttl_seconds {
+                let installed_version = cached.installed_version.unwrap_or_else(resolve_openclaw_version);
+                let upgrade_available = compare_semver(&installed_version, cached.latest_version.as_deref());
+                return Ok(OpenclawUpdateCheck {
+                    installed_version,
+                    latest_version: cached.latest_version,
+                    upgrade_available,
+                    channel: cached.channel,
+                    details: cached.details,
+                    source: cached.source,
+                    checked_at: format_timestamp_from_unix(now),
+                });
+            }
+        }
+    }
+
+    let installed_version = resolve_openclaw_version();
+    let (latest_version, channel, details, source, upgrade_available) = detect_openclaw_update_cached(&installed_version)
+        .unwrap_or((None, None, Some("failed to detect update status".into()), "openclaw-command".into(), false));
+    let checked_at = format_timestamp_from_unix(now);
+    let cache = OpenclawUpdateCache {
+        checked_at: now,
+        latest_version: latest_version.clone(),
+        channel,
+        details: details.clone(),
+        source: source.clone(),
+        installed_version: Some(installed_version.clone()),
+        ttl_seconds: OPENCLAW_UPDATE_CACHE_TTL_SECS,
+    };
+    save_openclaw_update_cache(&cache_path, &cache)?;
+    let upgrade = compare_semver(&installed_version, latest_version.as_deref());
+    Ok(OpenclawUpdateCheck {
+        installed_version,
+        latest_version,
+        upgrade_available: upgrade || upgrade_available,
+        channel: cache.channel,
+        details,
+        source,
+        checked_at,
+    })
+}
+
+fn detect_openclaw_update_cached(installed_version: &str) -> Option<(Option<String>, Option<String>, Option<String>, String, bool)> {
+    let output = run_openclaw_raw(&["update", "status"]).ok()?;
+    if let Some((latest_version, channel, details, upgrade_available)) =
+        parse_openclaw_update_json(&output.stdout, installed_version)
+    {
+        return Some((latest_version, Some(channel), Some(details), "openclaw update status --json".into(), upgrade_available));
+    }
+    let parsed = parse_openclaw_update_text(&output.stdout);
+    if let Some((latest_version, channel, details)) = parsed {
+        let source = "openclaw update status".into();
+        let available = latest_version
+            .as_ref()
+            .is_some_and(|latest| compare_semver(installed_version, Some(latest)));
+        return Some((latest_version, Some(channel), Some(details), source, available));
+    }
+    let latest_version = query_openclaw_latest_npm().ok().flatten();
+    let details = latest_version
+        .as_ref()
+        .map(|value| format!("npm latest {value}"))
+        .unwrap_or_else(|| "update status not available".into());
+    let upgrade = latest_version
+        .as_ref()
+        .is_some_and(|latest| compare_semver(installed_version, Some(latest.as_str())));
+    Some((latest_version, None, Some(details), "npm".into(), upgrade))
+}
+
+fn parse_openclaw_update_json(raw: &str, installed_version: &str) -> Option<(Option<String>, String, String, bool)> {
+    let json_str = extract_json_from_output(raw)?;
+    let payload: Value = serde_json::from_str(json_str).ok()?;
+    let channel = payload
+        .pointer("/channel/value")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown")
+        .to_string();
+
+    let latest_from_update = payload
+        .pointer("/update/registry/latestVersion")
+        .and_then(Value::as_str)
+        .map(|value| value.to_string());
+    let latest = payload
+        .pointer("/availability/latestVersion")
+        .and_then(Value::as_str)
+        .map(|value| value.to_string())
+        .or(latest_from_update);
+    let has_update = payload
+        .pointer("/availability/available")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let details = payload
+        .pointer("/availability/latestVersion")
+        .and_then(Value::as_str)
+        .map(|value| format!("npm latest {value}"))
+        .or_else(|| {
+            if has_update {
+                Some("update available".into())
+            } else {
+                Some("up to date".into())
+            }
+        })
+        .unwrap_or_else(|| "update status unavailable".into());
+
+    let upgrade_available = if let Some(latest_version) = latest.as_deref() {
+        compare_semver(installed_version, Some(latest_version))
+    } else {
+        has_update
+    };
+
+    Some((latest, channel, details, upgrade_available))
+}
+
+fn parse_openclaw_update_text(raw: &str) -> Option<(Option<String>, String, String)> {
+    let mut channel = String::from("unknown");
+    for line in raw.lines() {
+        if line.contains("Channel") {
+            let right = line.split('│').last().or_else(|| line.split('|').last())?;
+            channel = right.trim().to_string();
+        }
+        if line.to_lowercase().contains("update") && line.contains("npm latest") {
+            if let Some(token) = extract_version_from_text(line) {
+                return Some((Some(token), channel, line.trim().to_string()));
+            }
+            return Some((None, channel, line.trim().to_string()));
+        }
+        if line.to_lowercase().contains("update") && line.contains("unknown") {
+            return Some((None, channel, line.trim().to_string()));
+        }
+    }
+    None
+}
+
+fn query_openclaw_latest_npm() -> Result<Option<String>, String> {
+    // Query npm registry directly via HTTP — no local npm CLI needed
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("HTTP client error: {e}"))?;
+    let resp = client
+        .get("https://registry.npmjs.org/openclaw/latest")
+        .header("Accept", "application/json")
+        .send()
+        .map_err(|e| format!("npm registry request failed: {e}"))?;
+    if !resp.status().is_success() {
+        return Ok(None);
+    }
+    let body: Value = resp.json().map_err(|e| format!("npm registry parse failed: {e}"))?;
+    let version = body.get("version").and_then(Value::as_str).map(String::from);
+    Ok(version)
+}
+
+/// Fetch a Discord guild name via the Discord REST API using a bot token.
+fn fetch_discord_guild_name(bot_token: &str, guild_id: &str) -> Result<String, String> {
+    let url = format!("https://discord.com/api/v10/guilds/{guild_id}");
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(3))
+        .build()
+        .map_err(|e| format!("Discord HTTP client error: {e}"))?;
+    let resp = client
+        .get(&url)
+        .header("Authorization", format!("Bot {bot_token}"))
+        .send()
+        .map_err(|e| format!("Discord API request failed: {e}"))?;
+    if !resp.status().is_success() {
+        return Err(format!("Discord API returned status {}", resp.status()));
+    }
+    let body: Value = resp.json().map_err(|e| format!("Failed to parse Discord response: {e}"))?;
+    body.get("name")
+        .and_then(Value::as_str)
+        .map(|s| s.to_string())
+        .ok_or_else(|| "No name field in Discord guild response".to_string())
+}
+
+fn collect_channel_summary(cfg: &Value) -> ChannelSummary {
+    let examples = collect_channel_model_overrides_list(cfg);
+    let configured_channels = cfg
+        .get("channels")
+        .and_then(|v| v.as_object())
+        .map(|channels| channels.len())
+        .unwrap_or(0);
+
+    ChannelSummary {
+        configured_channels,
+        channel_model_overrides: examples.len(),
+        channel_examples: examples,
+    }
+}
+
+fn read_model_value(value: &Value) -> Option<String> {
+    if let Some(value) = value.as_str() {
+        return Some(value.to_string());
+    }
+
+    if let Some(model_obj) = value.as_object() {
+        if let Some(primary) = model_obj.get("primary").and_then(Value::as_str) {
+            return Some(primary.to_string());
+        }
+        if let Some(name) = model_obj.get("name").and_then(Value::as_str) {
+            return Some(name.to_string());
+        }
+        if let Some(model) = model_obj.get("model").and_then(Value::as_str) {
+            return Some(model.to_string());
+        }
+        if let Some(model) = model_obj.get("default").and_then(Value::as_str) {
+            return Some(model.to_string());
+        }
+        if let Some(v) = model_obj.get("provider").and_then(Value::as_str) {
+            if let Some(inner) = model_obj.get("id").and_then(Value::as_str) {
+                return Some(format!("{v}/{inner}"));
+            }
+        }
+    }
+    None
+}
+
+fn collect_channel_model_overrides(cfg: &Value) -> Vec<String> {
+    collect_channel_model_overrides_list(cfg)
+}
+
+fn collect_channel_model_overrides_list(cfg: &Value) -> Vec<String> {
+    let mut out = Vec::new();
+    if let Some(channels) = cfg.get("channels").and_then(Value::as_object) {
+        for (name, entry) in channels {
+            let mut branch = Vec::new();
+            collect_channel_paths(name, entry, &mut branch);
+            out.extend(branch);
+        }
+    }
+    out
+}
+
+fn collect_channel_paths(prefix: &str, node: &Value, out: &mut Vec<String>) {
+    if let Some(obj) = node.as_object() {
+        if let Some(model) = obj.get("model").and_then(read_model_value) {
+            out.push(format!("{prefix} => {model}"));
+        }
+        for (key, child) in obj {
+            if key == "model" {
+                continue;
+            }
+            let next = format!("{prefix}.{key}");
+            collect_channel_paths(&next, child, out);
+        }
+    }
+}
+
+fn collect_memory_overview(base_dir: &Path) -> MemorySummary {
+    let memory_root = base_dir.join("memory");
+    collect_file_inventory(&memory_root, Some(80))
+}
+
+/// Resolve `path` (relative to `root`, or absolute inside it) to a path that
+/// stays under `root`, following symlinks for the parts that exist.
+fn resolve_child_path(root: &Path, path: &str) -> Result<PathBuf, String> {
+    let requested = Path::new(path.trim());
+    let relative = if requested.is_absolute() {
+        requested
+            .strip_prefix(root)
+            .map_err(|_| format!("{} is outside {}", requested.display(), root.display()))?
+    } else {
+        requested
+    };
+    let mut resolved = root.to_path_buf();
+    for component in relative.components() {
+        match component {
+            std::path::Component::Normal(part) => resolved.push(part),
+            std::path::Component::CurDir => {}
+            _ => return Err(format!("invalid path: {path}")),
+        }
+    }
+    if resolved == root {
+        return Err("path must name a file".into());
+    }
+
+    // Symlinks inside the root must not lead back out of it.
+    if let Ok(canonical_root) = root.canonicalize() {
+        let existing = resolved.ancestors().find(|p| p.exists()).unwrap_or(root);
+        let canonical = existing.canonicalize().map_err(|e| e.to_string())?;
+        if !canonical.starts_with(&canonical_root) {
+            return Err(format!("{} is outside {}", resolved.display(), root.display()));
+        }
+    }
+    Ok(resolved)
+}
+
+#[tauri::command]
+pub fn read_memory_file(path: String) -> Result<String, String> {
+    let paths = resolve_paths();
+    let target = resolve_child_path(&paths.base_dir.join("memory"), &path)?;
+    fs::read_to_string(&target).map_err(|e| format!("Failed to read {}: {e}", target.display()))
+}
+
+#[tauri::command]
+pub fn write_memory_file(path: String, content: String) -> Result<bool, String> {
+    let paths = resolve_paths();
+    let memory_root = paths.base_dir.join("memory");
+    fs::create_dir_all(&memory_root).map_err(|e| e.to_string())?;
+    let target = resolve_child_path(&memory_root, &path)?;
+    if target.is_dir() {
+        return Err(format!("{} is a directory", target.display()));
+    }
+    write_text(&target, &content)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod memory_file_tests {
+    use super::*;
+
+    #[test]
+    fn child_paths_stay_inside_root() {
+        let root = Path::new("/nonexistent/clawpal-memory");
+        assert_eq!(resolve_child_path(root, "notes/a.md").unwrap(), root.join("notes/a.md"));
+        assert_eq!(resolve_child_path(root, "/nonexistent/clawpal-memory/b.md").unwrap(), root.join("b.md"));
+        assert!(resolve_child_path(root, "../secrets").is_err());
+        assert!(resolve_child_path(root, "/etc/passwd").is_err());
+        assert!(resolve_child_path(root, ".").is_err());
+    }
+}
+
+fn collect_file_inventory(path: &Path, max_files: Option<usize>) -> MemorySummary {
+    let mut queue = VecDeque::new();
+    let mut file_count = 0usize;
+    let mut total_bytes = 0u64;
+    let mut files = Vec::new();
+
+    if !path.exists() {
+        return MemorySummary {
+            file_count: 0,
+            total_bytes: 0,
+            files,
+        };
+    }
+
+    queue.push_back(path.to_path_buf());
+    while let Some(current) = queue.pop_front() {
+        let entries = match fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            if let Ok(metadata) = entry.metadata() {
+                if metadata.is_dir() {
+                    queue.push_back(entry_path);
+                    continue;
+                }
+                if metadata.is_file() {
+                    file_count += 1;
+                    total_bytes = total_bytes.saturating_add(metadata.len());
+                    if max_files.is_none_or(|limit| files.len() < limit) {
+                        files.push(MemoryFileSummary {
+                            path: entry_path.to_string_lossy().to_string(),
+                            size_bytes: metadata.len(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    files.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+    MemorySummary {
+        file_count,
+        total_bytes,
+        files,
+    }
+}
+
+fn collect_session_overview(base_dir: &Path) -> SessionSummary {
+    let agents_dir = base_dir.join("agents");
+    let mut by_agent = Vec::new();
+    let mut total_session_files = 0usize;
+    let mut total_archive_files = 0usize;
+    let mut total_bytes = 0u64;
+
+    if !agents_dir.exists() {
+        return SessionSummary {
+            total_session_files,
+            total_archive_files,
+            total_bytes,
+            by_agent,
+        };
+    }
+
+    if let Ok(entries) = fs::read_dir(agents_dir) {
+        for entry in entries.flatten() {
+            let agent_path = entry.path();
+            if !agent_path.is_dir() {
+                continue;
+            }
+            let agent = entry.file_name().to_string_lossy().to_string();
+            let sessions_dir = agent_path.join("sessions");
+            let archive_dir = agent_path.join("sessions_archive");
+
+            let session_info = collect_file_inventory_with_limit(&sessions_dir);
+            let archive_info = collect_file_inventory_with_limit(&archive_dir);
+
+            if session_info.files > 0 || archive_info.files > 0 {
+                by_agent.push(AgentSessionSummary {
+                    agent: agent.clone(),
+                    session_files: session_info.files,
+                    archive_files: archive_info.files,
+                    total_bytes: session_info.total_bytes.saturating_add(archive_info.total_bytes),
+                });
+            }
+
+            total_session_files = total_session_files.saturating_add(session_info.files);
+            total_archive_files = total_archive_files.saturating_add(archive_info.files);
+            total_bytes = total_bytes
+                .saturating_add(session_info.total_bytes)
+                .saturating_add(archive_info.total_bytes);
+        }
+    }
+
+    by_agent.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
+    SessionSummary {
+        total_session_files,
+        total_archive_files,
+        total_bytes,
+        by_agent,
+    }
+}
+
+struct InventorySummary {
+    files: usize,
+    total_bytes: u64,
+}
+
+fn collect_file_inventory_with_limit(path: &Path) -> InventorySummary {
+    if !path.exists() {
+        return InventorySummary {
+            files: 0,
+            total_bytes: 0,
+        };
+    }
+    let mut queue = VecDeque::new();
+    let mut files = 0usize;
+    let mut total_bytes = 0u64;
+    queue.push_back(path.to_path_buf());
+    while let Some(current) = queue.pop_front() {
+        let entries = match fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            if let Ok(metadata) = entry.metadata() {
+                let p = entry.path();
+                if metadata.is_dir() {
+                    queue.push_back(p);
+                } else if metadata.is_file() {
+                    files += 1;
+                    total_bytes = total_bytes.saturating_add(metadata.len());
+                }
+            }
+        }
+    }
+    InventorySummary {
+        files,
+        total_bytes,
+    }
+}
+
+fn list_session_files_detailed(base_dir: &Path) -> Result<Vec<SessionFile>, String> {
+    let agents_root = base_dir.join("agents");
+    if !agents_root.exists() {
+        return Ok(Vec::new());
+    }
+    let mut out = Vec::new();
+    let entries = fs::read_dir(&agents_root).map_err(|e| e.to_string())?;
+    for entry in entries.flatten() {
+        let entry_path = entry.path();
+        if !entry_path.is_dir() {
+            continue;
+        }
+        let agent = entry.file_name().to_string_lossy().to_string();
+        let sessions_root = entry_path.join("sessions");
+        let archive_root = entry_path.join("sessions_archive");
+
+        collect_session_files_in_scope(&sessions_root, &agent, "sessions", base_dir, &mut out)?;
+        collect_session_files_in_scope(&archive_root, &agent, "archive", base_dir, &mut out)?;
+    }
+    out.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    Ok(out)
+}
+
+fn collect_session_files_in_scope(
+    scope_root: &Path,
+    agent: &str,
+    kind: &str,
+    base_dir: &Path,
+    out: &mut Vec<SessionFile>,
+) -> Result<(), String> {
+    if !scope_root.exists() {
+        return Ok(());
+    }
+    let mut queue = VecDeque::new();
+    queue.push_back(scope_root.to_path_buf());
+    while let Some(current) = queue.pop_front() {
+        let entries = match fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            let metadata = match entry.metadata() {
+                Ok(meta) => meta,
+                Err(_) => continue,
+            };
+            if metadata.is_dir() {
+                queue.push_back(entry_path);
+                continue;
+            }
+            if metadata.is_file() {
+                let relative_path = entry_path
+                    .strip_prefix(base_dir)
+                    .unwrap_or(&entry_path)
+                    .to_string_lossy()
+                    .to_string();
+                out.push(SessionFile {
+                    path: entry_path.to_string_lossy().to_string(),
+                    relative_path,
+                    agent: agent.to_string(),
+                    kind: kind.to_string(),
+                    size_bytes: metadata.len(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn clear_agent_and_global_sessions(agents_root: &Path, agent_id: Option<&str>) -> Result<usize, String> {
+    if !agents_root.exists() {
+        return Ok(0);
+    }
+    let mut total = 0usize;
+    let mut targets = Vec::new();
+
+    match agent_id {
+        Some(agent) => targets.push(agents_root.join(agent)),
+        None => {
+            for entry in fs::read_dir(agents_root).map_err(|e| e.to_string())? {
+                let entry = entry.map_err(|e| e.to_string())?;
+                if entry
+                    .file_type()
+                    .map_err(|e| e.to_string())?
+                    .is_dir()
+                {
+                    targets.push(entry.path());
+                }
+            }
+        }
+    }
+
+    for agent_path in targets {
+        let sessions = agent_path.join("sessions");
+        let archive = agent_path.join("sessions_archive");
+        total = total.saturating_add(clear_directory_contents(&sessions)?);
+        total = total.saturating_add(clear_directory_contents(&archive)?);
+        fs::create_dir_all(&sessions).map_err(|e| e.to_string())?;
+        fs::create_dir_all(&archive).map_err(|e| e.to_string())?;
+    }
+    Ok(total)
+}
+
+fn clear_directory_contents(target: &Path) -> Result<usize, String> {
+    if !target.exists() {
+        return Ok(0);
+    }
+    let mut total = 0usize;
+    let entries = fs::read_dir(target).map_err(|e| e.to_string())?;
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        let metadata = entry
+            .metadata()
+            .map_err(|e| e.to_string())?;
+        if metadata.is_dir() {
+            total = total.saturating_add(clear_directory_contents(&path)?);
+            fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+            continue;
+        }
+        if metadata.is_file() || metadata.is_symlink() {
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+            total = total.saturating_add(1);
+        }
+    }
+    Ok(total)
+}
+
+fn model_profiles_path(paths: &crate::models::OpenClawPaths) -> std::path::PathBuf {
+    paths.clawpal_dir.join("model-profiles.json")
+}
+
+
+
+fn profile_to_model_value(profile: &ModelProfile) -> String {
+    if profile.model.contains('/') {
+        profile.model.clone()
+    } else {
+        format!("{}/{}", profile.provider, profile.model)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedApiKey {
+    pub profile_id: String,
+    pub masked_key: String,
+}
+
+#[tauri::command]
+pub fn resolve_api_keys() -> Result<Vec<ResolvedApiKey>, String> {
+    let paths = resolve_paths();
+    let profiles = load_model_profiles(&paths);
+    let mut out = Vec::new();
+    for profile in &profiles {
+        let key = resolve_profile_api_key(profile, &paths.base_dir);
+        let masked = mask_api_key(&key);
+        out.push(ResolvedApiKey {
+            profile_id: profile.id.clone(),
+            masked_key: masked,
+        });
+    }
+    Ok(out)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedKeyCluster {
+    /// Stable fingerprint of the shared key; never the key itself.
+    pub key_hash: String,
+    pub masked_key: String,
+    pub profile_ids: Vec<String>,
+}
+
+/// Truncated SHA-256 of the key: stable across runs and not reversible.
+fn api_key_fingerprint(key: &str) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(key.as_bytes())[..8]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn cluster_profiles_by_key(keys: &[(String, String)]) -> Vec<SharedKeyCluster> {
+    let mut grouped: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+    for (profile_id, key) in keys {
+        if key.is_empty() {
+            continue;
+        }
+        grouped
+            .entry(api_key_fingerprint(key))
+            .or_insert_with(|| (mask_api_key(key), Vec::new()))
+            .1
+            .push(profile_id.clone());
+    }
+    grouped
+        .into_iter()
+        .filter(|(_, (_, ids))| ids.len() > 1)
+        .map(|(key_hash, (masked_key, profile_ids))| SharedKeyCluster {
+            key_hash,
+            masked_key,
+            profile_ids,
+        })
+        .collect()
+}
+
+/// Group profiles whose resolved API keys are identical.
+#[tauri::command]
+pub fn profiles_sharing_keys() -> Result<Vec<SharedKeyCluster>, String> {
+    let paths = resolve_paths();
+    let profiles = load_model_profiles(&paths);
+    let keys: Vec<(String, String)> = profiles
+        .iter()
+        .map(|profile| (profile.id.clone(), resolve_profile_api_key(profile, &paths.base_dir)))
+        .collect();
+    Ok(cluster_profiles_by_key(&keys))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileInconsistency {
+    pub profile_id: String,
+    pub name: String,
+    pub provider: String,
+    pub model: String,
+    pub model_prefix: String,
+}
+
+/// Providers that route to other vendors' models, whose ids therefore carry
+/// the upstream vendor's prefix (`openrouter` + `moonshotai/kimi-k2.5`).
+const AGGREGATOR_PROVIDERS: &[&str] = &[
+    "openrouter",
+    "litellm",
+    "together",
+    "fireworks",
+    "deepinfra",
+    "huggingface",
+    "nvidia",
+    "vercel-ai-gateway",
+];
+
+/// Profiles whose model carries a `provider/` prefix that disagrees with the
+/// declared provider. Unprefixed models and aggregator providers are not
+/// checked.
+fn find_profile_inconsistencies(profiles: &[ModelProfile]) -> Vec<ProfileInconsistency> {
+    profiles
+        .iter()
+        .filter_map(|profile| {
+            if !profile.model.contains('/') {
+                return None;
+            }
+            let provider = normalize_model_ref(&profile.provider);
+            if AGGREGATOR_PROVIDERS.contains(&provider.as_str()) {
+                return None;
+            }
+            let model_ref = normalize_model_ref(&profile_to_model_value(profile));
+            if model_ref.starts_with(&format!("{provider}/")) {
+                return None;
+            }
+            let prefix = model_ref.split('/').next().unwrap_or("").to_string();
+            Some(ProfileInconsistency {
+                profile_id: profile.id.clone(),
+                name: profile.name.clone(),
+                provider: profile.provider.clone(),
+                model: profile.model.clone(),
+                model_prefix: prefix,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn validate_profile_consistency() -> Result<Vec<ProfileInconsistency>, String> {
+    let paths = resolve_paths();
+    Ok(find_profile_inconsistencies(&load_model_profiles(&paths)))
+}
+
+#[cfg(test)]
+mod profile_inconsistency_tests {
+    use super::*;
+
+    #[test]
+    fn inconsistencies_skip_aggregators_and_own_prefixes() {
+        let profiles = parse_profile_bundle(
+            r#"[{"id": "a", "name": "A", "provider": "openrouter", "model": "moonshotai/kimi-k2.5", "enabled": true},
+                {"id": "b", "name": "B", "provider": "openai", "model": "openai/gpt-4o", "enabled": true},
+                {"id": "c", "name": "C", "provider": "anthropic", "model": "openai/gpt-4o", "enabled": true},
+                {"id": "d", "name": "D", "provider": "openai", "model": "gpt-4o", "enabled": true}]"#,
+        )
+        .unwrap();
+        let found = find_profile_inconsistencies(&profiles);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].profile_id, "c");
+        assert_eq!(found[0].model_prefix, "openai");
+    }
+}
+
+fn resolve_profile_api_key(profile: &ModelProfile, base_dir: &Path) -> String {
+    // 1. Direct api_key field (user entered key directly in ClawPal)
+    if let Some(ref key) = profile.api_key {
+        let trimmed = key.trim();
+        if !trimmed.is_empty() {
+            return trimmed.to_string();
+        }
+    }
+
+    // 2. Try auth_ref as env var name directly (e.g. "OPENAI_API_KEY")
+    let auth_ref = profile.auth_ref.trim();
+    if !auth_ref.is_empty() {
+        if let Ok(val) = std::env::var(auth_ref) {
+            if !val.trim().is_empty() {
+                return val;
+            }
+        }
+    }
+
+    // 3. Look up auth_ref in agent-level auth-profiles.json files
+    //    Keys are stored at: {base_dir}/agents/{agent}/agent/auth-profiles.json
+    if !auth_ref.is_empty() {
+        if let Some(key) = resolve_key_from_agent_auth_profiles(base_dir, auth_ref) {
+            return key;
+        }
+    }
+
+    // 4. Try common env var naming conventions based on provider
+    let provider = profile.provider.trim().to_uppercase().replace('-', "_");
+    if !provider.is_empty() {
+        for suffix in ["_API_KEY", "_KEY", "_TOKEN"] {
+            let env_name = format!("{provider}{suffix}");
+            if let Ok(val) = std::env::var(&env_name) {
+                if !val.trim().is_empty() {
+                    return val;
+                }
+            }
+        }
+    }
+
+    String::new()
+}
+
+/// Reads agent-level auth-profiles.json to find the actual API key/token.
+/// Scans all agents and returns the first match.
+fn resolve_key_from_agent_auth_profiles(base_dir: &Path, auth_ref: &str) -> Option<String> {
+    let agents_dir = base_dir.join("agents");
+    if !agents_dir.exists() {
+        return None;
+    }
+    let entries = fs::read_dir(&agents_dir).ok()?;
+    for entry in entries.flatten() {
+        let auth_file = entry.path().join("agent").join("auth-profiles.json");
+        if !auth_file.exists() {
+            continue;
+        }
+        let text = fs::read_to_string(&auth_file).ok()?;
+        let data: Value = serde_json::from_str(&text).ok()?;
+        if let Some(profiles) = data.get("profiles").and_then(Value::as_object) {
+            if let Some(auth_entry) = profiles.get(auth_ref) {
+                if let Some(key) = extract_token_from_auth_entry(auth_entry) {
+                    return Some(key);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Extract the actual key/token from an agent auth-profiles entry.
+/// Handles different auth types: token, api_key, oauth.
+fn extract_token_from_auth_entry(entry: &Value) -> Option<String> {
+    // "token" type → "token" field (e.g. anthropic)
+    // "api_key" type → "key" field (e.g. kimi-coding)
+    // "oauth" type → "access" field (e.g. minimax-portal, openai-codex)
+    for field in ["token", "key", "apiKey", "api_key", "access"] {
+        if let Some(val) = entry.get(field).and_then(Value::as_str) {
+            let trimmed = val.trim();
+            if !trimmed.is_empty() {
+                return Some(trimmed.to_string());
+            }
+        }
+    }
+    None
+}
+
+fn mask_api_key(key: &str) -> String {
+    let key = key.trim();
+    if key.is_empty() {
+        return "not set".to_string();
+    }
+    if key.chars().count() <= 8 {
+        return "***".to_string();
+    }
+    let prefix: String = key.chars().take(4).collect();
+    let suffix: String = key.chars().rev().take(4).collect::<Vec<_>>().into_iter().rev().collect();
+    format!("{prefix}...{suffix}")
+}
+
+#[cfg(test)]
+mod mask_api_key_tests {
+    use super::*;
+
+    #[test]
+    fn masks_by_characters_not_bytes() {
+        assert_eq!(mask_api_key("sk-abcdefghijkl"), "sk-a...ijkl");
+        assert_eq!(mask_api_key("“sk-abcdefgh”"), "“sk-...fgh”");
+        assert_eq!(mask_api_key("ключ-ключ"), "ключ...ключ");
+        assert_eq!(mask_api_key("äöüß"), "***");
+    }
+}
+
+fn load_model_profiles(paths: &crate::models::OpenClawPaths) -> Vec<ModelProfile> {
+    let path = model_profiles_path(paths);
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|_| r#"{"profiles":[]}"#.to_string());
+    #[derive(serde::Deserialize)]
+    struct Storage {
+        #[serde(default)]
+        profiles: Vec<ModelProfile>,
+    }
+    let parsed = serde_json::from_str::<Storage>(&text).unwrap_or(Storage {
+        profiles: Vec::new(),
+    });
+    parsed.profiles
+}
+
+fn save_model_profiles(paths: &crate::models::OpenClawPaths, profiles: &[ModelProfile]) -> Result<(), String> {
+    let path = model_profiles_path(paths);
+    #[derive(serde::Serialize)]
+    struct Storage<'a> {
+        profiles: &'a [ModelProfile],
+        #[serde(rename = "version")]
+        version: u8,
+    }
+    let payload = Storage {
+        profiles,
+        version: 1,
+    };
+    let text = serde_json::to_string_pretty(&payload).map_err(|e| e.to_string())?;
+    crate::config_io::write_text(&path, &text)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+fn write_config_with_snapshot(
+    paths: &crate::models::OpenClawPaths,
+    current_text: &str,
+    next: &Value,
+    source: &str,
+) -> Result<(), String> {
+    let _ = add_snapshot(
+        &paths.history_dir,
+        &paths.metadata_path,
+        Some(source.to_string()),
+        source,
+        true,
+        current_text,
+        None,
+    )?;
+    write_json(&paths.config_path, next)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigEdit {
+    /// Dotted path, as accepted by `set_nested_value`.
+    pub path: String,
+    /// New value; `None` removes the key.
+    pub value: Option<Value>,
+}
+
+/// Apply every edit to a copy of `cfg`; the first invalid path aborts the
+/// whole batch and leaves `cfg` untouched.
+fn apply_config_edits_to(cfg: &Value, edits: Vec<ConfigEdit>) -> Result<Value, String> {
+    for edit in &edits {
+        let path = edit.path.trim().trim_matches('.');
+        if path.is_empty() || path.split('.').any(|part| part.trim().is_empty()) {
+            return Err(format!("invalid path: '{}'", edit.path));
+        }
+    }
+    let mut next = cfg.clone();
+    for edit in edits {
+        set_nested_value(&mut next, &edit.path, edit.value)
+            .map_err(|e| format!("{}: {e}", edit.path))?;
+    }
+    Ok(next)
+}
+
+/// Apply a batch of path/value edits under a single snapshot and write.
+#[tauri::command]
+pub fn apply_config_edits(edits: Vec<ConfigEdit>) -> Result<bool, String> {
+    if edits.is_empty() {
+        return Ok(false);
+    }
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    let next = apply_config_edits_to(&cfg, edits)?;
+    if next == cfg {
+        return Ok(false);
+    }
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+    write_config_with_snapshot(&paths, &current, &next, "config-edits")?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod config_edit_tests {
+    use super::*;
+
+    #[test]
+    fn batch_applies_all_or_nothing() {
+        let cfg = serde_json::json!({ "agents": { "defaults": { "model": "a" } }, "gateway": { "port": 1 } });
+        let next = apply_config_edits_to(&cfg, vec![
+            ConfigEdit { path: "agents.defaults.model".into(), value: Some("b".into()) },
+            ConfigEdit { path: "gateway.port".into(), value: None },
+        ])
+        .unwrap();
+        assert_eq!(next["agents"]["defaults"]["model"], "b");
+        assert!(next["gateway"].get("port").is_none());
+
+        let bad = apply_config_edits_to(&cfg, vec![
+            ConfigEdit { path: "gateway.port".into(), value: Some(2.into()) },
+            ConfigEdit { path: "gateway..bind".into(), value: Some("x".into()) },
+        ]);
+        assert!(bad.is_err());
+        assert_eq!(cfg["gateway"]["port"], 1);
+    }
+}
+
+fn set_nested_value(root: &mut Value, path: &str, value: Option<Value>) -> Result<(), String> {
+    let path = path.trim().trim_matches('.');
+    if path.is_empty() {
+        return Err("invalid path".into());
+    }
+    let mut cur = root;
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        let is_last = parts.peek().is_none();
+        let obj = cur
+            .as_object_mut()
+            .ok_or_else(|| "path must point to object".to_string())?;
+        if is_last {
+            if let Some(v) = value {
+                obj.insert(part.to_string(), v);
+            } else {
+                obj.remove(part);
+            }
+            return Ok(());
+        }
+        let child = obj
+            .entry(part.to_string())
+            .or_insert_with(|| Value::Object(Default::default()));
+        if !child.is_object() {
+            *child = Value::Object(Default::default());
+        }
+        cur = child;
+    }
+    unreachable!("path should have at least one segment");
+}
+
+fn set_agent_model_value(
+    root: &mut Value,
+    agent_id: &str,
+    model: Option<String>,
+) -> Result<(), String> {
+    if let Some(agents) = root.pointer_mut("/agents").and_then(Value::as_object_mut) {
+        if let Some(list) = agents.get_mut("list").and_then(Value::as_array_mut) {
+            for agent in list {
+                if agent.get("id").and_then(Value::as_str) == Some(agent_id) {
+                    if let Some(agent_obj) = agent.as_object_mut() {
+                        match model {
+                            Some(v) => {
+                                // If existing model is an object, update "primary" inside it
+                                if let Some(existing) = agent_obj.get_mut("model") {
+                                    if let Some(model_obj) = existing.as_object_mut() {
+                                        model_obj.insert("primary".into(), Value::String(v));
+                                        return Ok(());
+                                    }
+                                }
+                                agent_obj.insert("model".into(), Value::String(v));
+                            }
+                            None => {
+                                agent_obj.remove("model");
+                            }
+                        }
+                    }
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Err(format!("agent not found: {agent_id}"))
+}
+
+fn load_model_catalog(
+    paths: &crate::models::OpenClawPaths,
+) -> Result<Vec<ModelCatalogProvider>, String> {
+    let cache_path = model_catalog_cache_path(paths);
+    let current_version = resolve_openclaw_version();
+    let cached = read_model_catalog_cache(&cache_path);
+    if let Some(selected) = select_catalog_from_cache(cached.as_ref(), &current_version) {
+        return Ok(selected);
+    }
+
+    if let Some(catalog) = extract_model_catalog_from_cli(paths) {
+        if !catalog.is_empty() {
+            return Ok(catalog);
+        }
+    }
+
+    if let Some(previous) = cached {
+        if !previous.providers.is_empty() && previous.error.is_none() {
+            return Ok(previous.providers);
+        }
+    }
+
+    Err("Failed to load model catalog from openclaw CLI".into())
+}
+
+fn select_catalog_from_cache(
+    cached: Option<&ModelCatalogProviderCache>,
+    current_version: &str,
+) -> Option<Vec<ModelCatalogProvider>> {
+    let cache = cached?;
+    if cache.cli_version != current_version {
+        return None;
+    }
+    if cache.error.is_some() || cache.providers.is_empty() {
+        return None;
+    }
+    Some(cache.providers.clone())
+}
+
+/// First numeric value at any of the dotted `paths`; numeric strings count
+/// too, since some providers report prices as strings.
+fn catalog_number(model: &Value, paths: &[&str]) -> Option<f64> {
+    paths.iter().find_map(|path| {
+        let value = path.split('.').try_fold(model, |node, key| node.get(key))?;
+        value
+            .as_f64()
+            .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+    })
+}
+
+/// Price per million tokens. `per_token` paths hold OpenRouter-style
+/// per-token prices (`pricing.prompt`, `pricing.completion`) and are scaled up.
+fn catalog_price(model: &Value, per_million: &[&str], per_token: &[&str]) -> Option<f64> {
+    catalog_number(model, per_million).or_else(|| {
+        catalog_number(model, per_token).map(|price| (price * 1e12).round() / 1e6)
+    })
+}
+
+/// Parse CLI output from `openclaw models list --all --json` into grouped providers.
+/// Handles various output formats: flat arrays, {models: [...]}, {items: [...]}, {data: [...]}.
+/// Strips prefix junk (plugin log lines) before the JSON.
+fn parse_model_catalog_from_cli_output(raw: &str) -> Option<Vec<ModelCatalogProvider>> {
+    let json_str = extract_json_from_output(raw)?;
+    let response: Value = serde_json::from_str(json_str).ok()?;
+    let models: Vec<Value> = response
+        .as_array()
+        .map(|values| values.to_vec())
+        .or_else(|| {
+            response
+                .get("models")
+                .and_then(Value::as_array)
+                .map(|values| values.to_vec())
+        })
+        .or_else(|| {
+            response
+                .get("items")
+                .and_then(Value::as_array)
+                .map(|values| values.to_vec())
+        })
+        .or_else(|| {
+            response
+                .get("data")
+                .and_then(Value::as_array)
+                .map(|values| values.to_vec())
+        })
+        .unwrap_or_default();
+    if models.is_empty() {
+        return None;
+    }
+    let mut providers: BTreeMap<String, ModelCatalogProvider> = BTreeMap::new();
+    for model in &models {
+        let key = model
+            .get("key")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| {
+                let provider = model.get("provider").and_then(Value::as_str)?;
+                let model_id = model.get("id").and_then(Value::as_str)?;
+                Some(format!("{provider}/{model_id}"))
+            });
+        let key = match key {
+            Some(k) => k,
+            None => continue,
+        };
+        let mut parts = key.splitn(2, '/');
+        let provider = match parts.next() {
+            Some(p) if !p.trim().is_empty() => p.trim().to_lowercase(),
+            _ => continue,
+        };
+        let id = parts.next().unwrap_or("").trim().to_string();
+        if id.is_empty() {
+            continue;
+        }
+        let name = model
+            .get("name")
+            .and_then(Value::as_str)
+            .or_else(|| model.get("model").and_then(Value::as_str))
+            .or_else(|| model.get("title").and_then(Value::as_str))
+            .map(str::to_string);
+        let base_url = model
+            .get("baseUrl")
+            .or_else(|| model.get("base_url"))
+            .or_else(|| model.get("apiBase"))
+            .or_else(|| model.get("api_base"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| {
+                response
+                    .get("providers")
+                    .and_then(Value::as_object)
+                    .and_then(|providers| providers.get(&provider))
+                    .and_then(Value::as_object)
+                    .and_then(|provider_cfg| {
+                        provider_cfg
+                            .get("baseUrl")
+                            .or_else(|| provider_cfg.get("base_url"))
+                            .or_else(|| provider_cfg.get("apiBase"))
+                            .or_else(|| provider_cfg.get("api_base"))
+                            .and_then(Value::as_str)
+                    })
+                    .map(str::to_string)
+            });
+        let entry = providers.entry(provider.clone()).or_insert(ModelCatalogProvider {
+            provider: provider.clone(),
+            base_url,
+            models: Vec::new(),
+        });
+        if !entry.models.iter().any(|existing| existing.id == id) {
+            let context_window = catalog_number(
+                model,
+                &["contextWindow", "context_window", "contextLength", "context_length", "maxContextTokens"],
+            )
+            .filter(|n| *n > 0.0)
+            .map(|n| n as u64);
+            entry.models.push(ModelCatalogModel {
+                id: id.clone(),
+                name: name.clone(),
+                context_window,
+                input_price: catalog_price(
+                    model,
+                    &["inputPrice", "input_price", "pricing.input", "cost.input"],
+                    &["pricing.prompt"],
+                ),
+                output_price: catalog_price(
+                    model,
+                    &["outputPrice", "output_price", "pricing.output", "cost.output"],
+                    &["pricing.completion"],
+                ),
+            });
+        }
+    }
+
+    if providers.is_empty() {
+        return None;
+    }
+
+    let mut out: Vec<ModelCatalogProvider> = providers.into_values().collect();
+    for provider in &mut out {
+        provider.models.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+    out.sort_by(|a, b| a.provider.cmp(&b.provider));
+    Some(out)
+}
+
+fn extract_model_catalog_from_cli(
+    paths: &crate::models::OpenClawPaths,
+) -> Option<Vec<ModelCatalogProvider>> {
+    let output = run_openclaw_raw(&["models", "list", "--all", "--json", "--no-color"]).ok()?;
+    if output.stdout.trim().is_empty() {
+        return None;
+    }
+
+    let out = parse_model_catalog_from_cli_output(&output.stdout)?;
+    let _ = cache_model_catalog(paths, out.clone());
+    Some(out)
+}
+
+fn cache_model_catalog(paths: &crate::models::OpenClawPaths, providers: Vec<ModelCatalogProvider>) -> Option<()> {
+    let cache_path = model_catalog_cache_path(paths);
+    let now = unix_timestamp_secs();
+    let cache = ModelCatalogProviderCache {
+        cli_version: resolve_openclaw_version(),
+        updated_at: now,
+        providers,
+        source: "openclaw models list --all --json".into(),
+        error: None,
+    };
+    let _ = save_model_catalog_cache(&cache_path, &cache);
+    Some(())
+}
+
+#[cfg(test)]
+mod model_catalog_cache_tests {
+    use super::*;
+
+    #[test]
+    fn test_select_cached_catalog_same_version() {
+        let cached = ModelCatalogProviderCache {
+            cli_version: "1.2.3".into(),
+            updated_at: 123,
+            providers: vec![ModelCatalogProvider {
+                provider: "openrouter".into(),
+                base_url: None,
+                models: vec![ModelCatalogModel {
+                    id: "moonshotai/kimi-k2.5".into(),
+                    name: Some("Kimi".into()),
+                    context_window: None,
+                    input_price: None,
+                    output_price: None,
+                }],
+            }],
+            source: "openclaw models list --all --json".into(),
+            error: None,
+        };
+        let selected = select_catalog_from_cache(Some(&cached), "1.2.3");
+        assert!(selected.is_some(), "same version should use cache");
+    }
+
+    #[test]
+    fn test_select_cached_catalog_version_mismatch_requires_refresh() {
+        let cached = ModelCatalogProviderCache {
+            cli_version: "1.2.2".into(),
+            updated_at: 123,
+            providers: vec![ModelCatalogProvider {
+                provider: "openrouter".into(),
+                base_url: None,
+                models: vec![ModelCatalogModel {
+                    id: "moonshotai/kimi-k2.5".into(),
+                    name: Some("Kimi".into()),
+                    context_window: None,
+                    input_price: None,
+                    output_price: None,
+                }],
+            }],
+            source: "openclaw models list --all --json".into(),
+            error: None,
+        };
+        let selected = select_catalog_from_cache(Some(&cached), "1.2.3");
+        assert!(selected.is_none(), "version mismatch must force CLI refresh");
+    }
+
+    #[test]
+    fn test_parse_catalog_reads_optional_pricing() {
+        let raw = r#"{"models": [
+            {"key": "openai/gpt-4o", "contextWindow": 128000, "pricing": {"input": "2.5", "output": 10}},
+            {"key": "openai/o3-mini"},
+            {"key": "openrouter/qwen", "pricing": {"prompt": "0.0000025", "completion": 0.00001}}
+        ]}"#;
+        let catalog = parse_model_catalog_from_cli_output(raw).unwrap();
+        let models = &catalog[0].models;
+        assert_eq!(models[0].context_window, Some(128000));
+        assert_eq!(models[0].input_price, Some(2.5));
+        assert_eq!(models[0].output_price, Some(10.0));
+        assert_eq!(models[1].context_window, None);
+        assert_eq!(models[1].input_price, None);
+        let openrouter = &catalog[1].models[0];
+        assert_eq!(openrouter.input_price, Some(2.5));
+        assert_eq!(openrouter.output_price, Some(10.0));
+    }
+}
+
+pub(crate) fn collect_channel_nodes(cfg: &Value) -> Vec<ChannelNode> {
+    let mut out = Vec::new();
+    if let Some(channels) = cfg.get("channels") {
+        walk_channel_nodes("channels", channels, &mut out);
+    }
+    out.sort_by(|a, b| a.path.cmp(&b.path));
+    out
+}
+
+fn walk_channel_nodes(prefix: &str, node: &Value, out: &mut Vec<ChannelNode>) {
+    let Some(obj) = node.as_object() else {
+        return;
+    };
+
+    if is_channel_like_node(prefix, obj) {
+        let channel_type = resolve_channel_type(prefix, obj);
+        let mode = resolve_channel_mode(obj);
+        let allowlist = collect_channel_allowlist(obj);
+        let has_model_field = obj.contains_key("model");
+        let model = obj.get("model").and_then(read_model_value);
+        out.push(ChannelNode {
+            path: prefix.to_string(),
+            channel_type,
+            mode,
+            allowlist,
+            model,
+            has_model_field,
+            display_name: None,
+            name_status: None,
+        });
+    }
+
+    for (key, child) in obj {
+        if key == "allowlist" || key == "model" || key == "mode" {
+            continue;
+        }
+        if let Value::Object(_) = child {
+            walk_channel_nodes(&format!("{prefix}.{key}"), child, out);
+        }
+    }
+}
+
+type ChannelResolveMap = HashMap<String, (bool, Option<String>, Option<String>)>;
+
+/// Run `openclaw channels resolve` for one platform. `Ok(None)` means the CLI
+/// produced no output; `Err` means the command itself failed.
+fn resolve_channel_names_for_plugin(
+    plugin: &str,
+    kind: &str,
+    ids: &[String],
+) -> Result<Option<ChannelResolveMap>, String> {
+    let mut args = vec!["channels", "resolve", "--json", "--channel", plugin, "--kind", kind];
+    args.extend(ids.iter().map(String::as_str));
+    let output = run_openclaw_raw(&args)?;
+    if output.stdout.trim().is_empty() {
+        return Ok(None);
+    }
+    let json_str = extract_json_from_output(&output.stdout).unwrap_or("[]");
+    let parsed: Vec<Value> = serde_json::from_str(json_str).unwrap_or_default();
+    let mut name_map = HashMap::new();
+    for item in parsed {
+        let input = item.get("input").and_then(Value::as_str).unwrap_or_default().to_string();
+        let resolved = item.get("resolved").and_then(Value::as_bool).unwrap_or(false);
+        let name = item
+            .get("name")
+            .and_then(Value::as_str)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        let note = item.get("note").and_then(Value::as_str).map(|value| value.to_string());
+        if !input.is_empty() {
+            name_map.insert(input, (resolved, name, note));
+        }
+    }
+    Ok(Some(name_map))
+}
+
+/// Resolve display names for every platform concurrently; total latency is
+/// bounded by the slowest platform instead of the sum of all of them. With
+/// `platforms`, only those plugins are resolved and the rest keep their
+/// cached names.
+async fn enrich_channel_display_names(
+    paths: &crate::models::OpenClawPaths,
+    cfg: &Value,
+    nodes: &mut [ChannelNode],
+    platforms: Option<&HashSet<String>>,
+) -> Result<(), String> {
+    let mut grouped: BTreeMap<String, Vec<(usize, String, String)>> = BTreeMap::new();
+    let mut local_names: Vec<(usize, String)> = Vec::new();
+    let cache_file = paths.clawpal_dir.join("channel-name-cache.json");
+    let cached: HashMap<String, ChannelNameCacheEntry> = if platforms.is_some() {
+        read_json_cache_file::<ChannelNameCacheEntry>(&cache_file)
+            .into_iter()
+            .map(|entry| (entry.path.clone(), entry))
+            .collect()
     } else {
-        format!("{}/{}", profile.provider, profile.model)
+        HashMap::new()
+    };
+
+    for (index, node) in nodes.iter_mut().enumerate() {
+        if let Some((plugin, identifier, kind)) = resolve_channel_node_identity(cfg, node) {
+            if platforms.is_some_and(|wanted| !wanted.contains(&plugin)) {
+                if let Some(entry) = cached.get(&node.path) {
+                    node.display_name = entry.display_name.clone();
+                    node.name_status = entry.name_status.clone();
+                }
+            } else {
+                grouped
+                    .entry(plugin)
+                    .or_default()
+                    .push((index, identifier, kind));
+            }
+        }
+        if node.display_name.is_none() {
+            if let Some(local_name) = channel_node_local_name(cfg, &node.path) {
+                local_names.push((index, local_name));
+            }
+        }
+    }
+    for (index, local_name) in local_names {
+        if let Some(node) = nodes.get_mut(index) {
+            node.display_name = Some(local_name);
+            node.name_status = Some("local".into());
+        }
+    }
+
+    if nodes.is_empty() {
+        if cache_file.exists() {
+            let _ = fs::remove_file(&cache_file);
+        }
+        return Ok(());
+    }
+
+    let tasks = grouped
+        .into_iter()
+        .filter(|(_, entries)| !entries.is_empty())
+        .map(|(plugin, entries)| {
+            tauri::async_runtime::spawn_blocking(move || {
+                let ids: Vec<String> = entries.iter().map(|(_, identifier, _)| identifier.clone()).collect();
+                let kind = entries[0].2.clone();
+                let result = resolve_channel_names_for_plugin(&plugin, &kind, &ids);
+                (entries, result)
+            })
+        });
+    let results = futures_util::future::join_all(tasks).await;
+
+    for joined in results {
+        let (entries, result) = joined.map_err(|e| e.to_string())?;
+        let name_map = match result {
+            Ok(Some(name_map)) => name_map,
+            Ok(None) => {
+                for (index, _, _) in entries {
+                    nodes[index].name_status = Some("unresolved".into());
+                }
+                continue;
+            }
+            Err(_) => {
+                for (index, _, _) in entries {
+                    nodes[index].name_status = Some("resolve failed".into());
+                }
+                continue;
+            }
+        };
+
+        for (index, identifier, _) in entries {
+            if let Some((resolved, name, note)) = name_map.get(&identifier) {
+                if *resolved {
+                    if let Some(name) = name {
+                        nodes[index].display_name = Some(name.clone());
+                        nodes[index].name_status = Some("resolved".into());
+                    } else {
+                        nodes[index].name_status = Some("resolved".into());
+                    }
+                } else if let Some(note) = note {
+                    nodes[index].name_status = Some(note.clone());
+                } else {
+                    nodes[index].name_status = Some("unresolved".into());
+                }
+            } else {
+                nodes[index].name_status = Some("unresolved".into());
+            }
+        }
     }
+
+    let _ = save_json_cache(&cache_file, nodes);
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+struct ChannelNameCacheEntry {
+    path: String,
+    display_name: Option<String>,
+    name_status: Option<String>,
+}
+
+fn save_json_cache(cache_file: &Path, nodes: &[ChannelNode]) -> Result<(), String> {
+    let payload: Vec<ChannelNameCacheEntry> = nodes
+        .iter()
+        .map(|node| ChannelNameCacheEntry {
+            path: node.path.clone(),
+            display_name: node.display_name.clone(),
+            name_status: node.name_status.clone(),
+        })
+        .collect();
+    write_text(cache_file, &serde_json::to_string_pretty(&payload).map_err(|e| e.to_string())?)
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NameCachesExport {
+    version: u8,
+    channel_names: Vec<ChannelNameCacheEntry>,
+    discord_guild_channels: Vec<DiscordGuildChannel>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ResolvedApiKey {
-    pub profile_id: String,
-    pub masked_key: String,
+pub struct NameCachesImportResult {
+    pub channel_names: usize,
+    pub discord_guild_channels: usize,
+}
+
+fn read_json_cache_file<T: serde::de::DeserializeOwned>(path: &Path) -> Vec<T> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
 }
 
+/// Bundle the resolved channel-name and Discord guild caches into one JSON
+/// document that `import_name_caches` can seed another install with.
 #[tauri::command]
-pub fn resolve_api_keys() -> Result<Vec<ResolvedApiKey>, String> {
+pub fn export_name_caches() -> Result<String, String> {
     let paths = resolve_paths();
-    let profiles = load_model_profiles(&paths);
-    let mut out = Vec::new();
-    for profile in &profiles {
-        let key = resolve_profile_api_key(profile, &paths.base_dir);
-        let masked = mask_api_key(&key);
-        out.push(ResolvedApiKey {
-            profile_id: profile.id.clone(),
-            masked_key: masked,
+    let payload = NameCachesExport {
+        version: 1,
+        channel_names: read_json_cache_file(&paths.clawpal_dir.join("channel-name-cache.json")),
+        discord_guild_channels: read_json_cache_file(&paths.clawpal_dir.join("discord-guild-channels.json")),
+    };
+    serde_json::to_string_pretty(&payload).map_err(|e| e.to_string())
+}
+
+/// Merge an exported name-cache document into the local caches. Imported
+/// entries replace existing ones with the same path / guild+channel id.
+#[tauri::command]
+pub fn import_name_caches(json: String) -> Result<NameCachesImportResult, String> {
+    let imported: NameCachesExport =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid name cache export: {e}"))?;
+    if imported.version != 1 {
+        return Err(format!("Unsupported name cache export version: {}", imported.version));
+    }
+    if imported.channel_names.iter().any(|e| !e.path.starts_with("channels.")) {
+        return Err("Invalid name cache export: channel paths must start with \"channels.\"".into());
+    }
+    if imported
+        .discord_guild_channels
+        .iter()
+        .any(|e| e.guild_id.trim().is_empty() || e.channel_id.trim().is_empty())
+    {
+        return Err("Invalid name cache export: guild and channel ids are required".into());
+    }
+
+    let paths = resolve_paths();
+    ensure_dirs(&paths)?;
+
+    let names_file = paths.clawpal_dir.join("channel-name-cache.json");
+    let mut names: Vec<ChannelNameCacheEntry> = read_json_cache_file(&names_file);
+    let channel_names = imported.channel_names.len();
+    for entry in imported.channel_names {
+        names.retain(|existing| existing.path != entry.path);
+        names.push(entry);
+    }
+    names.sort_by(|a, b| a.path.cmp(&b.path));
+    write_text(&names_file, &serde_json::to_string_pretty(&names).map_err(|e| e.to_string())?)?;
+
+    let guilds_file = paths.clawpal_dir.join("discord-guild-channels.json");
+    let mut guilds: Vec<DiscordGuildChannel> = read_json_cache_file(&guilds_file);
+    let discord_guild_channels = imported.discord_guild_channels.len();
+    for entry in imported.discord_guild_channels {
+        guilds.retain(|existing| !(existing.guild_id == entry.guild_id && existing.channel_id == entry.channel_id));
+        guilds.push(entry);
+    }
+    write_text(&guilds_file, &serde_json::to_string_pretty(&guilds).map_err(|e| e.to_string())?)?;
+
+    Ok(NameCachesImportResult {
+        channel_names,
+        discord_guild_channels,
+    })
+}
+
+fn resolve_channel_node_identity(cfg: &Value, node: &ChannelNode) -> Option<(String, String, String)> {
+    let parts: Vec<&str> = node.path.split('.').collect();
+    if parts.len() < 2 || parts[0] != "channels" {
+        return None;
+    }
+    let plugin = parts[1].to_string();
+    let identifier = channel_last_segment(node.path.as_str())?;
+    let config_node = channel_lookup_node(cfg, &node.path);
+    let kind = if node.channel_type.as_deref() == Some("dm") || node.path.ends_with(".dm") {
+        "user".to_string()
+    } else if config_node
+        .and_then(|value| value.get("users").or(value.get("members")).or_else(|| value.get("peerIds")))
+        .is_some()
+    {
+        "user".to_string()
+    } else {
+        "group".to_string()
+    };
+    Some((plugin, identifier, kind))
+}
+
+fn channel_last_segment(path: &str) -> Option<String> {
+    path.split('.').next_back().map(|value| value.to_string())
+}
+
+fn channel_node_local_name(cfg: &Value, path: &str) -> Option<String> {
+    channel_lookup_node(cfg, path).and_then(|node| {
+        if let Some(slug) = node.get("slug").and_then(Value::as_str) {
+            let trimmed = slug.trim();
+            if !trimmed.is_empty() {
+                return Some(trimmed.to_string());
+            }
+        }
+        if let Some(name) = node.get("name").and_then(Value::as_str) {
+            let trimmed = name.trim();
+            if !trimmed.is_empty() {
+                return Some(trimmed.to_string());
+            }
+        }
+        None
+    })
+}
+
+fn channel_lookup_node<'a>(cfg: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = cfg;
+    for part in path.split('.') {
+        current = current.get(part)?;
+    }
+    Some(current)
+}
+
+fn is_channel_like_node(prefix: &str, obj: &serde_json::Map<String, Value>) -> bool {
+    if prefix == "channels" {
+        return false;
+    }
+    if obj.contains_key("model")
+        || obj.contains_key("type")
+        || obj.contains_key("mode")
+        || obj.contains_key("policy")
+        || obj.contains_key("allowlist")
+        || obj.contains_key("allowFrom")
+        || obj.contains_key("groupAllowFrom")
+        || obj.contains_key("dmPolicy")
+        || obj.contains_key("groupPolicy")
+        || obj.contains_key("guilds")
+        || obj.contains_key("accounts")
+        || obj.contains_key("dm")
+        || obj.contains_key("users")
+        || obj.contains_key("enabled")
+        || obj.contains_key("token")
+        || obj.contains_key("botToken")
+    {
+        return true;
+    }
+    if prefix.contains(".accounts.")
+        || prefix.contains(".guilds.")
+        || prefix.contains(".channels.")
+        || prefix.contains(".groups.")
+    {
+        return true;
+    }
+    if prefix.ends_with(".dm") || prefix.ends_with(".default") {
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+mod channel_node_tests {
+    use super::*;
+
+    #[test]
+    fn telegram_groups_resolve_through_the_unified_channel_nodes() {
+        let cfg = serde_json::json!({
+            "channels": { "telegram": {
+                "botToken": "t",
+                "groups": { "-1001": { "name": "Ops" }, "-1002": {} }
+            }}
         });
+        let nodes = collect_channel_nodes(&cfg);
+        let paths: Vec<&str> = nodes.iter().map(|n| n.path.as_str()).collect();
+        assert_eq!(paths, vec!["channels.telegram", "channels.telegram.groups.-1001", "channels.telegram.groups.-1002"]);
+        assert_eq!(
+            resolve_channel_node_identity(&cfg, &nodes[2]),
+            Some(("telegram".to_string(), "-1002".to_string(), "group".to_string()))
+        );
+        assert_eq!(channel_node_local_name(&cfg, &nodes[1].path).as_deref(), Some("Ops"));
     }
-    Ok(out)
 }
 
-fn resolve_profile_api_key(profile: &ModelProfile, base_dir: &Path) -> String {
-    // 1. Direct api_key field (user entered key directly in ClawPal)
-    if let Some(ref key) = profile.api_key {
-        let trimmed = key.trim();
-        if !trimmed.is_empty() {
-            return trimmed.to_string();
+fn resolve_channel_type(prefix: &str, obj: &serde_json::Map<String, Value>) -> Option<String> {
+    obj.get("type")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| {
+            if prefix.ends_with(".dm") {
+                Some("dm".into())
+            } else if prefix.contains(".accounts.") {
+                Some("account".into())
+            } else if prefix.contains(".channels.") && prefix.contains(".guilds.") {
+                Some("channel".into())
+            } else if prefix.contains(".guilds.") {
+                Some("guild".into())
+            } else if obj.contains_key("guilds") {
+                Some("platform".into())
+            } else if obj.contains_key("accounts") {
+                Some("platform".into())
+            } else {
+                None
+            }
+        })
+}
+
+fn resolve_channel_mode(obj: &serde_json::Map<String, Value>) -> Option<String> {
+    let mut modes: Vec<String> = Vec::new();
+    if let Some(v) = obj.get("mode").and_then(Value::as_str) {
+        modes.push(v.to_string());
+    }
+    if let Some(v) = obj.get("policy").and_then(Value::as_str) {
+        if !modes.iter().any(|m| m == v) {
+            modes.push(v.to_string());
+        }
+    }
+    if let Some(v) = obj.get("dmPolicy").and_then(Value::as_str) {
+        if !modes.iter().any(|m| m == v) {
+            modes.push(v.to_string());
+        }
+    }
+    if let Some(v) = obj.get("groupPolicy").and_then(Value::as_str) {
+        if !modes.iter().any(|m| m == v) {
+            modes.push(v.to_string());
+        }
+    }
+    if modes.is_empty() {
+        None
+    } else {
+        Some(modes.join(" / "))
+    }
+}
+
+fn collect_channel_allowlist(obj: &serde_json::Map<String, Value>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut uniq = HashSet::<String>::new();
+    for key in ["allowlist", "allowFrom", "groupAllowFrom"] {
+        if let Some(values) = obj.get(key).and_then(Value::as_array) {
+            for value in values.iter().filter_map(Value::as_str) {
+                let next = value.to_string();
+                if uniq.insert(next.clone()) {
+                    out.push(next);
+                }
+            }
+        }
+    }
+    if let Some(values) = obj.get("users").and_then(Value::as_array) {
+        for value in values.iter().filter_map(Value::as_str) {
+            let next = value.to_string();
+            if uniq.insert(next.clone()) {
+                out.push(next);
+            }
         }
     }
+    out
+}
 
-    // 2. Try auth_ref as env var name directly (e.g. "OPENAI_API_KEY")
-    let auth_ref = profile.auth_ref.trim();
-    if !auth_ref.is_empty() {
-        if let Ok(val) = std::env::var(auth_ref) {
-            if !val.trim().is_empty() {
-                return val;
+pub(crate) fn collect_agent_ids(cfg: &Value) -> Vec<String> {
+    let mut ids = Vec::new();
+    if let Some(agents) = cfg.get("agents").and_then(|v| v.get("list")).and_then(Value::as_array) {
+        for agent in agents {
+            if let Some(id) = agent.get("id").and_then(Value::as_str) {
+                ids.push(id.to_string());
             }
         }
     }
+    // Implicit "main" agent when no agents.list
+    if ids.is_empty() {
+        ids.push("main".into());
+    }
+    ids
+}
 
-    // 3. Look up auth_ref in agent-level auth-profiles.json files
-    //    Keys are stored at: {base_dir}/agents/{agent}/agent/auth-profiles.json
-    if !auth_ref.is_empty() {
-        if let Some(key) = resolve_key_from_agent_auth_profiles(base_dir, auth_ref) {
-            return key;
+#[tauri::command]
+pub fn find_unreachable_agents() -> Result<Vec<String>, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    Ok(find_unreachable_agents_in(&cfg))
+}
+
+fn collect_model_bindings(cfg: &Value, profiles: &[ModelProfile]) -> Vec<ModelBinding> {
+    let mut out = Vec::new();
+    let global = cfg
+        .pointer("/agents/defaults/model")
+        .or_else(|| cfg.pointer("/agents/default/model"))
+        .and_then(read_model_value);
+    out.push(ModelBinding {
+        scope: "global".into(),
+        scope_id: "global".into(),
+        model_profile_id: find_profile_by_model(profiles, global.as_deref()),
+        model_value: global,
+        path: Some("agents.defaults.model".into()),
+    });
+
+    if let Some(agents) = cfg.get("agents").and_then(|v| v.get("list")).and_then(Value::as_array) {
+        for agent in agents {
+            let id = agent.get("id").and_then(Value::as_str).unwrap_or("agent");
+            let model = agent.get("model").and_then(read_model_value);
+            out.push(ModelBinding {
+                scope: "agent".into(),
+                scope_id: id.to_string(),
+                model_profile_id: find_profile_by_model(profiles, model.as_deref()),
+                model_value: model,
+                path: Some(format!("agents.list.{id}.model")),
+            });
         }
     }
 
-    // 4. Try common env var naming conventions based on provider
-    let provider = profile.provider.trim().to_uppercase().replace('-', "_");
-    if !provider.is_empty() {
-        for suffix in ["_API_KEY", "_KEY", "_TOKEN"] {
-            let env_name = format!("{provider}{suffix}");
-            if let Ok(val) = std::env::var(&env_name) {
-                if !val.trim().is_empty() {
-                    return val;
+    fn walk_channel_binding(prefix: &str, node: &Value, out: &mut Vec<ModelBinding>, profiles: &[ModelProfile]) {
+        if let Some(obj) = node.as_object() {
+            if let Some(model) = obj.get("model").and_then(read_model_value) {
+                out.push(ModelBinding {
+                    scope: "channel".into(),
+                    scope_id: prefix.to_string(),
+                    model_profile_id: find_profile_by_model(profiles, Some(&model)),
+                    model_value: Some(model),
+                    path: Some(format!("{}.model", prefix)),
+                });
+            }
+            for (k, child) in obj {
+                if let Value::Object(_) = child {
+                    walk_channel_binding(&format!("{}.{}", prefix, k), child, out, profiles);
                 }
             }
         }
     }
 
-    String::new()
+    if let Some(channels) = cfg.get("channels") {
+        walk_channel_binding("channels", channels, &mut out, profiles);
+    }
+
+    out
 }
 
-/// Reads agent-level auth-profiles.json to find the actual API key/token.
-/// Scans all agents and returns the first match.
-fn resolve_key_from_agent_auth_profiles(base_dir: &Path, auth_ref: &str) -> Option<String> {
-    let agents_dir = base_dir.join("agents");
-    if !agents_dir.exists() {
-        return None;
-    }
-    let entries = fs::read_dir(&agents_dir).ok()?;
-    for entry in entries.flatten() {
-        let auth_file = entry.path().join("agent").join("auth-profiles.json");
-        if !auth_file.exists() {
-            continue;
-        }
-        let text = fs::read_to_string(&auth_file).ok()?;
-        let data: Value = serde_json::from_str(&text).ok()?;
-        if let Some(profiles) = data.get("profiles").and_then(Value::as_object) {
-            if let Some(auth_entry) = profiles.get(auth_ref) {
-                if let Some(key) = extract_token_from_auth_entry(auth_entry) {
-                    return Some(key);
-                }
-            }
+fn find_profile_by_model(profiles: &[ModelProfile], value: Option<&str>) -> Option<String> {
+    let value = value?;
+    let normalized = normalize_model_ref(value);
+    for profile in profiles {
+        if normalize_model_ref(&profile_to_model_value(profile)) == normalized
+            || normalize_model_ref(&profile.model) == normalized
+        {
+            return Some(profile.id.clone());
         }
     }
     None
 }
 
-/// Extract the actual key/token from an agent auth-profiles entry.
-/// Handles different auth types: token, api_key, oauth.
-fn extract_token_from_auth_entry(entry: &Value) -> Option<String> {
-    // "token" type → "token" field (e.g. anthropic)
-    // "api_key" type → "key" field (e.g. kimi-coding)
-    // "oauth" type → "access" field (e.g. minimax-portal, openai-codex)
-    for field in ["token", "key", "apiKey", "api_key", "access"] {
-        if let Some(val) = entry.get(field).and_then(Value::as_str) {
-            let trimmed = val.trim();
-            if !trimmed.is_empty() {
-                return Some(trimmed.to_string());
+fn resolve_auth_ref_for_provider(cfg: &Value, provider: &str) -> Option<String> {
+    let provider = provider.trim().to_lowercase();
+    if provider.is_empty() {
+        return None;
+    }
+    if let Some(auth_profiles) = cfg.pointer("/auth/profiles").and_then(Value::as_object) {
+        let mut fallback = None;
+        for (profile_id, profile) in auth_profiles {
+            let entry_provider = profile.get("provider").or_else(|| profile.get("name"));
+            if let Some(entry_provider) = entry_provider.and_then(Value::as_str) {
+                if entry_provider.trim().eq_ignore_ascii_case(&provider) {
+                    if profile_id.ends_with(":default") {
+                        return Some(profile_id.clone());
+                    }
+                    if fallback.is_none() {
+                        fallback = Some(profile_id.clone());
+                    }
+                }
             }
         }
+        if fallback.is_some() {
+            return fallback;
+        }
     }
     None
 }
 
-fn mask_api_key(key: &str) -> String {
-    let key = key.trim();
-    if key.is_empty() {
-        return "not set".to_string();
-    }
-    if key.len() <= 8 {
-        return "***".to_string();
-    }
-    let prefix = &key[..4.min(key.len())];
-    let suffix = &key[key.len().saturating_sub(4)..];
-    format!("{prefix}...{suffix}")
+#[tauri::command]
+pub fn read_raw_config() -> Result<String, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())
 }
 
-fn load_model_profiles(paths: &crate::models::OpenClawPaths) -> Vec<ModelProfile> {
-    let path = model_profiles_path(paths);
-    let text = std::fs::read_to_string(&path).unwrap_or_else(|_| r#"{"profiles":[]}"#.to_string());
-    #[derive(serde::Deserialize)]
-    struct Storage {
-        #[serde(default)]
-        profiles: Vec<ModelProfile>,
+/// Config file text exactly as stored, comments and formatting included, so
+/// the raw editor can round-trip it through `write_raw_config`. A missing
+/// file reads as `{}`.
+#[tauri::command]
+pub fn read_raw_config_text() -> Result<String, String> {
+    let paths = resolve_paths();
+    match fs::read_to_string(&paths.config_path) {
+        Ok(text) => Ok(text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok("{}".into()),
+        Err(e) => Err(format!("Failed to read {}: {e}", paths.config_path.display())),
     }
-    let parsed = serde_json::from_str::<Storage>(&text).unwrap_or(Storage {
-        profiles: Vec::new(),
-    });
-    parsed.profiles
 }
 
-fn save_model_profiles(paths: &crate::models::OpenClawPaths, profiles: &[ModelProfile]) -> Result<(), String> {
-    let path = model_profiles_path(paths);
-    #[derive(serde::Serialize)]
-    struct Storage<'a> {
-        profiles: &'a [ModelProfile],
-        #[serde(rename = "version")]
-        version: u8,
-    }
-    let payload = Storage {
-        profiles,
-        version: 1,
-    };
-    let text = serde_json::to_string_pretty(&payload).map_err(|e| e.to_string())?;
-    crate::config_io::write_text(&path, &text)?;
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+/// Save raw config text exactly as edited. The content must parse as JSON5,
+/// but comments and formatting are kept instead of being normalized.
+#[tauri::command]
+pub fn write_raw_config(content: String) -> Result<bool, String> {
+    json5::from_str::<Value>(&content).map_err(|e| format!("Invalid JSON5: {e}"))?;
+    let paths = resolve_paths();
+    let current = fs::read_to_string(&paths.config_path).unwrap_or_default();
+    if current == content {
+        return Ok(false);
     }
-    Ok(())
-}
-
-fn write_config_with_snapshot(
-    paths: &crate::models::OpenClawPaths,
-    current_text: &str,
-    next: &Value,
-    source: &str,
-) -> Result<(), String> {
-    let _ = add_snapshot(
+    add_snapshot(
         &paths.history_dir,
         &paths.metadata_path,
-        Some(source.to_string()),
-        source,
+        Some("raw-edit".into()),
+        "raw-edit",
         true,
-        current_text,
+        &current,
         None,
     )?;
-    write_json(&paths.config_path, next)
+    write_text(&paths.config_path, &content)?;
+    Ok(true)
 }
 
-fn set_nested_value(root: &mut Value, path: &str, value: Option<Value>) -> Result<(), String> {
-    let path = path.trim().trim_matches('.');
-    if path.is_empty() {
-        return Err("invalid path".into());
-    }
-    let mut cur = root;
-    let mut parts = path.split('.').peekable();
-    while let Some(part) = parts.next() {
-        let is_last = parts.peek().is_none();
-        let obj = cur
-            .as_object_mut()
-            .ok_or_else(|| "path must point to object".to_string())?;
-        if is_last {
-            if let Some(v) = value {
-                obj.insert(part.to_string(), v);
-            } else {
-                obj.remove(part);
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigTreeEntry {
+    pub pointer: String,
+    pub value: Value,
+    #[serde(rename = "type")]
+    pub value_type: String,
+    pub description: Option<String>,
+    pub editable: bool,
+}
+
+/// Known config keys: (pointer pattern, expected type, description).
+/// `*` matches any single segment (object key or array index).
+const CONFIG_KEY_DESCRIPTIONS: &[(&str, &str, &str)] = &[
+    ("/gateway", "object", "Gateway server settings"),
+    ("/gateway/port", "number", "TCP port the gateway listens on (default 18789)"),
+    ("/gateway/bind", "string", "Address the gateway binds to"),
+    ("/gateway/mode", "string", "Gateway run mode (local or remote)"),
+    ("/gateway/auth", "object", "Gateway authentication settings"),
+    ("/gateway/auth/token", "string", "Shared token clients use to authenticate with the gateway"),
+    ("/agents", "object", "Agent definitions and defaults"),
+    ("/agents/defaults", "object", "Settings inherited by every agent"),
+    ("/agents/defaults/model", "string", "Default model used by agents without an override"),
+    ("/agents/defaults/model/primary", "string", "Primary default model"),
+    ("/agents/defaults/model/fallbacks", "array", "Models tried in order when the primary fails"),
+    ("/agents/defaults/workspace", "string", "Default workspace directory for agents"),
+    ("/agents/list", "array", "Configured agents"),
+    ("/agents/list/*/id", "string", "Unique agent identifier"),
+    ("/agents/list/*/model", "string", "Model override for this agent"),
+    ("/agents/list/*/workspace", "string", "Workspace directory for this agent"),
+    ("/channels", "object", "Messaging channel integrations"),
+    ("/channels/*", "object", "Channel platform settings"),
+    ("/channels/*/enabled", "boolean", "Whether this channel platform is enabled"),
+    ("/channels/*/model", "string", "Model override for this channel"),
+    ("/channels/*/botToken", "string", "Bot token used to connect to the platform"),
+    ("/channels/*/dmPolicy", "string", "Who may message the bot directly"),
+    ("/channels/*/groupPolicy", "string", "How the bot handles group messages"),
+    ("/models", "object", "Model provider definitions"),
+    ("/models/providers", "object", "Custom model providers keyed by provider id"),
+    ("/models/providers/*/baseUrl", "string", "Base URL of the provider API"),
+    ("/models/providers/*/apiKey", "string", "API key for the provider"),
+    ("/models/providers/*/api", "string", "API flavour spoken by the provider"),
+    ("/models/providers/*/models", "array", "Models exposed by the provider"),
+    ("/bindings", "array", "Routes from channels to agents"),
+    ("/bindings/*/agentId", "string", "Agent that handles matching messages"),
+    ("/bindings/*/match", "object", "Conditions a message must satisfy"),
+    ("/bindings/*/match/channel", "string", "Channel platform to match"),
+    ("/bindings/*/match/guildId", "string", "Discord guild id to match"),
+    ("/bindings/*/match/peer/id", "string", "Peer (user, group or channel) id to match"),
+    ("/bindings/*/match/peer/kind", "string", "Peer kind (dm, group or channel)"),
+];
+
+fn json_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Walk the config depth-first, yielding the JSON pointer of every node.
+fn config_outline(prefix: &str, node: &Value, out: &mut Vec<(String, Value)>) {
+    if !prefix.is_empty() {
+        out.push((prefix.to_string(), node.clone()));
+    }
+    match node {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let pointer = format!("{prefix}/{}", escape_pointer_segment(key));
+                config_outline(&pointer, child, out);
             }
-            return Ok(());
         }
-        let child = obj
-            .entry(part.to_string())
-            .or_insert_with(|| Value::Object(Default::default()));
-        if !child.is_object() {
-            *child = Value::Object(Default::default());
+        Value::Array(items) => {
+            for (idx, child) in items.iter().enumerate() {
+                config_outline(&format!("{prefix}/{idx}"), child, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn lookup_config_key_description(pointer: &str) -> Option<(&'static str, &'static str)> {
+    let segments: Vec<&str> = pointer.split('/').skip(1).collect();
+    CONFIG_KEY_DESCRIPTIONS.iter().find_map(|(pattern, ty, desc)| {
+        let pattern_segments: Vec<&str> = pattern.split('/').skip(1).collect();
+        if pattern_segments.len() != segments.len() {
+            return None;
+        }
+        let matched = pattern_segments
+            .iter()
+            .zip(&segments)
+            .all(|(p, s)| *p == "*" || p == s);
+        if matched { Some((*ty, *desc)) } else { None }
+    })
+}
+
+fn build_config_schema_tree(cfg: &Value) -> Vec<ConfigTreeEntry> {
+    let mut outline = Vec::new();
+    config_outline("", cfg, &mut outline);
+    outline
+        .into_iter()
+        .map(|(pointer, value)| {
+            let value_type = json_value_type(&value).to_string();
+            let known = lookup_config_key_description(&pointer);
+            // Only scalars whose actual type matches the documented one get a typed widget.
+            let editable = match known {
+                Some((ty, _)) => ty == value_type && !matches!(value, Value::Object(_) | Value::Array(_)),
+                None => false,
+            };
+            ConfigTreeEntry {
+                pointer,
+                value,
+                value_type,
+                description: known.map(|(_, desc)| desc.to_string()),
+                editable,
+            }
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn config_schema_tree() -> Result<Vec<ConfigTreeEntry>, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    Ok(build_config_schema_tree(&cfg))
+}
+
+#[tauri::command]
+pub fn find_unknown_config_keys() -> Result<Vec<UnknownConfigKey>, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    Ok(find_unknown_config_keys_in(&cfg))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigFinding {
+    pub path: String,
+    pub severity: String,
+    pub message: String,
+}
+
+fn config_finding(path: impl Into<String>, severity: &str, message: impl Into<String>) -> ConfigFinding {
+    ConfigFinding {
+        path: path.into(),
+        severity: severity.into(),
+        message: message.into(),
+    }
+}
+
+/// Structural checks the gateway enforces at startup. Errors would make the
+/// gateway reject the config; warnings are likely mistakes it tolerates.
+fn validate_config_value(cfg: &Value) -> Vec<ConfigFinding> {
+    let mut out = Vec::new();
+    if !cfg.is_object() {
+        out.push(config_finding("", "error", "config root must be an object"));
+        return out;
+    }
+
+    if let Some(port) = cfg.pointer("/gateway/port") {
+        let valid = port.as_u64().is_some_and(|p| (1..=u16::MAX as u64).contains(&p));
+        if !valid {
+            out.push(config_finding("/gateway/port", "error", "gateway port must be an integer between 1 and 65535"));
         }
-        cur = child;
     }
-    unreachable!("path should have at least one segment");
-}
 
-fn set_agent_model_value(
-    root: &mut Value,
-    agent_id: &str,
-    model: Option<String>,
-) -> Result<(), String> {
-    if let Some(agents) = root.pointer_mut("/agents").and_then(Value::as_object_mut) {
-        if let Some(list) = agents.get_mut("list").and_then(Value::as_array_mut) {
-            for agent in list {
-                if agent.get("id").and_then(Value::as_str) == Some(agent_id) {
-                    if let Some(agent_obj) = agent.as_object_mut() {
-                        match model {
-                            Some(v) => {
-                                // If existing model is an object, update "primary" inside it
-                                if let Some(existing) = agent_obj.get_mut("model") {
-                                    if let Some(model_obj) = existing.as_object_mut() {
-                                        model_obj.insert("primary".into(), Value::String(v));
-                                        return Ok(());
-                                    }
-                                }
-                                agent_obj.insert("model".into(), Value::String(v));
-                            }
-                            None => {
-                                agent_obj.remove("model");
-                            }
+    if let Some(model) = cfg.pointer("/agents/defaults/model") {
+        if read_model_value(model).is_none() {
+            out.push(config_finding("/agents/defaults/model", "warn", "default model is not a recognizable model reference"));
+        }
+    }
+
+    let mut agent_ids = HashSet::new();
+    match cfg.pointer("/agents/list") {
+        Some(Value::Array(list)) => {
+            for (idx, agent) in list.iter().enumerate() {
+                let path = format!("/agents/list/{idx}");
+                let Some(obj) = agent.as_object() else {
+                    out.push(config_finding(path, "error", "agent entry must be an object"));
+                    continue;
+                };
+                match obj.get("id").and_then(Value::as_str).map(str::trim) {
+                    Some(id) if !id.is_empty() => {
+                        if !agent_ids.insert(id.to_string()) {
+                            out.push(config_finding(format!("{path}/id"), "error", format!("duplicate agent id '{id}'")));
                         }
                     }
-                    return Ok(());
+                    _ => out.push(config_finding(format!("{path}/id"), "error", "agent id must be a non-empty string")),
+                }
+                if let Some(model) = obj.get("model") {
+                    if read_model_value(model).is_none() {
+                        out.push(config_finding(format!("{path}/model"), "warn", "agent model is not a recognizable model reference"));
+                    }
                 }
             }
         }
+        Some(_) => out.push(config_finding("/agents/list", "error", "agents.list must be an array")),
+        None => {}
     }
-    Err(format!("agent not found: {agent_id}"))
-}
 
-fn load_model_catalog(
-    paths: &crate::models::OpenClawPaths,
-) -> Result<Vec<ModelCatalogProvider>, String> {
-    let cache_path = model_catalog_cache_path(paths);
-    let current_version = resolve_openclaw_version();
-    let cached = read_model_catalog_cache(&cache_path);
-    if let Some(selected) = select_catalog_from_cache(cached.as_ref(), &current_version) {
-        return Ok(selected);
+    match cfg.get("bindings") {
+        Some(Value::Array(bindings)) => {
+            for (idx, binding) in bindings.iter().enumerate() {
+                let path = format!("/bindings/{idx}");
+                let Some(obj) = binding.as_object() else {
+                    out.push(config_finding(path, "error", "binding must be an object"));
+                    continue;
+                };
+                let has_channel = obj
+                    .get("match")
+                    .and_then(|m| m.get("channel"))
+                    .and_then(Value::as_str)
+                    .is_some_and(|c| !c.trim().is_empty());
+                if !has_channel {
+                    out.push(config_finding(format!("{path}/match/channel"), "error", "binding needs a match.channel string"));
+                }
+                match obj.get("agentId").and_then(Value::as_str) {
+                    Some(agent_id) if !agent_ids.is_empty() && !agent_ids.contains(agent_id) => out.push(config_finding(
+                        format!("{path}/agentId"),
+                        "warn",
+                        format!("binding targets unknown agent '{agent_id}'"),
+                    )),
+                    Some(_) => {}
+                    None => out.push(config_finding(format!("{path}/agentId"), "error", "binding needs an agentId string")),
+                }
+            }
+        }
+        Some(_) => out.push(config_finding("/bindings", "error", "bindings must be an array")),
+        None => {}
     }
 
-    if let Some(catalog) = extract_model_catalog_from_cli(paths) {
-        if !catalog.is_empty() {
-            return Ok(catalog);
+    match cfg.pointer("/models/providers") {
+        Some(Value::Object(providers)) => {
+            for (name, provider) in providers {
+                if !provider.is_object() {
+                    out.push(config_finding(
+                        format!("/models/providers/{}", escape_pointer_segment(name)),
+                        "error",
+                        "provider definition must be an object",
+                    ));
+                }
+            }
         }
+        Some(_) => out.push(config_finding("/models/providers", "error", "models.providers must be an object")),
+        None => {}
     }
 
-    if let Some(previous) = cached {
-        if !previous.providers.is_empty() && previous.error.is_none() {
-            return Ok(previous.providers);
+    for node in collect_channel_nodes(cfg) {
+        if node.has_model_field && node.model.is_none() {
+            out.push(config_finding(
+                format!("/{}/model", node.path.replace('.', "/")),
+                "warn",
+                "channel model override is not a recognizable model reference",
+            ));
         }
     }
+    out
+}
 
-    Err("Failed to load model catalog from openclaw CLI".into())
+/// Validate raw config text (JSON5) before it is saved.
+#[tauri::command]
+pub fn validate_config(content: String) -> Result<Vec<ConfigFinding>, String> {
+    match json5::from_str::<Value>(&content) {
+        Ok(cfg) => Ok(validate_config_value(&cfg)),
+        Err(e) => Ok(vec![config_finding("", "error", format!("config is not valid JSON5: {e}"))]),
+    }
 }
 
-fn select_catalog_from_cache(
-    cached: Option<&ModelCatalogProviderCache>,
-    current_version: &str,
-) -> Option<Vec<ModelCatalogProvider>> {
-    let cache = cached?;
-    if cache.cli_version != current_version {
-        return None;
+#[cfg(test)]
+mod config_validation_tests {
+    use super::*;
+
+    #[test]
+    fn flags_structural_mistakes() {
+        let cfg = serde_json::json!({
+            "gateway": { "port": 70000 },
+            "agents": { "list": [{ "id": "main" }, { "id": "main" }, { "name": "x" }] },
+            "bindings": [{ "agentId": "ghost", "match": {} }],
+            "models": { "providers": { "openai": "nope" } }
+        });
+        let paths: Vec<String> = validate_config_value(&cfg).into_iter().map(|f| f.path).collect();
+        assert!(paths.contains(&"/gateway/port".to_string()));
+        assert!(paths.contains(&"/agents/list/1/id".to_string()));
+        assert!(paths.contains(&"/agents/list/2/id".to_string()));
+        assert!(paths.contains(&"/bindings/0/match/channel".to_string()));
+        assert!(paths.contains(&"/bindings/0/agentId".to_string()));
+        assert!(paths.contains(&"/models/providers/openai".to_string()));
+
+        let ok = serde_json::json!({
+            "gateway": { "port": 18789 },
+            "agents": { "list": [{ "id": "main" }] },
+            "bindings": [{ "agentId": "main", "match": { "channel": "discord" } }]
+        });
+        assert!(validate_config_value(&ok).is_empty());
     }
-    if cache.error.is_some() || cache.providers.is_empty() {
-        return None;
+}
+
+// resolve_full_api_key is intentionally not exposed as a Tauri command.
+// It returns raw API keys which should never be sent to the frontend.
+#[allow(dead_code)]
+fn resolve_full_api_key(profile_id: String) -> Result<String, String> {
+    let paths = resolve_paths();
+    let profiles = load_model_profiles(&paths);
+    let profile = profiles.iter().find(|p| p.id == profile_id)
+        .ok_or_else(|| "Profile not found".to_string())?;
+    let key = resolve_profile_api_key(profile, &paths.base_dir);
+    if key.is_empty() {
+        return Err("No API key configured for this profile".to_string());
     }
-    Some(cache.providers.clone())
+    Ok(key)
 }
 
-/// Parse CLI output from `openclaw models list --all --json` into grouped providers.
-/// Handles various output formats: flat arrays, {models: [...]}, {items: [...]}, {data: [...]}.
-/// Strips prefix junk (plugin log lines) before the JSON.
-fn parse_model_catalog_from_cli_output(raw: &str) -> Option<Vec<ModelCatalogProvider>> {
-    let json_str = extract_json_from_output(raw)?;
-    let response: Value = serde_json::from_str(json_str).ok()?;
-    let models: Vec<Value> = response
-        .as_array()
-        .map(|values| values.to_vec())
-        .or_else(|| {
-            response
-                .get("models")
-                .and_then(Value::as_array)
-                .map(|values| values.to_vec())
-        })
-        .or_else(|| {
-            response
-                .get("items")
-                .and_then(Value::as_array)
-                .map(|values| values.to_vec())
-        })
-        .or_else(|| {
-            response
-                .get("data")
-                .and_then(Value::as_array)
-                .map(|values| values.to_vec())
-        })
-        .unwrap_or_default();
-    if models.is_empty() {
-        return None;
+#[tauri::command]
+pub fn open_url(url: String) -> Result<(), String> {
+    let trimmed = url.trim();
+    if trimmed.is_empty() {
+        return Err("URL is required".into());
     }
-    let mut providers: BTreeMap<String, ModelCatalogProvider> = BTreeMap::new();
-    for model in &models {
-        let key = model
-            .get("key")
-            .and_then(Value::as_str)
-            .map(str::to_string)
-            .or_else(|| {
-                let provider = model.get("provider").and_then(Value::as_str)?;
-                let model_id = model.get("id").and_then(Value::as_str)?;
-                Some(format!("{provider}/{model_id}"))
-            });
-        let key = match key {
-            Some(k) => k,
-            None => continue,
-        };
-        let mut parts = key.splitn(2, '/');
-        let provider = match parts.next() {
-            Some(p) if !p.trim().is_empty() => p.trim().to_lowercase(),
-            _ => continue,
-        };
-        let id = parts.next().unwrap_or("").trim().to_string();
-        if id.is_empty() {
-            continue;
-        }
-        let name = model
-            .get("name")
-            .and_then(Value::as_str)
-            .or_else(|| model.get("model").and_then(Value::as_str))
-            .or_else(|| model.get("title").and_then(Value::as_str))
-            .map(str::to_string);
-        let base_url = model
-            .get("baseUrl")
-            .or_else(|| model.get("base_url"))
-            .or_else(|| model.get("apiBase"))
-            .or_else(|| model.get("api_base"))
-            .and_then(Value::as_str)
-            .map(str::to_string)
-            .or_else(|| {
-                response
-                    .get("providers")
-                    .and_then(Value::as_object)
-                    .and_then(|providers| providers.get(&provider))
-                    .and_then(Value::as_object)
-                    .and_then(|provider_cfg| {
-                        provider_cfg
-                            .get("baseUrl")
-                            .or_else(|| provider_cfg.get("base_url"))
-                            .or_else(|| provider_cfg.get("apiBase"))
-                            .or_else(|| provider_cfg.get("api_base"))
-                            .and_then(Value::as_str)
-                    })
-                    .map(str::to_string)
-            });
-        let entry = providers.entry(provider.clone()).or_insert(ModelCatalogProvider {
-            provider: provider.clone(),
-            base_url,
-            models: Vec::new(),
-        });
-        if !entry.models.iter().any(|existing| existing.id == id) {
-            entry.models.push(ModelCatalogModel {
-                id: id.clone(),
-                name: name.clone(),
-            });
+    // Allow http(s) URLs and local paths within user home directory
+    if !trimmed.starts_with("http://") && !trimmed.starts_with("https://") {
+        // For local paths, ensure they don't execute apps
+        let path = std::path::Path::new(trimmed);
+        if path.extension().map_or(false, |ext| ext == "app" || ext == "exe") {
+            return Err("Cannot open application files".into());
         }
     }
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg(&url).spawn().map_err(|e| e.to_string())?;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open").arg(&url).spawn().map_err(|e| e.to_string())?;
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("cmd").args(["/c", "start", &url]).spawn().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
 
-    if providers.is_empty() {
-        return None;
+#[tauri::command]
+pub async fn chat_via_openclaw(agent_id: String, message: String, session_id: Option<String>) -> Result<Value, String> {
+    tauri::async_runtime::spawn_blocking(move || chat_via_openclaw_sync(agent_id, message, session_id))
+        .await
+        .map_err(|e| format!("Task join failed: {}", e))?
+}
+
+fn chat_via_openclaw_sync(agent_id: String, message: String, session_id: Option<String>) -> Result<Value, String> {
+    let mut args = vec![
+        "agent".to_string(),
+        "--local".to_string(),
+        "--agent".to_string(),
+        agent_id,
+        "--message".to_string(),
+        message,
+        "--json".to_string(),
+        "--no-color".to_string(),
+    ];
+    if let Some(sid) = session_id {
+        args.push("--session-id".to_string());
+        args.push(sid);
     }
 
-    let mut out: Vec<ModelCatalogProvider> = providers.into_values().collect();
-    for provider in &mut out {
-        provider.models.sort_by(|a, b| a.id.cmp(&b.id));
+    let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+    let output = run_openclaw_raw(&arg_refs)?;
+    let json_str = extract_json_from_output(&output.stdout)
+        .ok_or_else(|| format!("No JSON in openclaw output: {}", output.stdout))?;
+    serde_json::from_str(json_str)
+        .map_err(|e| format!("Parse openclaw response failed: {}", e))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestBindingResult {
+    pub agent_id: String,
+    /// False when no binding matched and the message went to the default agent.
+    pub matched: bool,
+    pub binding_index: Option<usize>,
+    pub note: Option<String>,
+    pub response: Value,
+}
+
+/// Rewrite numeric `match.peer.id` / `match.guildId` values as strings in
+/// place. Returns how many values changed.
+fn normalize_peer_ids_in(cfg: &mut Value) -> usize {
+    let Some(bindings) = cfg.get_mut("bindings").and_then(Value::as_array_mut) else {
+        return 0;
+    };
+    let mut changed = 0;
+    for binding in bindings {
+        for pointer in ["/match/peer/id", "/match/guildId"] {
+            if let Some(value) = binding.pointer_mut(pointer) {
+                let canonical = match value {
+                    Value::Number(n) => Some(n.to_string()),
+                    Value::String(s) if s.trim() != s => Some(s.trim().to_string()),
+                    _ => None,
+                };
+                if let Some(canonical) = canonical {
+                    *value = Value::String(canonical);
+                    changed += 1;
+                }
+            }
+        }
     }
-    out.sort_by(|a, b| a.provider.cmp(&b.provider));
-    Some(out)
+    changed
 }
 
-fn extract_model_catalog_from_cli(
-    paths: &crate::models::OpenClawPaths,
-) -> Option<Vec<ModelCatalogProvider>> {
-    let output = run_openclaw_raw(&["models", "list", "--all", "--json", "--no-color"]).ok()?;
-    if output.stdout.trim().is_empty() {
-        return None;
+#[tauri::command]
+pub fn normalize_binding_peer_ids() -> Result<usize, String> {
+    let paths = resolve_paths();
+    let mut cfg = read_openclaw_config(&paths)?;
+    let current = serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())?;
+    let changed = normalize_peer_ids_in(&mut cfg);
+    if changed > 0 {
+        write_config_with_snapshot(&paths, &current, &cfg, "normalize-peer-ids")?;
     }
+    Ok(changed)
+}
 
-    let out = parse_model_catalog_from_cli_output(&output.stdout)?;
-    let _ = cache_model_catalog(paths, out.clone());
-    Some(out)
+#[tauri::command]
+pub fn validate_bindings_against_channels() -> Result<Vec<OrphanedBinding>, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths)?;
+    Ok(find_orphaned_bindings(&cfg))
 }
 
-fn cache_model_catalog(paths: &crate::models::OpenClawPaths, providers: Vec<ModelCatalogProvider>) -> Option<()> {
-    let cache_path = model_catalog_cache_path(paths);
-    let now = unix_timestamp_secs();
-    let cache = ModelCatalogProviderCache {
-        cli_version: resolve_openclaw_version(),
-        updated_at: now,
-        providers,
-        source: "openclaw models list --all --json".into(),
-        error: None,
-    };
-    let _ = save_model_catalog_cache(&cache_path, &cache);
-    Some(())
+/// Find the binding that routes `peer_id` on `channel_type`. An exact peer
+/// match wins over a channel-wide binding (one without peer/guild filters).
+fn resolve_binding_agent(cfg: &Value, channel_type: &str, peer_id: &str) -> Option<(usize, String)> {
+    let bindings = cfg.get("bindings").and_then(Value::as_array)?;
+    let mut channel_wide = None;
+    for (index, binding) in bindings.iter().enumerate() {
+        let Some(agent_id) = binding.get("agentId").and_then(Value::as_str) else {
+            continue;
+        };
+        let Some(m) = binding.get("match") else {
+            continue;
+        };
+        if m.get("channel").and_then(Value::as_str) != Some(channel_type) {
+            continue;
+        }
+        let binding_peer = match m.pointer("/peer/id") {
+            Some(Value::String(s)) => Some(s.trim().to_string()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+        match binding_peer {
+            Some(id) if id == peer_id => return Some((index, agent_id.to_string())),
+            Some(_) => {}
+            None => {
+                if channel_wide.is_none() && m.get("guildId").is_none() {
+                    channel_wide = Some((index, agent_id.to_string()));
+                }
+            }
+        }
+    }
+    channel_wide
 }
 
 #[cfg(test)]
-mod model_catalog_cache_tests {
+mod binding_resolution_tests {
     use super::*;
 
     #[test]
-    fn test_select_cached_catalog_same_version() {
-        let cached = ModelCatalogProviderCache {
-            cli_version: "1.2.3".into(),
-            updated_at: 123,
-            providers: vec![ModelCatalogProvider {
-                provider: "openrouter".into(),
-                base_url: None,
-                models: vec![ModelCatalogModel {
-                    id: "moonshotai/kimi-k2.5".into(),
-                    name: Some("Kimi".into()),
-                }],
-            }],
-            source: "openclaw models list --all --json".into(),
-            error: None,
-        };
-        let selected = select_catalog_from_cache(Some(&cached), "1.2.3");
-        assert!(selected.is_some(), "same version should use cache");
+    fn numeric_peer_ids_match_exactly_before_channel_wide() {
+        let cfg = serde_json::json!({
+            "bindings": [
+                { "agentId": "ops", "match": { "channel": "telegram" } },
+                { "agentId": "support", "match": { "channel": "telegram", "peer": { "kind": "group", "id": -1001 } } }
+            ]
+        });
+        assert_eq!(resolve_binding_agent(&cfg, "telegram", "-1001"), Some((1, "support".to_string())));
+        assert_eq!(resolve_binding_agent(&cfg, "telegram", "-1002"), Some((0, "ops".to_string())));
+        assert_eq!(resolve_binding_agent(&cfg, "discord", "-1001"), None);
     }
+}
 
-    #[test]
-    fn test_select_cached_catalog_version_mismatch_requires_refresh() {
-        let cached = ModelCatalogProviderCache {
-            cli_version: "1.2.2".into(),
-            updated_at: 123,
-            providers: vec![ModelCatalogProvider {
-                provider: "openrouter".into(),
-                base_url: None,
-                models: vec![ModelCatalogModel {
-                    id: "moonshotai/kimi-k2.5".into(),
-                    name: Some("Kimi".into()),
-                }],
-            }],
-            source: "openclaw models list --all --json".into(),
-            error: None,
-        };
-        let selected = select_catalog_from_cache(Some(&cached), "1.2.3");
-        assert!(selected.is_none(), "version mismatch must force CLI refresh");
+/// Send a test message through the agent a binding routes to, verifying
+/// channel→agent routing end-to-end.
+#[tauri::command]
+pub async fn test_binding(channel_type: String, peer_id: String, message: String) -> Result<TestBindingResult, String> {
+    if channel_type.trim().is_empty() || peer_id.trim().is_empty() {
+        return Err("channel type and peer id are required".into());
     }
+    tauri::async_runtime::spawn_blocking(move || {
+        let paths = resolve_paths();
+        let cfg = read_openclaw_config(&paths)?;
+        let (binding_index, agent_id, note) = match resolve_binding_agent(&cfg, channel_type.trim(), peer_id.trim()) {
+            Some((index, agent_id)) => (Some(index), agent_id, None),
+            None => (
+                None,
+                crate::doctor::default_agent_id(&cfg),
+                Some(format!(
+                    "No binding matches {channel_type} peer {peer_id}; messages would go to the default agent"
+                )),
+            ),
+        };
+        let response = chat_via_openclaw_sync(agent_id.clone(), message, None)?;
+        Ok(TestBindingResult {
+            agent_id,
+            matched: binding_index.is_some(),
+            binding_index,
+            note,
+            response,
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join failed: {}", e))?
 }
 
-fn collect_channel_nodes(cfg: &Value) -> Vec<ChannelNode> {
-    let mut out = Vec::new();
-    if let Some(channels) = cfg.get("channels") {
-        walk_channel_nodes("channels", channels, &mut out);
+#[tauri::command]
+pub async fn remote_chat_via_openclaw(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    agent_id: String,
+    message: String,
+    session_id: Option<String>,
+) -> Result<Value, String> {
+    let escaped_msg = message.replace('\'', "'\\''");
+    let escaped_agent = agent_id.replace('\'', "'\\''");
+    let mut cmd = format!(
+        "openclaw agent --local --agent '{}' --message '{}' --json --no-color",
+        escaped_agent, escaped_msg
+    );
+    if let Some(sid) = session_id {
+        let escaped_sid = sid.replace('\'', "'\\''");
+        cmd.push_str(&format!(" --session-id '{}'", escaped_sid));
     }
-    out.sort_by(|a, b| a.path.cmp(&b.path));
-    out
+    let result = pool.exec_login(&host_id, &cmd).await?;
+    if result.exit_code != 0 {
+        return Err(format!(
+            "Remote chat failed (exit {}): {}",
+            result.exit_code, result.stderr
+        ));
+    }
+    let json_str = extract_json_from_output(&result.stdout)
+        .ok_or_else(|| format!("No JSON in remote openclaw output: {}", result.stdout))?;
+    serde_json::from_str(json_str)
+        .map_err(|e| format!("Failed to parse remote chat response: {e}"))
 }
 
-fn walk_channel_nodes(prefix: &str, node: &Value, out: &mut Vec<ChannelNode>) {
-    let Some(obj) = node.as_object() else {
-        return;
-    };
+// ---- Support bundle inspection ----
 
-    if is_channel_like_node(prefix, obj) {
-        let channel_type = resolve_channel_type(prefix, obj);
-        let mode = resolve_channel_mode(obj);
-        let allowlist = collect_channel_allowlist(obj);
-        let has_model_field = obj.contains_key("model");
-        let model = obj.get("model").and_then(read_model_value);
-        out.push(ChannelNode {
-            path: prefix.to_string(),
-            channel_type,
-            mode,
-            allowlist,
-            model,
-            has_model_field,
-            display_name: None,
-            name_status: None,
-        });
-    }
+/// Files inside a support bundle (`.tar.gz` or extracted directory).
+const BUNDLE_METADATA_FILE: &str = "metadata.json";
+const BUNDLE_CONFIG_FILE: &str = "openclaw.json";
+const BUNDLE_DOCTOR_FILE: &str = "doctor.json";
+const BUNDLE_LOGS_DIR: &str = "logs";
 
-    for (key, child) in obj {
-        if key == "allowlist" || key == "model" || key == "mode" {
-            continue;
-        }
-        if let Value::Object(_) = child {
-            walk_channel_nodes(&format!("{prefix}.{key}"), child, out);
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportBundleInspection {
+    pub path: String,
+    pub metadata: Option<Value>,
+    pub config: Option<Value>,
+    pub doctor_report: Option<Value>,
+    /// Log file name -> last lines.
+    pub log_tails: BTreeMap<String, String>,
+    /// Components that were absent or unreadable.
+    pub missing: Vec<String>,
+}
+
+/// Mask every string value stored under a secret-looking key.
+fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if env_name_looks_secret(key) {
+                    if let Value::String(s) = child {
+                        *s = mask_api_key(s);
+                        continue;
+                    }
+                }
+                redact_secrets(child);
+            }
         }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
     }
 }
 
-fn enrich_channel_display_names(
-    paths: &crate::models::OpenClawPaths,
-    cfg: &Value,
-    nodes: &mut [ChannelNode],
-) -> Result<(), String> {
-    let mut grouped: BTreeMap<String, Vec<(usize, String, String)>> = BTreeMap::new();
-    let mut local_names: Vec<(usize, String)> = Vec::new();
+fn read_bundle_dir(dir: &Path, display_path: &str) -> Result<SupportBundleInspection, String> {
+    let mut inspection = SupportBundleInspection {
+        path: display_path.to_string(),
+        metadata: None,
+        config: None,
+        doctor_report: None,
+        log_tails: BTreeMap::new(),
+        missing: Vec::new(),
+    };
+    let read_json = |name: &str| -> Option<Value> {
+        let text = fs::read_to_string(dir.join(name)).ok()?;
+        json5::from_str(&text).ok()
+    };
 
-    for (index, node) in nodes.iter().enumerate() {
-        if let Some((plugin, identifier, kind)) = resolve_channel_node_identity(cfg, node) {
-            grouped
-                .entry(plugin)
-                .or_default()
-                .push((index, identifier, kind));
-        }
-        if node.display_name.is_none() {
-            if let Some(local_name) = channel_node_local_name(cfg, &node.path) {
-                local_names.push((index, local_name));
-            }
+    inspection.metadata = read_json(BUNDLE_METADATA_FILE);
+    inspection.config = read_json(BUNDLE_CONFIG_FILE).map(|mut cfg| {
+        // Bundles should already be redacted; mask again in case they are not.
+        redact_secrets(&mut cfg);
+        cfg
+    });
+    inspection.doctor_report = read_json(BUNDLE_DOCTOR_FILE);
+    for (name, present) in [
+        (BUNDLE_METADATA_FILE, inspection.metadata.is_some()),
+        (BUNDLE_CONFIG_FILE, inspection.config.is_some()),
+        (BUNDLE_DOCTOR_FILE, inspection.doctor_report.is_some()),
+    ] {
+        if !present {
+            inspection.missing.push(name.to_string());
         }
     }
-    for (index, local_name) in local_names {
-        if let Some(node) = nodes.get_mut(index) {
-            node.display_name = Some(local_name);
-            node.name_status = Some("local".into());
+
+    match fs::read_dir(dir.join(BUNDLE_LOGS_DIR)) {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_file() {
+                    continue;
+                }
+                if let Ok(text) = fs::read_to_string(&path) {
+                    let lines: Vec<&str> = text.lines().collect();
+                    let start = lines.len().saturating_sub(200);
+                    inspection
+                        .log_tails
+                        .insert(entry.file_name().to_string_lossy().to_string(), lines[start..].join("\n"));
+                }
+            }
         }
+        Err(_) => inspection.missing.push(BUNDLE_LOGS_DIR.to_string()),
     }
 
-    let cache_file = paths.clawpal_dir.join("channel-name-cache.json");
-    if nodes.is_empty() {
-        if cache_file.exists() {
-            let _ = fs::remove_file(&cache_file);
-        }
-        return Ok(());
+    if inspection.metadata.is_none() && inspection.config.is_none() && inspection.doctor_report.is_none() && inspection.log_tails.is_empty() {
+        return Err(format!("{display_path} does not look like a ClawPal support bundle"));
     }
+    Ok(inspection)
+}
 
-    for (plugin, entries) in grouped {
-        if entries.is_empty() {
-            continue;
-        }
-        let ids: Vec<String> = entries.iter().map(|(_, identifier, _)| identifier.clone()).collect();
-        let kind = &entries[0].2;
-        let mut args = vec![
-            "channels".to_string(),
-            "resolve".to_string(),
-            "--json".to_string(),
-            "--channel".to_string(),
-            plugin.clone(),
-            "--kind".to_string(),
-            kind.clone(),
-        ];
-        for entry in &ids {
-            args.push(entry.clone());
-        }
-        let args: Vec<&str> = args.iter().map(String::as_str).collect();
-        let output = match run_openclaw_raw(&args) {
-            Ok(output) => output,
-            Err(_) => {
-                for (index, _, _) in entries {
-                    nodes[index].name_status = Some("resolve failed".into());
-                }
-                continue;
-            }
-        };
-        if output.stdout.trim().is_empty() {
-            for (index, _, _) in entries {
-                nodes[index].name_status = Some("unresolved".into());
-            }
-            continue;
+/// Load a support bundle (`.tar.gz` or extracted directory) read-only for
+/// diagnosis. Nothing from the bundle touches the local install.
+#[tauri::command]
+pub async fn inspect_support_bundle(path: String) -> Result<SupportBundleInspection, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let source = PathBuf::from(expand_tilde(path.trim()));
+        if source.is_dir() {
+            return read_bundle_dir(&source, &path);
         }
-        let json_str = extract_json_from_output(&output.stdout).unwrap_or("[]");
-        let parsed: Vec<Value> = serde_json::from_str(json_str).unwrap_or_default();
-        let mut name_map = HashMap::new();
-        for item in parsed {
-            let input = item.get("input").and_then(Value::as_str).unwrap_or_default().to_string();
-            let resolved = item.get("resolved").and_then(Value::as_bool).unwrap_or(false);
-            let name = item
-                .get("name")
-                .and_then(Value::as_str)
-                .map(|value| value.trim().to_string())
-                .filter(|value| !value.is_empty());
-            let note = item.get("note").and_then(Value::as_str).map(|value| value.to_string());
-            if !input.is_empty() {
-                name_map.insert(input, (resolved, name, note));
-            }
+        if !source.is_file() {
+            return Err(format!("Bundle not found: {path}"));
         }
 
-        for (index, identifier, _) in entries {
-            if let Some((resolved, name, note)) = name_map.get(&identifier) {
-                if *resolved {
-                    if let Some(name) = name {
-                        nodes[index].display_name = Some(name.clone());
-                        nodes[index].name_status = Some("resolved".into());
-                    } else {
-                        nodes[index].name_status = Some("resolved".into());
+        // Extract archives into a scratch directory under ~/.clawpal, never into ~/.openclaw.
+        let paths = resolve_paths();
+        let scratch = paths
+            .clawpal_dir
+            .join("tmp")
+            .join(format!("bundle-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&scratch).map_err(|e| e.to_string())?;
+        let file = fs::File::open(&source).map_err(|e| format!("Failed to open bundle: {e}"))?;
+        let result = tar::Archive::new(flate2::read::GzDecoder::new(file))
+            .unpack(&scratch)
+            .map_err(|e| format!("Failed to extract bundle: {e}"))
+            .and_then(|_| {
+                // Bundles may wrap their content in a single top-level folder.
+                let root = match fs::read_dir(&scratch) {
+                    Ok(entries) => {
+                        let dirs: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+                        if dirs.len() == 1 && dirs[0].is_dir() { dirs[0].clone() } else { scratch.clone() }
                     }
-                } else if let Some(note) = note {
-                    nodes[index].name_status = Some(note.clone());
-                } else {
-                    nodes[index].name_status = Some("unresolved".into());
-                }
-            } else {
-                nodes[index].name_status = Some("unresolved".into());
-            }
-        }
-    }
-
-    let _ = save_json_cache(&cache_file, nodes);
-    Ok(())
+                    Err(_) => scratch.clone(),
+                };
+                read_bundle_dir(&root, &path)
+            });
+        let _ = fs::remove_dir_all(&scratch);
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
-#[derive(Serialize, Deserialize)]
-struct ChannelNameCacheEntry {
-    path: String,
-    display_name: Option<String>,
-    name_status: Option<String>,
+// ---- CLI environment ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathEntryInfo {
+    pub path: String,
+    pub exists: bool,
+    pub has_openclaw: bool,
 }
 
-fn save_json_cache(cache_file: &Path, nodes: &[ChannelNode]) -> Result<(), String> {
-    let payload: Vec<ChannelNameCacheEntry> = nodes
-        .iter()
-        .map(|node| ChannelNameCacheEntry {
-            path: node.path.clone(),
-            display_name: node.display_name.clone(),
-            name_status: node.name_status.clone(),
-        })
-        .collect();
-    write_text(cache_file, &serde_json::to_string_pretty(&payload).map_err(|e| e.to_string())?)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvVarInfo {
+    pub name: String,
+    pub value: String,
+    pub redacted: bool,
 }
 
-fn resolve_channel_node_identity(cfg: &Value, node: &ChannelNode) -> Option<(String, String, String)> {
-    let parts: Vec<&str> = node.path.split('.').collect();
-    if parts.len() < 2 || parts[0] != "channels" {
-        return None;
-    }
-    let plugin = parts[1].to_string();
-    let identifier = channel_last_segment(node.path.as_str())?;
-    let config_node = channel_lookup_node(cfg, &node.path);
-    let kind = if node.channel_type.as_deref() == Some("dm") || node.path.ends_with(".dm") {
-        "user".to_string()
-    } else if config_node
-        .and_then(|value| value.get("users").or(value.get("members")).or_else(|| value.get("peerIds")))
-        .is_some()
-    {
-        "user".to_string()
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliEnvironmentInfo {
+    pub resolved_binary: String,
+    /// Absolute path the child process would execute, if it can be found.
+    pub binary_path: Option<String>,
+    pub path_entries: Vec<PathEntryInfo>,
+    pub env_vars: Vec<EnvVarInfo>,
+}
+
+/// Env vars (besides `OPENCLAW_*`) that change how the node-based CLI resolves.
+const CLI_RELEVANT_ENV_VARS: &[&str] = &["NVM_DIR", "NODE_OPTIONS", "NPM_CONFIG_PREFIX", "FNM_DIR", "VOLTA_HOME"];
+
+fn env_name_looks_secret(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    ["TOKEN", "KEY", "SECRET", "PASSWORD", "PASS", "AUTH", "CREDENTIAL"]
+        .iter()
+        .any(|needle| upper.contains(needle))
+}
+
+/// Show the PATH, relevant env vars and binary that `openclaw` child processes
+/// actually run with (after PATH fixups), for "works in my terminal" issues.
+#[tauri::command]
+pub fn cli_environment_info() -> Result<CliEnvironmentInfo, String> {
+    let resolved_binary = resolve_openclaw_bin().to_string();
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    let path_entries: Vec<PathEntryInfo> = std::env::split_paths(&path_var)
+        .map(|dir| PathEntryInfo {
+            path: dir.to_string_lossy().to_string(),
+            exists: dir.is_dir(),
+            has_openclaw: dir.join("openclaw").is_file(),
+        })
+        .collect();
+
+    let binary_path = if Path::new(&resolved_binary).is_absolute() {
+        Some(resolved_binary.clone())
     } else {
-        "group".to_string()
+        std::env::split_paths(&path_var)
+            .map(|dir| dir.join(&resolved_binary))
+            .find(|candidate| candidate.is_file())
+            .map(|p| p.to_string_lossy().to_string())
     };
-    Some((plugin, identifier, kind))
+
+    let mut env_vars: Vec<EnvVarInfo> = std::env::vars()
+        .filter(|(name, _)| name.starts_with("OPENCLAW_") || CLI_RELEVANT_ENV_VARS.contains(&name.as_str()))
+        .map(|(name, value)| {
+            let redacted = env_name_looks_secret(&name);
+            EnvVarInfo {
+                value: if redacted { mask_api_key(&value) } else { value },
+                name,
+                redacted,
+            }
+        })
+        .collect();
+    env_vars.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(CliEnvironmentInfo {
+        resolved_binary,
+        binary_path,
+        path_entries,
+        env_vars,
+    })
 }
 
-fn channel_last_segment(path: &str) -> Option<String> {
-    path.split('.').next_back().map(|value| value.to_string())
+// ---- Cache freshness ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheFreshness {
+    pub name: String,
+    pub age_seconds: Option<u64>,
+    pub ttl_seconds: Option<u64>,
+    pub expired: bool,
+    pub source: String,
 }
 
-fn channel_node_local_name(cfg: &Value, path: &str) -> Option<String> {
-    channel_lookup_node(cfg, path).and_then(|node| {
-        if let Some(slug) = node.get("slug").and_then(Value::as_str) {
-            let trimmed = slug.trim();
-            if !trimmed.is_empty() {
-                return Some(trimmed.to_string());
+fn file_age_secs(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(SystemTime::now().duration_since(modified).map(|d| d.as_secs()).unwrap_or(0))
+}
+
+fn file_cache_freshness(name: &str, path: &Path, ttl_seconds: Option<u64>) -> CacheFreshness {
+    let age_seconds = file_age_secs(path);
+    let expired = match (age_seconds, ttl_seconds) {
+        (None, _) => true,
+        (Some(age), Some(ttl)) => age >= ttl,
+        (Some(_), None) => false,
+    };
+    CacheFreshness {
+        name: name.to_string(),
+        age_seconds,
+        ttl_seconds,
+        expired,
+        source: if age_seconds.is_some() { "file".into() } else { "missing".into() },
+    }
+}
+
+fn collect_cache_freshness(paths: &crate::models::OpenClawPaths) -> Vec<CacheFreshness> {
+    let now = unix_timestamp_secs();
+    let mut report = Vec::new();
+
+    let update_path = openclaw_update_cache_path(paths);
+    report.push(match read_openclaw_update_cache(&update_path) {
+        Some(cache) => {
+            let age = now.saturating_sub(cache.checked_at);
+            CacheFreshness {
+                name: "openclaw-update".into(),
+                age_seconds: Some(age),
+                ttl_seconds: Some(cache.ttl_seconds),
+                expired: age >= cache.ttl_seconds,
+                source: cache.source,
             }
         }
-        if let Some(name) = node.get("name").and_then(Value::as_str) {
-            let trimmed = name.trim();
-            if !trimmed.is_empty() {
-                return Some(trimmed.to_string());
+        None => file_cache_freshness("openclaw-update", &update_path, Some(OPENCLAW_UPDATE_CACHE_TTL_SECS)),
+    });
+
+    let catalog_path = model_catalog_cache_path(paths);
+    report.push(match read_model_catalog_cache(&catalog_path) {
+        Some(cache) => {
+            let age = now.saturating_sub(cache.updated_at);
+            // The catalog has no TTL: it is reused until the CLI version changes,
+            // and a catalog built by a different version is ignored on read.
+            let version_mismatch = cache.cli_version != resolve_openclaw_version();
+            CacheFreshness {
+                name: "model-catalog".into(),
+                age_seconds: Some(age),
+                ttl_seconds: None,
+                expired: version_mismatch || cache.error.is_some(),
+                source: cache.source,
             }
         }
-        None
-    })
+        None => file_cache_freshness("model-catalog", &catalog_path, None),
+    });
+
+    report.push(file_cache_freshness(
+        "discord-guild-channels",
+        &paths.clawpal_dir.join("discord-guild-channels.json"),
+        None,
+    ));
+    report.push(file_cache_freshness(
+        "telegram-channels",
+        &paths.clawpal_dir.join("telegram-channels.json"),
+        None,
+    ));
+    report.push(file_cache_freshness(
+        "channel-names",
+        &paths.clawpal_dir.join("channel-name-cache.json"),
+        None,
+    ));
+    report
 }
 
-fn channel_lookup_node<'a>(cfg: &'a Value, path: &str) -> Option<&'a Value> {
-    let mut current = cfg;
-    for part in path.split('.') {
-        current = current.get(part)?;
+/// Report age, TTL and origin of every cache ClawPal reads from, so stale
+/// data can be traced to a specific cache.
+#[tauri::command]
+pub fn cache_freshness_report(
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
+) -> Result<Vec<CacheFreshness>, String> {
+    let paths = resolve_paths();
+    let mut report = collect_cache_freshness(&paths);
+    for (key, age) in cache.entries() {
+        report.push(CacheFreshness {
+            name: key,
+            age_seconds: Some(age.as_secs()),
+            ttl_seconds: None,
+            expired: false,
+            source: "memory".into(),
+        });
     }
-    Some(current)
+    Ok(report)
 }
 
-fn is_channel_like_node(prefix: &str, obj: &serde_json::Map<String, Value>) -> bool {
-    if prefix == "channels" {
-        return false;
-    }
-    if obj.contains_key("model")
-        || obj.contains_key("type")
-        || obj.contains_key("mode")
-        || obj.contains_key("policy")
-        || obj.contains_key("allowlist")
-        || obj.contains_key("allowFrom")
-        || obj.contains_key("groupAllowFrom")
-        || obj.contains_key("dmPolicy")
-        || obj.contains_key("groupPolicy")
-        || obj.contains_key("guilds")
-        || obj.contains_key("accounts")
-        || obj.contains_key("dm")
-        || obj.contains_key("users")
-        || obj.contains_key("enabled")
-        || obj.contains_key("token")
-        || obj.contains_key("botToken")
-    {
-        return true;
-    }
-    if prefix.contains(".accounts.") || prefix.contains(".guilds.") || prefix.contains(".channels.") {
-        return true;
-    }
-    if prefix.ends_with(".dm") || prefix.ends_with(".default") {
-        return true;
-    }
-    false
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactoryRefreshSummary {
+    /// Cache files that existed and were removed.
+    pub cleared: Vec<String>,
+    pub memory_entries_cleared: usize,
+    /// Fetches re-run after clearing, with an error message if one failed.
+    pub refreshed: Vec<(String, Option<String>)>,
 }
 
-fn resolve_channel_type(prefix: &str, obj: &serde_json::Map<String, Value>) -> Option<String> {
-    obj.get("type")
-        .and_then(Value::as_str)
-        .map(str::to_string)
-        .or_else(|| {
-            if prefix.ends_with(".dm") {
-                Some("dm".into())
-            } else if prefix.contains(".accounts.") {
-                Some("account".into())
-            } else if prefix.contains(".channels.") && prefix.contains(".guilds.") {
-                Some("channel".into())
-            } else if prefix.contains(".guilds.") {
-                Some("guild".into())
-            } else if obj.contains_key("guilds") {
-                Some("platform".into())
-            } else if obj.contains_key("accounts") {
-                Some("platform".into())
-            } else {
-                None
-            }
-        })
+/// Drop every ClawPal cache (never config, profiles or history) and re-run
+/// the catalog and update fetches so nothing stale survives.
+#[tauri::command]
+pub async fn factory_refresh(
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
+) -> Result<FactoryRefreshSummary, String> {
+    let paths = resolve_paths();
+    let mut cleared = Vec::new();
+    for file in [
+        model_catalog_cache_path(&paths),
+        openclaw_update_cache_path(&paths),
+        paths.clawpal_dir.join("channel-name-cache.json"),
+        paths.clawpal_dir.join("discord-guild-channels.json"),
+        paths.clawpal_dir.join("telegram-channels.json"),
+    ] {
+        if file.exists() {
+            fs::remove_file(&file).map_err(|e| format!("Failed to remove {}: {e}", file.display()))?;
+            cleared.push(file.file_name().unwrap_or_default().to_string_lossy().to_string());
+        }
+    }
+    let memory_entries_cleared = cache.entries().len();
+    cache.invalidate_all();
+    clear_openclaw_version_cache();
+
+    let refreshed = tauri::async_runtime::spawn_blocking(|| {
+        vec![
+            ("model-catalog".to_string(), refresh_model_catalog().err()),
+            ("openclaw-update".to_string(), check_openclaw_update().err()),
+        ]
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(FactoryRefreshSummary {
+        cleared,
+        memory_entries_cleared,
+        refreshed,
+    })
 }
 
-fn resolve_channel_mode(obj: &serde_json::Map<String, Value>) -> Option<String> {
-    let mut modes: Vec<String> = Vec::new();
-    if let Some(v) = obj.get("mode").and_then(Value::as_str) {
-        modes.push(v.to_string());
-    }
-    if let Some(v) = obj.get("policy").and_then(Value::as_str) {
-        if !modes.iter().any(|m| m == v) {
-            modes.push(v.to_string());
-        }
-    }
-    if let Some(v) = obj.get("dmPolicy").and_then(Value::as_str) {
-        if !modes.iter().any(|m| m == v) {
-            modes.push(v.to_string());
-        }
-    }
-    if let Some(v) = obj.get("groupPolicy").and_then(Value::as_str) {
-        if !modes.iter().any(|m| m == v) {
-            modes.push(v.to_string());
-        }
-    }
-    if modes.is_empty() {
-        None
-    } else {
-        Some(modes.join(" / "))
+// ---- Gateway uptime ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayUptime {
+    pub running: bool,
+    pub pid: Option<u32>,
+    pub uptime_seconds: Option<u64>,
+    pub started_at: Option<String>,
+}
+
+/// Parse `ps -o etime=` output (`[[dd-]hh:]mm:ss`) into seconds.
+fn parse_ps_etime(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+    let (days, clock) = match raw.split_once('-') {
+        Some((d, rest)) => (d.parse::<u64>().ok()?, rest),
+        None => (0, raw),
+    };
+    let mut secs = 0u64;
+    for part in clock.split(':') {
+        secs = secs * 60 + part.parse::<u64>().ok()?;
     }
+    Some(days * 86_400 + secs)
 }
 
-fn collect_channel_allowlist(obj: &serde_json::Map<String, Value>) -> Vec<String> {
-    let mut out: Vec<String> = Vec::new();
-    let mut uniq = HashSet::<String>::new();
-    for key in ["allowlist", "allowFrom", "groupAllowFrom"] {
-        if let Some(values) = obj.get(key).and_then(Value::as_array) {
-            for value in values.iter().filter_map(Value::as_str) {
-                let next = value.to_string();
-                if uniq.insert(next.clone()) {
-                    out.push(next);
-                }
-            }
-        }
-    }
-    if let Some(values) = obj.get("users").and_then(Value::as_array) {
-        for value in values.iter().filter_map(Value::as_str) {
-            let next = value.to_string();
-            if uniq.insert(next.clone()) {
-                out.push(next);
-            }
-        }
+fn build_gateway_uptime(pid: Option<u32>, uptime_seconds: Option<u64>) -> GatewayUptime {
+    let started_at = uptime_seconds
+        .map(|up| format_timestamp_from_unix(unix_timestamp_secs().saturating_sub(up)));
+    GatewayUptime {
+        running: pid.is_some(),
+        pid,
+        uptime_seconds,
+        started_at,
     }
-    out
 }
 
-fn collect_agent_ids(cfg: &Value) -> Vec<String> {
-    let mut ids = Vec::new();
-    if let Some(agents) = cfg.get("agents").and_then(|v| v.get("list")).and_then(Value::as_array) {
-        for agent in agents {
-            if let Some(id) = agent.get("id").and_then(Value::as_str) {
-                ids.push(id.to_string());
-            }
-        }
+#[tauri::command]
+pub async fn gateway_uptime() -> Result<GatewayUptime, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let Some(pid) = crate::doctor::gateway_pid() else {
+            return Ok(build_gateway_uptime(None, None));
+        };
+        let output = std::process::Command::new("ps")
+            .args(["-o", "etime=", "-p", &pid.to_string()])
+            .output()
+            .map_err(|e| e.to_string())?;
+        let uptime = parse_ps_etime(&String::from_utf8_lossy(&output.stdout));
+        Ok(build_gateway_uptime(Some(pid), uptime))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn remote_gateway_uptime(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+) -> Result<GatewayUptime, String> {
+    // etime rather than etimes: BSD and busybox ps lack the latter.
+    let cmd = "pid=$(pgrep -f '[o]penclaw-gateway' | head -n1); \
+               [ -n \"$pid\" ] && echo \"$pid $(ps -o etime= -p \"$pid\")\" || true";
+    let result = pool.exec(&host_id, cmd).await?;
+    let line = result.stdout.trim();
+    let Some((pid, etime)) = line.split_once(char::is_whitespace) else {
+        return Ok(build_gateway_uptime(None, None));
+    };
+    let pid = pid.parse::<u32>().ok();
+    Ok(build_gateway_uptime(pid, pid.and_then(|_| parse_ps_etime(etime))))
+}
+
+// ---- Openclaw processes ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenclawProcess {
+    pub pid: u32,
+    pub command: String,
+    pub port: Option<u16>,
+}
+
+/// Whether a command line runs openclaw itself: the `openclaw` binary (or an
+/// `openclaw-*` process title), or a JS runtime executing the openclaw
+/// entrypoint. Editors, pagers and shells that merely mention an openclaw
+/// path do not count.
+fn is_openclaw_command(args: &str) -> bool {
+    let basename = |token: &str| token.rsplit(['/', '\\']).next().unwrap_or(token).to_string();
+    let is_openclaw_name = |name: &str| name == "openclaw" || name.starts_with("openclaw-");
+    let mut tokens = args.split_whitespace();
+    let Some(program) = tokens.next().map(basename) else {
+        return false;
+    };
+    if is_openclaw_name(&program) {
+        return true;
     }
-    // Implicit "main" agent when no agents.list
-    if ids.is_empty() {
-        ids.push("main".into());
+    if !matches!(program.as_str(), "node" | "nodejs" | "bun") {
+        return false;
     }
-    ids
+    let Some(script) = tokens.find(|t| !t.starts_with('-')) else {
+        return false;
+    };
+    let script_name = basename(script);
+    is_openclaw_name(&script_name)
+        || script_name == "openclaw.mjs"
+        || script_name == "openclaw.js"
+        || script.contains("/node_modules/openclaw/")
 }
 
-fn collect_model_bindings(cfg: &Value, profiles: &[ModelProfile]) -> Vec<ModelBinding> {
+/// Parse `ps -eo pid=,args=` output, keeping openclaw processes only.
+fn parse_openclaw_processes(ps_output: &str) -> Vec<OpenclawProcess> {
     let mut out = Vec::new();
-    let global = cfg
-        .pointer("/agents/defaults/model")
-        .or_else(|| cfg.pointer("/agents/default/model"))
-        .and_then(read_model_value);
-    out.push(ModelBinding {
-        scope: "global".into(),
-        scope_id: "global".into(),
-        model_profile_id: find_profile_by_model(profiles, global.as_deref()),
-        model_value: global,
-        path: Some("agents.defaults.model".into()),
-    });
-
-    if let Some(agents) = cfg.get("agents").and_then(|v| v.get("list")).and_then(Value::as_array) {
-        for agent in agents {
-            let id = agent.get("id").and_then(Value::as_str).unwrap_or("agent");
-            let model = agent.get("model").and_then(read_model_value);
-            out.push(ModelBinding {
-                scope: "agent".into(),
-                scope_id: id.to_string(),
-                model_profile_id: find_profile_by_model(profiles, model.as_deref()),
-                model_value: model,
-                path: Some(format!("agents.list.{id}.model")),
-            });
+    for line in ps_output.lines() {
+        let line = line.trim();
+        let Some((pid, args)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+        let Ok(pid) = pid.parse::<u32>() else {
+            continue;
+        };
+        let args = args.trim();
+        if !is_openclaw_command(args) {
+            continue;
         }
+        let tokens: Vec<&str> = args.split_whitespace().collect();
+        let port = tokens.iter().enumerate().find_map(|(i, t)| {
+            t.strip_prefix("--port=")
+                .or_else(|| (*t == "--port").then(|| tokens.get(i + 1).copied()).flatten())
+                .and_then(|v| v.parse().ok())
+        });
+        out.push(OpenclawProcess {
+            pid,
+            command: args.to_string(),
+            port,
+        });
     }
+    out
+}
 
-    fn walk_channel_binding(prefix: &str, node: &Value, out: &mut Vec<ModelBinding>, profiles: &[ModelProfile]) {
-        if let Some(obj) = node.as_object() {
-            if let Some(model) = obj.get("model").and_then(read_model_value) {
-                out.push(ModelBinding {
-                    scope: "channel".into(),
-                    scope_id: prefix.to_string(),
-                    model_profile_id: find_profile_by_model(profiles, Some(&model)),
-                    model_value: Some(model),
-                    path: Some(format!("{}.model", prefix)),
-                });
-            }
-            for (k, child) in obj {
-                if let Value::Object(_) = child {
-                    walk_channel_binding(&format!("{}.{}", prefix, k), child, out, profiles);
-                }
-            }
+fn local_listening_port(pid: u32) -> Option<u16> {
+    let output = std::process::Command::new("lsof")
+        .args(["-Pan", "-p", &pid.to_string(), "-iTCP", "-sTCP:LISTEN", "-Fn"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix('n'))
+        .find_map(|addr| addr.rsplit(':').next()?.parse().ok())
+}
+
+#[tauri::command]
+pub fn list_openclaw_processes() -> Result<Vec<OpenclawProcess>, String> {
+    let output = std::process::Command::new("ps")
+        .args(["-eo", "pid=,args="])
+        .output()
+        .map_err(|e| format!("Failed to list processes: {e}"))?;
+    let own_pid = std::process::id();
+    let mut processes: Vec<OpenclawProcess> = parse_openclaw_processes(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|p| p.pid != own_pid)
+        .collect();
+    for process in &mut processes {
+        if process.port.is_none() {
+            process.port = local_listening_port(process.pid);
         }
     }
+    Ok(processes)
+}
 
-    if let Some(channels) = cfg.get("channels") {
-        walk_channel_binding("channels", channels, &mut out, profiles);
-    }
+#[cfg(test)]
+mod openclaw_process_tests {
+    use super::*;
 
-    out
+    #[test]
+    fn only_openclaw_executables_match() {
+        let ps = "\
+  101 /usr/local/bin/openclaw gateway --port 18789
+  102 openclaw-gateway
+  103 node /usr/lib/node_modules/openclaw/dist/index.js gateway
+  104 /usr/bin/node --max-old-space-size=512 /opt/openclaw/openclaw.mjs gateway --port=19000
+  201 tail -f /home/u/.openclaw/logs/gateway.log
+  202 vim /home/u/.openclaw/openclaw.json
+  203 grep openclaw
+  204 ssh host openclaw status
+  205 bash -c openclaw gateway
+  206 node /srv/app/server.js --config /home/u/.openclaw/openclaw.json
+";
+        let pids: Vec<u32> = parse_openclaw_processes(ps).iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![101, 102, 103, 104]);
+        let ports: Vec<Option<u16>> = parse_openclaw_processes(ps).iter().map(|p| p.port).collect();
+        assert_eq!(ports, vec![Some(18789), None, None, Some(19000)]);
+    }
+}
+
+/// Kill an openclaw process. The pid must belong to a process that
+/// `list_openclaw_processes` reports, so arbitrary pids are refused.
+#[tauri::command]
+pub fn kill_openclaw_process(pid: u32, force: Option<bool>) -> Result<bool, String> {
+    if !list_openclaw_processes()?.iter().any(|p| p.pid == pid) {
+        return Err(format!("pid {pid} is not an openclaw process"));
+    }
+    let signal = if force.unwrap_or(false) { "-KILL" } else { "-TERM" };
+    let status = std::process::Command::new("kill")
+        .args([signal, &pid.to_string()])
+        .status()
+        .map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err(format!("kill {signal} {pid} failed"));
+    }
+    crate::logging::log_info(&format!("Killed openclaw process {pid} ({signal})"));
+    Ok(true)
 }
 
-fn find_profile_by_model(profiles: &[ModelProfile], value: Option<&str>) -> Option<String> {
-    let value = value?;
-    let normalized = normalize_model_ref(value);
-    for profile in profiles {
-        if normalize_model_ref(&profile_to_model_value(profile)) == normalized
-            || normalize_model_ref(&profile.model) == normalized
-        {
-            return Some(profile.id.clone());
-        }
+#[tauri::command]
+pub async fn remote_list_openclaw_processes(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+) -> Result<Vec<OpenclawProcess>, String> {
+    let result = pool.exec(&host_id, "ps -eo pid=,args=").await?;
+    if result.exit_code != 0 {
+        return Err(format!("Failed to list processes: {}", result.stderr.trim()));
     }
-    None
+    Ok(parse_openclaw_processes(&result.stdout))
 }
 
-fn resolve_auth_ref_for_provider(cfg: &Value, provider: &str) -> Option<String> {
-    let provider = provider.trim().to_lowercase();
-    if provider.is_empty() {
-        return None;
+#[tauri::command]
+pub async fn remote_kill_openclaw_process(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    pid: u32,
+    force: Option<bool>,
+) -> Result<bool, String> {
+    let listing = pool.exec(&host_id, "ps -eo pid=,args=").await?;
+    if !parse_openclaw_processes(&listing.stdout).iter().any(|p| p.pid == pid) {
+        return Err(format!("pid {pid} is not an openclaw process"));
     }
-    if let Some(auth_profiles) = cfg.pointer("/auth/profiles").and_then(Value::as_object) {
-        let mut fallback = None;
-        for (profile_id, profile) in auth_profiles {
-            let entry_provider = profile.get("provider").or_else(|| profile.get("name"));
-            if let Some(entry_provider) = entry_provider.and_then(Value::as_str) {
-                if entry_provider.trim().eq_ignore_ascii_case(&provider) {
-                    if profile_id.ends_with(":default") {
-                        return Some(profile_id.clone());
-                    }
-                    if fallback.is_none() {
-                        fallback = Some(profile_id.clone());
-                    }
-                }
-            }
-        }
-        if fallback.is_some() {
-            return fallback;
-        }
+    let signal = if force.unwrap_or(false) { "-KILL" } else { "-TERM" };
+    let result = pool.exec(&host_id, &format!("kill {signal} {pid}")).await?;
+    if result.exit_code != 0 {
+        return Err(format!("kill {signal} {pid} failed: {}", result.stderr.trim()));
     }
-    None
+    Ok(true)
 }
 
-#[tauri::command]
-pub fn read_raw_config() -> Result<String, String> {
-    let paths = resolve_paths();
-    let cfg = read_openclaw_config(&paths)?;
-    serde_json::to_string_pretty(&cfg).map_err(|e| e.to_string())
+// ---- Storage breakdown ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageCategory {
+    pub category: String,
+    pub bytes: u64,
+    pub percent: f64,
 }
 
-// resolve_full_api_key is intentionally not exposed as a Tauri command.
-// It returns raw API keys which should never be sent to the frontend.
-#[allow(dead_code)]
-fn resolve_full_api_key(profile_id: String) -> Result<String, String> {
-    let paths = resolve_paths();
-    let profiles = load_model_profiles(&paths);
-    let profile = profiles.iter().find(|p| p.id == profile_id)
-        .ok_or_else(|| "Profile not found".to_string())?;
-    let key = resolve_profile_api_key(profile, &paths.base_dir);
-    if key.is_empty() {
-        return Err("No API key configured for this profile".to_string());
-    }
-    Ok(key)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageBreakdown {
+    pub total_bytes: u64,
+    pub categories: Vec<StorageCategory>,
 }
 
-#[tauri::command]
-pub fn open_url(url: String) -> Result<(), String> {
-    let trimmed = url.trim();
-    if trimmed.is_empty() {
-        return Err("URL is required".into());
+fn build_storage_breakdown(sizes: Vec<(&str, u64)>) -> StorageBreakdown {
+    let total_bytes: u64 = sizes.iter().map(|(_, bytes)| *bytes).sum();
+    let mut categories: Vec<StorageCategory> = sizes
+        .into_iter()
+        .map(|(category, bytes)| StorageCategory {
+            category: category.to_string(),
+            bytes,
+            percent: if total_bytes == 0 {
+                0.0
+            } else {
+                (bytes as f64 / total_bytes as f64 * 1000.0).round() / 10.0
+            },
+        })
+        .collect();
+    categories.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+    StorageBreakdown {
+        total_bytes,
+        categories,
     }
-    // Allow http(s) URLs and local paths within user home directory
-    if !trimmed.starts_with("http://") && !trimmed.starts_with("https://") {
-        // For local paths, ensure they don't execute apps
-        let path = std::path::Path::new(trimmed);
-        if path.extension().map_or(false, |ext| ext == "app" || ext == "exe") {
-            return Err("Cannot open application files".into());
+}
+
+/// Workspace directories referenced by the config plus the default
+/// `workspaces/` folder, with nested entries dropped to avoid double counting.
+fn collect_workspace_dirs(cfg: &Value, base_dir: &Path) -> Vec<PathBuf> {
+    let mut candidates = vec![base_dir.join("workspaces")];
+    for pointer in ["/agents/defaults/workspace", "/agents/default/workspace"] {
+        if let Some(ws) = cfg.pointer(pointer).and_then(Value::as_str) {
+            candidates.push(PathBuf::from(expand_tilde(ws)));
         }
     }
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open").arg(&url).spawn().map_err(|e| e.to_string())?;
-    }
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open").arg(&url).spawn().map_err(|e| e.to_string())?;
+    if let Some(list) = cfg.pointer("/agents/list").and_then(Value::as_array) {
+        for agent in list {
+            if let Some(ws) = agent.get("workspace").and_then(Value::as_str) {
+                candidates.push(PathBuf::from(expand_tilde(ws)));
+            }
+        }
     }
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("cmd").args(["/c", "start", &url]).spawn().map_err(|e| e.to_string())?;
+    candidates.sort();
+    candidates.dedup();
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for candidate in candidates {
+        if !dirs.iter().any(|d| candidate.starts_with(d)) {
+            dirs.push(candidate);
+        }
     }
-    Ok(())
+    dirs
 }
 
 #[tauri::command]
-pub async fn chat_via_openclaw(agent_id: String, message: String, session_id: Option<String>) -> Result<Value, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        let mut args = vec![
-            "agent".to_string(),
-            "--local".to_string(),
-            "--agent".to_string(),
-            agent_id,
-            "--message".to_string(),
-            message,
-            "--json".to_string(),
-            "--no-color".to_string(),
-        ];
-        if let Some(sid) = session_id {
-            args.push("--session-id".to_string());
-            args.push(sid);
+pub fn storage_breakdown() -> Result<StorageBreakdown, String> {
+    let paths = resolve_paths();
+    let cfg = read_openclaw_config(&paths).unwrap_or(Value::Null);
+
+    let sessions_total = collect_session_overview(&paths.base_dir).total_bytes;
+    let mut archive_bytes = 0u64;
+    if let Ok(entries) = fs::read_dir(paths.base_dir.join("agents")) {
+        for entry in entries.flatten() {
+            archive_bytes = archive_bytes
+                .saturating_add(collect_file_inventory_with_limit(&entry.path().join("sessions_archive")).total_bytes);
         }
+    }
+    let memory_bytes = collect_memory_overview(&paths.base_dir).total_bytes;
+    let backups_bytes = dir_size(&paths.clawpal_dir.join("backups"));
+    let snapshots_bytes = dir_size(&paths.history_dir);
+    let workspaces_bytes: u64 = collect_workspace_dirs(&cfg, &paths.base_dir)
+        .iter()
+        .map(|dir| dir_size(dir))
+        .sum();
 
-        let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        let output = run_openclaw_raw(&arg_refs)?;
-        let json_str = extract_json_from_output(&output.stdout)
-            .ok_or_else(|| format!("No JSON in openclaw output: {}", output.stdout))?;
-        serde_json::from_str(json_str)
-            .map_err(|e| format!("Parse openclaw response failed: {}", e))
-    })
-    .await
-    .map_err(|e| format!("Task join failed: {}", e))?
+    Ok(build_storage_breakdown(vec![
+        ("sessions", sessions_total.saturating_sub(archive_bytes)),
+        ("archives", archive_bytes),
+        ("memory", memory_bytes),
+        ("backups", backups_bytes),
+        ("snapshots", snapshots_bytes),
+        ("workspaces", workspaces_bytes),
+    ]))
 }
 
 #[tauri::command]
-pub async fn remote_chat_via_openclaw(
+pub async fn remote_storage_breakdown(
     pool: State<'_, SshConnectionPool>,
     host_id: String,
-    agent_id: String,
-    message: String,
-    session_id: Option<String>,
-) -> Result<Value, String> {
-    let escaped_msg = message.replace('\'', "'\\''");
-    let escaped_agent = agent_id.replace('\'', "'\\''");
-    let mut cmd = format!(
-        "openclaw agent --local --agent '{}' --message '{}' --json --no-color",
-        escaped_agent, escaped_msg
+) -> Result<StorageBreakdown, String> {
+    // One `du` per category; globs that match nothing report 0.
+    let script = concat!(
+        "sz() { name=$1; shift; kb=0; for p in \"$@\"; do ",
+            "if [ -e \"$p\" ]; then k=$(du -sk \"$p\" 2>/dev/null | cut -f1); kb=$((kb + ${k:-0})); fi; ",
+        "done; printf '%s\\t%s\\n' \"$name\" \"$kb\"; }; ",
+        "sz sessions \"$HOME\"/.openclaw/agents/*/sessions; ",
+        "sz archives \"$HOME\"/.openclaw/agents/*/sessions_archive; ",
+        "sz memory \"$HOME/.openclaw/memory\"; ",
+        "sz backups \"$HOME/.clawpal/backups\"; ",
+        "sz snapshots \"$HOME/.clawpal/snapshots\"; ",
+        "sz workspaces \"$HOME/.openclaw/workspaces\""
     );
-    if let Some(sid) = session_id {
-        let escaped_sid = sid.replace('\'', "'\\''");
-        cmd.push_str(&format!(" --session-id '{}'", escaped_sid));
-    }
-    let result = pool.exec_login(&host_id, &cmd).await?;
+    let result = pool.exec(&host_id, script).await?;
     if result.exit_code != 0 {
-        return Err(format!(
-            "Remote chat failed (exit {}): {}",
-            result.exit_code, result.stderr
-        ));
+        return Err(format!("Failed to measure storage: {}", result.stderr.trim()));
     }
-    let json_str = extract_json_from_output(&result.stdout)
-        .ok_or_else(|| format!("No JSON in remote openclaw output: {}", result.stdout))?;
-    serde_json::from_str(json_str)
-        .map_err(|e| format!("Failed to parse remote chat response: {e}"))
+    let mut sizes = Vec::new();
+    for category in ["sessions", "archives", "memory", "backups", "snapshots", "workspaces"] {
+        let kb = result
+            .stdout
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .find(|(name, _)| *name == category)
+            .and_then(|(_, kb)| kb.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+        sizes.push((category, kb * 1024));
+    }
+    Ok(build_storage_breakdown(sizes))
 }
 
 // ---- Backup / Restore ----
@@ -3789,10 +9089,163 @@ pub struct BackupInfo {
     pub path: String,
     pub created_at: String,
     pub size_bytes: u64,
+    /// Older backups removed to stay within `max_backups` after this one was
+    /// created.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pruned: Vec<String>,
+}
+
+/// Backups kept by `backup_before_upgrade` when the caller gives no limit.
+const DEFAULT_MAX_BACKUPS: usize = 10;
+
+const BACKUP_ARCHIVE_EXT: &str = ".tar.gz";
+
+/// On-disk location of a backup: a directory, or a `<name>.tar.gz` archive.
+fn backup_location(backups_dir: &Path, name: &str) -> Option<PathBuf> {
+    let dir = backups_dir.join(name);
+    if dir.is_dir() {
+        return Some(dir);
+    }
+    let archive = backups_dir.join(format!("{name}{BACKUP_ARCHIVE_EXT}"));
+    archive.is_file().then_some(archive)
+}
+
+/// Remove a backup regardless of its format.
+fn remove_backup_at(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// Mirror of `copy_dir_recursive` that appends into a tar archive instead.
+fn append_dir_to_tar<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    src: &Path,
+    prefix: &Path,
+    skip_dirs: &HashSet<&str>,
+) -> Result<(), String> {
+    let entries = fs::read_dir(src).map_err(|e| format!("Failed to read dir {}: {e}", src.display()))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
+        if name_str == "openclaw.json" {
+            continue;
+        }
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        let dest = prefix.join(&name);
+        if file_type.is_dir() {
+            if skip_dirs.contains(name_str.as_ref()) {
+                continue;
+            }
+            append_dir_to_tar(builder, &entry.path(), &dest, skip_dirs)?;
+        } else if file_type.is_file() {
+            builder
+                .append_path_with_name(entry.path(), &dest)
+                .map_err(|e| format!("Failed to archive {}: {e}", name_str))?;
+        }
+    }
+    Ok(())
+}
+
+fn write_backup_archive(
+    paths: &crate::models::OpenClawPaths,
+    archive_path: &Path,
+    skip_dirs: &HashSet<&str>,
+) -> Result<(), String> {
+    let file = fs::File::create(archive_path).map_err(|e| format!("Failed to create archive: {e}"))?;
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    if paths.config_path.exists() {
+        builder
+            .append_path_with_name(&paths.config_path, "openclaw.json")
+            .map_err(|e| format!("Failed to archive config: {e}"))?;
+    }
+    append_dir_to_tar(&mut builder, &paths.base_dir, Path::new(""), skip_dirs)?;
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| format!("Failed to finish archive: {e}"))?;
+    Ok(())
+}
+
+/// Names of local backups, directories and archives alike.
+fn local_backup_names(backups_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(backups_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| {
+            let file_name = e.file_name().to_string_lossy().to_string();
+            if file_name.starts_with('.') {
+                None
+            } else if e.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                Some(file_name)
+            } else {
+                file_name.strip_suffix(BACKUP_ARCHIVE_EXT).map(str::to_string)
+            }
+        })
+        .collect()
+}
+
+/// Backups past the newest `max`, oldest last. Only names in the backup
+/// timestamp format (`%Y-%m-%d_%H%M%S`) take part, so they sort
+/// chronologically and anything else in the directory is left alone. A `max`
+/// of 0 keeps everything.
+fn backups_beyond_limit(names: &[String], max: usize) -> Vec<String> {
+    if max == 0 {
+        return Vec::new();
+    }
+    let mut sorted: Vec<&String> = names
+        .iter()
+        .filter(|name| chrono::NaiveDateTime::parse_from_str(name, "%Y-%m-%d_%H%M%S").is_ok())
+        .collect();
+    sorted.sort_by(|a, b| b.cmp(a));
+    sorted.dedup();
+    sorted.into_iter().skip(max).cloned().collect()
+}
+
+#[cfg(test)]
+mod backup_rotation_tests {
+    use super::*;
+
+    #[test]
+    fn rotation_keeps_newest_backups() {
+        let names: Vec<String> = ["2024-01-03_000000", "2024-01-01_000000", "notes", "2024-01-02_000000"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(backups_beyond_limit(&names, 2), vec!["2024-01-01_000000".to_string()]);
+        assert!(backups_beyond_limit(&names, 3).is_empty());
+        assert!(!backups_beyond_limit(&names, 1).contains(&"notes".to_string()));
+        assert!(backups_beyond_limit(&names, 0).is_empty());
+    }
+}
+
+/// Delete local backups beyond `max`, returning the removed names.
+fn rotate_local_backups(backups_dir: &Path, max: usize) -> Result<Vec<String>, String> {
+    let mut pruned = Vec::new();
+    for name in backups_beyond_limit(&local_backup_names(backups_dir), max) {
+        if let Some(location) = backup_location(backups_dir, &name) {
+            remove_backup_at(&location).map_err(|e| format!("Failed to delete backup {name}: {e}"))?;
+            pruned.push(name);
+        }
+    }
+    Ok(pruned)
 }
 
+/// Back up config and agent data. `format` is `dir` (default, a plain copy)
+/// or `targz` (a single compressed archive). Afterwards only the newest
+/// `max_backups` (default 10, 0 for no limit) are kept.
 #[tauri::command]
-pub fn backup_before_upgrade() -> Result<BackupInfo, String> {
+pub fn backup_before_upgrade(format: Option<String>, max_backups: Option<usize>) -> Result<BackupInfo, String> {
+    let format = format.unwrap_or_else(|| "dir".into());
+    if format != "dir" && format != "targz" {
+        return Err(format!("unknown backup format: {format} (expected dir or targz)"));
+    }
     let paths = resolve_paths();
     let backups_dir = paths.clawpal_dir.join("backups");
     fs::create_dir_all(&backups_dir).map_err(|e| format!("Failed to create backups dir: {e}"))?;
@@ -3802,6 +9255,24 @@ pub fn backup_before_upgrade() -> Result<BackupInfo, String> {
     let name = now_dt
         .map(|dt| dt.format("%Y-%m-%d_%H%M%S").to_string())
         .unwrap_or_else(|| format!("{now_secs}"));
+    let skip_dirs: HashSet<&str> = ["sessions", "archive", ".clawpal"].iter().copied().collect();
+
+    if format == "targz" {
+        let archive_path = backups_dir.join(format!("{name}{BACKUP_ARCHIVE_EXT}"));
+        if let Err(e) = write_backup_archive(&paths, &archive_path, &skip_dirs) {
+            let _ = fs::remove_file(&archive_path);
+            return Err(e);
+        }
+        let pruned = rotate_local_backups(&backups_dir, max_backups.unwrap_or(DEFAULT_MAX_BACKUPS))?;
+        return Ok(BackupInfo {
+            name,
+            path: archive_path.to_string_lossy().to_string(),
+            created_at: format_timestamp_from_unix(now_secs),
+            size_bytes: fs::metadata(&archive_path).map(|m| m.len()).unwrap_or(0),
+            pruned,
+        });
+    }
+
     let backup_dir = backups_dir.join(&name);
     fs::create_dir_all(&backup_dir).map_err(|e| format!("Failed to create backup dir: {e}"))?;
 
@@ -3815,14 +9286,15 @@ pub fn backup_before_upgrade() -> Result<BackupInfo, String> {
     }
 
     // Copy directories, excluding sessions and archive
-    let skip_dirs: HashSet<&str> = ["sessions", "archive", ".clawpal"].iter().copied().collect();
     copy_dir_recursive(&paths.base_dir, &backup_dir, &skip_dirs, &mut total_bytes)?;
+    let pruned = rotate_local_backups(&backups_dir, max_backups.unwrap_or(DEFAULT_MAX_BACKUPS))?;
 
     Ok(BackupInfo {
         name: name.clone(),
         path: backup_dir.to_string_lossy().to_string(),
         created_at: format_timestamp_from_unix(now_secs),
         size_bytes: total_bytes,
+        pruned,
     })
 }
 
@@ -3866,12 +9338,19 @@ pub fn list_backups() -> Result<Vec<BackupInfo>, String> {
     let entries = fs::read_dir(&backups_dir).map_err(|e| e.to_string())?;
     for entry in entries {
         let entry = entry.map_err(|e| e.to_string())?;
-        if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
-            continue;
-        }
-        let name = entry.file_name().to_string_lossy().to_string();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        let name = match file_name.strip_suffix(BACKUP_ARCHIVE_EXT) {
+            Some(stem) if !is_dir => stem.to_string(),
+            _ if is_dir && !file_name.starts_with('.') => file_name,
+            _ => continue,
+        };
         let path = entry.path();
-        let size = dir_size(&path);
+        let size = if is_dir {
+            dir_size(&path)
+        } else {
+            fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+        };
         let created_at = fs::metadata(&path)
             .and_then(|m| m.created())
             .map(|t| {
@@ -3884,10 +9363,101 @@ pub fn list_backups() -> Result<Vec<BackupInfo>, String> {
             path: path.to_string_lossy().to_string(),
             created_at,
             size_bytes: size,
+            pruned: Vec::new(),
+        });
+    }
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(backups)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestorePoint {
+    /// "snapshot" (restore with `rollback`) or "backup" (`restore_from_backup`).
+    pub kind: String,
+    pub id: String,
+    pub created_at: String,
+    pub timestamp: i64,
+    pub label: String,
+    pub can_restore: bool,
+}
+
+/// Accepts RFC 3339, snapshot times (`%Y-%m-%dT%H-%M-%S`, see history.rs)
+/// and backup directory names (`%Y-%m-%d_%H%M%S`).
+fn parse_restore_point_time(raw: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(raw)
+        .map(|dt| dt.timestamp())
+        .ok()
+        .or_else(|| {
+            ["%Y-%m-%dT%H-%M-%S", "%Y-%m-%d_%H%M%S"].iter().find_map(|fmt| {
+                chrono::NaiveDateTime::parse_from_str(raw, fmt)
+                    .ok()
+                    .map(|dt| dt.and_utc().timestamp())
+            })
+        })
+}
+
+/// Snapshots and backups merged into one newest-first recovery timeline.
+#[tauri::command]
+pub fn list_restore_points() -> Result<Vec<RestorePoint>, String> {
+    let paths = resolve_paths();
+    let mut points = Vec::new();
+    for snap in list_snapshots(&paths.metadata_path)?.items {
+        let timestamp = parse_restore_point_time(&snap.created_at).unwrap_or(0);
+        let label = match &snap.recipe_id {
+            Some(recipe) if recipe != &snap.source => format!("{} ({recipe})", snap.source),
+            _ => snap.source.clone(),
+        };
+        points.push(RestorePoint {
+            kind: "snapshot".into(),
+            id: snap.id,
+            created_at: format_timestamp_from_unix(timestamp.max(0) as u64),
+            timestamp,
+            label,
+            can_restore: snap.can_rollback,
+        });
+    }
+    for backup in list_backups()? {
+        let timestamp = parse_restore_point_time(&backup.name)
+            .or_else(|| parse_restore_point_time(&backup.created_at))
+            .unwrap_or(0);
+        points.push(RestorePoint {
+            kind: "backup".into(),
+            id: backup.name,
+            created_at: format_timestamp_from_unix(timestamp.max(0) as u64),
+            timestamp,
+            label: "backup".into(),
+            can_restore: true,
         });
     }
-    backups.sort_by(|a, b| b.name.cmp(&a.name));
-    Ok(backups)
+    points.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(points)
+}
+
+#[cfg(test)]
+mod restore_point_tests {
+    use super::*;
+
+    #[test]
+    fn restore_point_time_parses_snapshot_times() {
+        let dir = std::env::temp_dir().join(format!("clawpal-restore-points-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let snap = add_snapshot(
+            &dir.join("history"),
+            &dir.join("metadata.json"),
+            Some("manual".into()),
+            "manual",
+            true,
+            "{}",
+            None,
+        )
+        .unwrap();
+        let parsed = parse_restore_point_time(&snap.created_at).unwrap();
+        assert!((parsed - chrono::Utc::now().timestamp()).abs() < 60);
+        assert_eq!(parse_restore_point_time("2024-01-02_030405"), Some(1704164645));
+        assert!(parse_restore_point_time("latest").is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
 
 fn dir_size(path: &Path) -> u64 {
@@ -3904,29 +9474,111 @@ fn dir_size(path: &Path) -> u64 {
     total
 }
 
+/// Validate restore components. Returns `None` for "everything" (empty list),
+/// otherwise whether the config is included plus the top-level dirs to walk.
+fn parse_restore_components(components: &[String]) -> Result<Option<(bool, HashSet<String>)>, String> {
+    if components.is_empty() {
+        return Ok(None);
+    }
+    let mut include_config = false;
+    let mut dirs = HashSet::new();
+    for component in components {
+        let component = component.trim();
+        if component.is_empty()
+            || component.starts_with('.')
+            || component.contains('/')
+            || component.contains('\\')
+        {
+            return Err(format!("invalid restore component: {component}"));
+        }
+        if component == "config" {
+            include_config = true;
+        } else {
+            dirs.insert(component.to_string());
+        }
+    }
+    Ok(Some((include_config, dirs)))
+}
+
+/// Restore a backup. `components` picks what to restore: `config` for
+/// openclaw.json, or top-level directory names such as `agents` and
+/// `memory`. An empty list restores everything.
 #[tauri::command]
-pub fn restore_from_backup(backup_name: String) -> Result<String, String> {
+pub fn restore_from_backup(backup_name: String, components: Vec<String>) -> Result<String, String> {
+    let selection = parse_restore_components(&components)?;
     let paths = resolve_paths();
-    let backup_dir = paths.clawpal_dir.join("backups").join(&backup_name);
-    if !backup_dir.exists() {
-        return Err(format!("Backup '{}' not found", backup_name));
+    let backups_dir = paths.clawpal_dir.join("backups");
+    let location = backup_location(&backups_dir, &backup_name)
+        .ok_or_else(|| format!("Backup '{}' not found", backup_name))?;
+    if location.is_dir() {
+        return restore_backup_dir(&paths, &location, &backup_name, selection.as_ref());
+    }
+
+    // Archives are unpacked into a scratch dir and restored like a directory.
+    let scratch = backups_dir.join(format!(".restore-{backup_name}"));
+    let _ = fs::remove_dir_all(&scratch);
+    let file = fs::File::open(&location).map_err(|e| format!("Failed to open backup: {e}"))?;
+    let unpacked = tar::Archive::new(flate2::read::GzDecoder::new(file))
+        .unpack(&scratch)
+        .map_err(|e| format!("Failed to extract backup: {e}"))
+        .and_then(|_| restore_backup_dir(&paths, &scratch, &backup_name, selection.as_ref()));
+    let _ = fs::remove_dir_all(&scratch);
+    unpacked
+}
+
+#[cfg(test)]
+mod backup_restore_tests {
+    use super::*;
+
+    #[test]
+    fn restore_components_select_config_and_dirs() {
+        assert!(parse_restore_components(&[]).unwrap().is_none());
+        let (config, dirs) = parse_restore_components(&["config".into(), "memory".into()])
+            .unwrap()
+            .unwrap();
+        assert!(config);
+        assert_eq!(dirs.len(), 1);
+        assert!(dirs.contains("memory"));
+        assert!(parse_restore_components(&["../etc".into()]).is_err());
+
+        let script = remote_restore_script("b1", Some(&(true, HashSet::new())));
+        assert!(script.contains("openclaw.json"));
+        assert!(!script.contains("agents"));
     }
+}
 
+fn restore_backup_dir(
+    paths: &crate::models::OpenClawPaths,
+    backup_dir: &Path,
+    backup_name: &str,
+    selection: Option<&(bool, HashSet<String>)>,
+) -> Result<String, String> {
     // Restore config file
     let backup_config = backup_dir.join("openclaw.json");
-    if backup_config.exists() {
+    let restore_config = selection.map(|(config, _)| *config).unwrap_or(true);
+    if restore_config && backup_config.exists() {
         fs::copy(&backup_config, &paths.config_path)
             .map_err(|e| format!("Failed to restore config: {e}"))?;
     }
 
     // Restore other directories (agents except sessions/archive, memory, etc.)
     let skip_dirs: HashSet<&str> = ["sessions", "archive", ".clawpal"].iter().copied().collect();
-    restore_dir_recursive(&backup_dir, &paths.base_dir, &skip_dirs)?;
+    let only = selection.map(|(_, dirs)| dirs);
+    if only.map(|dirs| !dirs.is_empty()).unwrap_or(true) {
+        restore_dir_recursive(backup_dir, &paths.base_dir, &skip_dirs, only)?;
+    }
 
     Ok(format!("Restored from backup '{}'", backup_name))
 }
 
-fn restore_dir_recursive(src: &Path, dst: &Path, skip_dirs: &HashSet<&str>) -> Result<(), String> {
+/// Copy a backup tree over `dst`. When `only` is set, just those top-level
+/// directories are walked; loose top-level files are left alone.
+fn restore_dir_recursive(
+    src: &Path,
+    dst: &Path,
+    skip_dirs: &HashSet<&str>,
+    only: Option<&HashSet<String>>,
+) -> Result<(), String> {
     let entries = fs::read_dir(src).map_err(|e| format!("Failed to read backup dir: {e}"))?;
     for entry in entries {
         let entry = entry.map_err(|e| e.to_string())?;
@@ -3944,9 +9596,15 @@ fn restore_dir_recursive(src: &Path, dst: &Path, skip_dirs: &HashSet<&str>) -> R
             if skip_dirs.contains(name_str.as_ref()) {
                 continue;
             }
+            if only.is_some_and(|dirs| !dirs.contains(name_str.as_ref())) {
+                continue;
+            }
             fs::create_dir_all(&dest).map_err(|e| e.to_string())?;
-            restore_dir_recursive(&entry.path(), &dest, skip_dirs)?;
+            restore_dir_recursive(&entry.path(), &dest, skip_dirs, None)?;
         } else if file_type.is_file() {
+            if only.is_some() {
+                continue;
+            }
             fs::copy(entry.path(), &dest).map_err(|e| format!("Failed to restore {}: {e}", name_str))?;
         }
     }
@@ -3956,20 +9614,165 @@ fn restore_dir_recursive(src: &Path, dst: &Path, skip_dirs: &HashSet<&str>) -> R
 #[tauri::command]
 pub fn delete_backup(backup_name: String) -> Result<bool, String> {
     let paths = resolve_paths();
-    let backup_dir = paths.clawpal_dir.join("backups").join(&backup_name);
-    if !backup_dir.exists() {
+    let Some(location) = backup_location(&paths.clawpal_dir.join("backups"), &backup_name) else {
         return Ok(false);
-    }
-    fs::remove_dir_all(&backup_dir).map_err(|e| format!("Failed to delete backup: {e}"))?;
+    };
+    remove_backup_at(&location).map_err(|e| format!("Failed to delete backup: {e}"))?;
     Ok(true)
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupVerification {
+    pub config_valid: bool,
+    pub has_agents: bool,
+    pub has_memory: bool,
+    pub total_bytes: u64,
+}
+
+fn validate_backup_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() || name.contains("..") || name.contains('/') || name.contains('\\') {
+        return Err(format!("invalid backup name: {name}"));
+    }
+    Ok(())
+}
+
+fn verify_backup_dir(dir: &Path) -> BackupVerification {
+    BackupVerification {
+        config_valid: fs::read_to_string(dir.join("openclaw.json"))
+            .ok()
+            .is_some_and(|text| json5::from_str::<Value>(&text).is_ok()),
+        has_agents: dir.join("agents").is_dir(),
+        has_memory: dir.join("memory").is_dir(),
+        total_bytes: dir_size(dir),
+    }
+}
+
+/// Walk a `.tar.gz` backup without unpacking it; a truncated archive fails
+/// here rather than halfway through a restore.
+fn verify_backup_archive<R: std::io::Read>(reader: R) -> Result<BackupVerification, String> {
+    use std::io::Read;
+    let mut result = BackupVerification::default();
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(reader));
+    let entries = archive.entries().map_err(|e| format!("Failed to read backup: {e}"))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Backup archive is corrupt: {e}"))?;
+        let path = entry.path().map_err(|e| e.to_string())?.to_path_buf();
+        result.total_bytes += entry.header().size().unwrap_or(0);
+        match path.components().next().map(|c| c.as_os_str().to_string_lossy().to_string()).as_deref() {
+            Some("openclaw.json") => {
+                let mut text = String::new();
+                result.config_valid = entry.read_to_string(&mut text).is_ok()
+                    && json5::from_str::<Value>(&text).is_ok();
+            }
+            Some("agents") => result.has_agents = true,
+            Some("memory") => result.has_memory = true,
+            _ => {}
+        }
+    }
+    Ok(result)
+}
+
+/// Check that a backup has a parseable config and which data directories it
+/// holds, before relying on it for a restore.
+#[tauri::command]
+pub fn verify_backup(backup_name: String) -> Result<BackupVerification, String> {
+    validate_backup_name(&backup_name)?;
+    let paths = resolve_paths();
+    let location = backup_location(&paths.clawpal_dir.join("backups"), &backup_name)
+        .ok_or_else(|| format!("Backup '{}' not found", backup_name))?;
+    if location.is_dir() {
+        return Ok(verify_backup_dir(&location));
+    }
+    let file = fs::File::open(&location).map_err(|e| format!("Failed to open backup: {e}"))?;
+    verify_backup_archive(file)
+}
+
+#[cfg(test)]
+mod backup_verification_tests {
+    use super::*;
+
+    #[test]
+    fn verifies_archive_contents() {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+        for (name, body) in [("openclaw.json", "{ agents: {} }"), ("agents/main/IDENTITY.md", "- Name: x")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(body.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, body.as_bytes()).unwrap();
+        }
+        let bytes = builder.into_inner().unwrap().finish().unwrap();
+        let result = verify_backup_archive(bytes.as_slice()).unwrap();
+        assert!(result.config_valid);
+        assert!(result.has_agents);
+        assert!(!result.has_memory);
+        assert_eq!(result.total_bytes, 23);
+
+        assert!(verify_backup_archive(&bytes[..bytes.len() / 2]).is_err());
+        assert!(validate_backup_name("../x").is_err());
+    }
+}
+
+/// Backup names (`%Y-%m-%d_%H%M%S`) older than the cutoff. The newest backup
+/// is always kept, and names that don't parse as timestamps are left alone.
+fn backups_past_age(names: &[String], older_than_days: u64) -> Vec<String> {
+    let cutoff = unix_timestamp_secs().saturating_sub(older_than_days.saturating_mul(86_400)) as i64;
+    let mut dated: Vec<(i64, &String)> = names
+        .iter()
+        .filter_map(|name| {
+            chrono::NaiveDateTime::parse_from_str(name, "%Y-%m-%d_%H%M%S")
+                .ok()
+                .map(|dt| (dt.and_utc().timestamp(), name))
+        })
+        .collect();
+    dated.sort_by(|a, b| b.0.cmp(&a.0));
+    dated
+        .into_iter()
+        .skip(1)
+        .filter(|(ts, _)| *ts < cutoff)
+        .map(|(_, name)| name.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod backup_age_tests {
+    use super::*;
+
+    #[test]
+    fn newest_dated_backup_survives_stray_entries() {
+        let names = vec![
+            "2020-01-01_000000".to_string(),
+            "notes".to_string(),
+            "2020-02-01_000000".to_string(),
+        ];
+        assert_eq!(backups_past_age(&names, 1), vec!["2020-01-01_000000".to_string()]);
+    }
+}
+
+#[tauri::command]
+pub fn prune_backups_by_age(older_than_days: u64) -> Result<usize, String> {
+    let paths = resolve_paths();
+    let backups_dir = paths.clawpal_dir.join("backups");
+    let names = local_backup_names(&backups_dir);
+    let mut removed = 0usize;
+    for name in backups_past_age(&names, older_than_days) {
+        let Some(location) = backup_location(&backups_dir, &name) else {
+            continue;
+        };
+        remove_backup_at(&location).map_err(|e| format!("Failed to delete backup {name}: {e}"))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
 // ---- Remote Backup / Restore (via SSH) ----
 
 #[tauri::command]
 pub async fn remote_backup_before_upgrade(
     pool: State<'_, SshConnectionPool>,
     host_id: String,
+    max_backups: Option<usize>,
 ) -> Result<BackupInfo, String> {
     let now_secs = unix_timestamp_secs();
     let now_dt = chrono::DateTime::<chrono::Utc>::from_timestamp(now_secs as i64, 0);
@@ -3986,9 +9789,19 @@ pub async fn remote_backup_before_upgrade(
             "cp \"$HOME/.openclaw/openclaw.json\" \"$BDIR/\" 2>/dev/null || true; ",
             "cp -r \"$HOME/.openclaw/agents\" \"$BDIR/\" 2>/dev/null || true; ",
             "cp -r \"$HOME/.openclaw/memory\" \"$BDIR/\" 2>/dev/null || true; ",
+            // Never rotate older backups away when this one lacks the config.
+            "if [ ! -f \"$BDIR/openclaw.json\" ]; then rm -rf \"$BDIR\"; echo 'openclaw.json was not backed up' >&2; exit 1; fi; ",
+            "MAX={max}; ",
+            "if [ \"$MAX\" -gt 0 ]; then ",
+            "ls -1 \"$HOME/.clawpal/backups\" 2>/dev/null ",
+            "| grep -E '^[0-9]{{4}}-[0-9]{{2}}-[0-9]{{2}}_[0-9]{{6}}(\\.tar\\.gz)?$' | sed 's/\\.tar\\.gz$//' | sort -ru ",
+            "| tail -n +$((MAX + 1)) | while IFS= read -r old; do ",
+            "[ -n \"$old\" ] || continue; rm -rf \"$HOME/.clawpal/backups/$old\" \"$HOME/.clawpal/backups/$old.tar.gz\"; echo \"pruned:$old\"; ",
+            "done; fi; ",
             "du -sk \"$BDIR\" 2>/dev/null | awk '{{print $1 * 1024}}' || echo 0"
         ),
-        name = escaped_name
+        name = escaped_name,
+        max = max_backups.unwrap_or(DEFAULT_MAX_BACKUPS)
     );
 
     let result = pool.exec_login(&host_id, &cmd).await?;
@@ -3999,12 +9812,19 @@ pub async fn remote_backup_before_upgrade(
     let size_bytes: u64 = result.stdout.trim().lines().last()
         .and_then(|l| l.trim().parse().ok())
         .unwrap_or(0);
+    let pruned = result
+        .stdout
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("pruned:"))
+        .map(str::to_string)
+        .collect();
 
     Ok(BackupInfo {
         name,
         path: String::new(),
         created_at: format_timestamp_from_unix(now_secs),
         size_bytes,
+        pruned,
     })
 }
 
@@ -4066,6 +9886,7 @@ pub async fn remote_list_backups(
                 path: d.clone(),
                 created_at: name.clone(), // Name is the timestamp
                 size_bytes,
+                pruned: Vec::new(),
             }
         })
         .collect();
@@ -4077,29 +9898,214 @@ pub async fn remote_list_backups(
 #[tauri::command]
 pub async fn remote_restore_from_backup(
     pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
     host_id: String,
     backup_name: String,
+    components: Vec<String>,
 ) -> Result<String, String> {
+    let cmd = remote_restore_script(&backup_name, parse_restore_components(&components)?.as_ref());
+
+    let result = pool.exec_login(&host_id, &cmd).await?;
+    cache.invalidate(&remote_agents_overview_cache_key(&host_id));
+    if result.exit_code != 0 {
+        return Err(format!("Remote restore failed: {}", result.stderr));
+    }
+
+    Ok(format!("Restored from backup '{}'", backup_name))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadedBackup {
+    pub local_path: String,
+    pub bytes_written: u64,
+}
+
+/// Transfer chunk size for backup downloads (raw bytes, before base64).
+const BACKUP_DOWNLOAD_CHUNK_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Make sure `dir` exists and accepts new files before a long transfer.
+fn ensure_dir_writable(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {e}", dir.display()))?;
+    let probe = dir.join(format!(".clawpal-write-test-{}", std::process::id()));
+    fs::write(&probe, b"").map_err(|e| format!("{} is not writable: {e}", dir.display()))?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+/// Unpack a downloaded backup archive into `dest_root`. Only entries under
+/// `<backup_name>/` are accepted; anything else aborts the extraction and
+/// removes what was written so far.
+fn unpack_downloaded_backup<R: std::io::Read>(reader: R, dest_root: &Path, backup_name: &str) -> Result<(), String> {
+    let unpack = || -> Result<(), String> {
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(reader));
+        let entries = archive.entries().map_err(|e| format!("Failed to read backup: {e}"))?;
+        for entry in entries {
+            let mut entry = entry.map_err(|e| format!("Failed to extract backup: {e}"))?;
+            let path = entry.path().map_err(|e| e.to_string())?.to_path_buf();
+            let first = path.components().next();
+            if first != Some(std::path::Component::Normal(std::ffi::OsStr::new(backup_name))) {
+                return Err(format!("Backup archive has an unexpected entry: {}", path.display()));
+            }
+            entry
+                .unpack_in(dest_root)
+                .map_err(|e| format!("Failed to extract backup: {e}"))?;
+        }
+        Ok(())
+    };
+    let result = unpack();
+    if result.is_err() {
+        let _ = fs::remove_dir_all(dest_root.join(backup_name));
+    }
+    result
+}
+
+/// Pull a remote backup down into `dest_dir/<backup_name>`. The backup is
+/// tarred on the host and fetched in base64 chunks so large backups never
+/// sit in memory as a single string.
+#[tauri::command]
+pub async fn download_remote_backup(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    backup_name: String,
+    dest_dir: String,
+) -> Result<DownloadedBackup, String> {
+    if backup_name.is_empty() || backup_name.contains("..") || backup_name.contains('/') || backup_name.contains('\\') {
+        return Err("invalid backup name".into());
+    }
+    let dest_root = PathBuf::from(expand_tilde(&dest_dir));
+    ensure_dir_writable(&dest_root)?;
+    let target = dest_root.join(&backup_name);
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+
     let escaped_name = shell_escape(&backup_name);
-    let cmd = format!(
+    let remote_archive = format!("/tmp/clawpal-backup-{}.tar.gz", uuid::Uuid::new_v4());
+    let pack_cmd = format!(
+        concat!(
+            "set -e; ",
+            "[ -d \"$HOME/.clawpal/backups/\"{name} ] || {{ echo 'Backup not found' >&2; exit 1; }}; ",
+            "tar czf {archive} -C \"$HOME/.clawpal/backups\" {name}; ",
+            "wc -c < {archive}"
+        ),
+        name = escaped_name,
+        archive = shell_escape(&remote_archive),
+    );
+    let packed = pool.exec_login(&host_id, &pack_cmd).await?;
+    if packed.exit_code != 0 {
+        return Err(format!("Failed to pack remote backup: {}", packed.stderr.trim()));
+    }
+    let archive_size: u64 = packed
+        .stdout
+        .trim()
+        .lines()
+        .last()
+        .and_then(|l| l.trim().parse().ok())
+        .ok_or("Failed to read remote archive size")?;
+
+    let local_archive = dest_root.join(format!(".{backup_name}.download.tar.gz"));
+    let transfer = async {
+        use std::io::Write;
+        let mut file = fs::File::create(&local_archive)
+            .map_err(|e| format!("Failed to create {}: {e}", local_archive.display()))?;
+        let chunks = archive_size.div_ceil(BACKUP_DOWNLOAD_CHUNK_BYTES);
+        for index in 0..chunks {
+            let cmd = format!(
+                "dd if={} bs={} skip={} count=1 2>/dev/null | base64",
+                shell_escape(&remote_archive),
+                BACKUP_DOWNLOAD_CHUNK_BYTES,
+                index
+            );
+            let result = pool.exec(&host_id, &cmd).await?;
+            if result.exit_code != 0 {
+                return Err(format!("Failed to read backup chunk {index}: {}", result.stderr.trim()));
+            }
+            let bytes = crate::ssh::decode_base64_output(&result.stdout)?;
+            file.write_all(&bytes).map_err(|e| format!("Failed to write backup: {e}"))?;
+        }
+        file.sync_all().map_err(|e| e.to_string())?;
+        Ok::<(), String>(())
+    }
+    .await;
+    let _ = pool.exec(&host_id, &format!("rm -f {}", shell_escape(&remote_archive))).await;
+
+    let extracted = transfer.and_then(|_| {
+        let file = fs::File::open(&local_archive).map_err(|e| e.to_string())?;
+        unpack_downloaded_backup(file, &dest_root, &backup_name)
+    });
+    let _ = fs::remove_file(&local_archive);
+    extracted?;
+
+    Ok(DownloadedBackup {
+        local_path: target.to_string_lossy().to_string(),
+        bytes_written: dir_size(&target),
+    })
+}
+
+#[cfg(test)]
+mod backup_download_tests {
+    use super::*;
+
+    #[test]
+    fn downloaded_backup_only_unpacks_its_own_directory() {
+        let archive = |names: &[&str]| {
+            let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+            for name in names {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(2);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder.append_data(&mut header, name, "{}".as_bytes()).unwrap();
+            }
+            builder.into_inner().unwrap().finish().unwrap()
+        };
+        let root = std::env::temp_dir().join(format!("clawpal-download-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        let good = archive(&["2024-01-01_000000/openclaw.json"]);
+        unpack_downloaded_backup(good.as_slice(), &root, "2024-01-01_000000").unwrap();
+        assert!(root.join("2024-01-01_000000").join("openclaw.json").exists());
+
+        let bad = archive(&["2024-01-02_000000/openclaw.json", "evil.sh"]);
+        assert!(unpack_downloaded_backup(bad.as_slice(), &root, "2024-01-02_000000").is_err());
+        assert!(!root.join("evil.sh").exists());
+        assert!(!root.join("2024-01-02_000000").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
+
+/// Shell script restoring a remote backup; `selection` follows
+/// `parse_restore_components`.
+fn remote_restore_script(backup_name: &str, selection: Option<&(bool, HashSet<String>)>) -> String {
+    let escaped_name = shell_escape(backup_name);
+    let restore_config = selection.map(|(config, _)| *config).unwrap_or(true);
+    let mut dirs: Vec<String> = match selection {
+        Some((_, dirs)) => dirs.iter().cloned().collect(),
+        None => vec!["agents".into(), "memory".into()],
+    };
+    dirs.sort();
+
+    let mut cmd = format!(
         concat!(
             "set -e; ",
             "BDIR=\"$HOME/.clawpal/backups/\"{name}; ",
             "[ -d \"$BDIR\" ] || {{ echo 'Backup not found'; exit 1; }}; ",
-            "cp \"$BDIR/openclaw.json\" \"$HOME/.openclaw/openclaw.json\" 2>/dev/null || true; ",
-            "[ -d \"$BDIR/agents\" ] && cp -r \"$BDIR/agents\" \"$HOME/.openclaw/\" 2>/dev/null || true; ",
-            "[ -d \"$BDIR/memory\" ] && cp -r \"$BDIR/memory\" \"$HOME/.openclaw/\" 2>/dev/null || true; ",
-            "echo 'Restored from backup '{name}"
         ),
         name = escaped_name
     );
-
-    let result = pool.exec_login(&host_id, &cmd).await?;
-    if result.exit_code != 0 {
-        return Err(format!("Remote restore failed: {}", result.stderr));
+    if restore_config {
+        cmd.push_str("cp \"$BDIR/openclaw.json\" \"$HOME/.openclaw/openclaw.json\" 2>/dev/null || true; ");
     }
-
-    Ok(format!("Restored from backup '{}'", backup_name))
+    for dir in &dirs {
+        let dir = shell_escape(dir);
+        cmd.push_str(&format!(
+            "[ -d \"$BDIR\"/{dir} ] && cp -r \"$BDIR\"/{dir} \"$HOME/.openclaw/\" 2>/dev/null || true; "
+        ));
+    }
+    cmd.push_str(&format!("echo 'Restored from backup '{escaped_name}"));
+    cmd
 }
 
 #[tauri::command]
@@ -4118,6 +10124,79 @@ pub async fn remote_delete_backup(
     Ok(result.stdout.trim() == "deleted")
 }
 
+#[tauri::command]
+pub async fn remote_verify_backup(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    backup_name: String,
+) -> Result<BackupVerification, String> {
+    validate_backup_name(&backup_name)?;
+    let cmd = format!(
+        concat!(
+            "BDIR=\"$HOME/.clawpal/backups/\"{name}; ",
+            "[ -d \"$BDIR\" ] || {{ echo missing; exit 0; }}; ",
+            "a=0; [ -d \"$BDIR/agents\" ] && a=1; ",
+            "m=0; [ -d \"$BDIR/memory\" ] && m=1; ",
+            "b=$(du -sk \"$BDIR\" 2>/dev/null | awk '{{print $1 * 1024}}'); ",
+            "echo \"$a $m ${{b:-0}}\""
+        ),
+        name = shell_escape(&backup_name)
+    );
+    let result = pool.exec_login(&host_id, &cmd).await?;
+    let line = result.stdout.trim();
+    if line == "missing" {
+        return Err(format!("Backup '{}' not found", backup_name));
+    }
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != 3 {
+        return Err(format!("Unexpected verify output: {line}"));
+    }
+    // Parse the config locally so JSON5 configs are judged like local ones.
+    let config_valid = pool
+        .sftp_read(&host_id, &format!("~/.clawpal/backups/{backup_name}/openclaw.json"))
+        .await
+        .ok()
+        .is_some_and(|text| json5::from_str::<Value>(&text).is_ok());
+    Ok(BackupVerification {
+        config_valid,
+        has_agents: fields[0] == "1",
+        has_memory: fields[1] == "1",
+        total_bytes: fields[2].parse().unwrap_or(0),
+    })
+}
+
+#[tauri::command]
+pub async fn remote_prune_backups_by_age(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    older_than_days: u64,
+) -> Result<usize, String> {
+    let listing = pool
+        .exec_login(&host_id, "ls -1d \"$HOME/.clawpal/backups\"/*/ 2>/dev/null || true")
+        .await?;
+    let names: Vec<String> = listing
+        .stdout
+        .lines()
+        .filter_map(|l| l.trim().trim_end_matches('/').rsplit('/').next())
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .collect();
+    let stale = backups_past_age(&names, older_than_days);
+    if stale.is_empty() {
+        return Ok(0);
+    }
+    let cmd = stale
+        .iter()
+        .map(|name| format!("rm -rf \"$HOME/.clawpal/backups/\"{}", shell_escape(name)))
+        .collect::<Vec<_>>()
+        .join(" && ");
+    let result = pool.exec_login(&host_id, &cmd).await?;
+    if result.exit_code != 0 {
+        return Err(format!("Failed to prune backups: {}", result.stderr.trim()));
+    }
+    Ok(stale.len())
+}
+
 fn resolve_model_provider_base_url(cfg: &Value, provider: &str) -> Option<String> {
     let provider = provider.trim();
     if provider.is_empty() {
@@ -4182,6 +10261,9 @@ pub fn list_ssh_hosts() -> Result<Vec<SshHostConfig>, String> {
 
 #[tauri::command]
 pub fn upsert_ssh_host(host: SshHostConfig) -> Result<SshHostConfig, String> {
+    if let Some(jump) = host.jump_host.as_deref().filter(|j| !j.trim().is_empty()) {
+        crate::ssh::validate_jump_host(jump.trim())?;
+    }
     let mut hosts = read_hosts_from_disk()?;
     if let Some(existing) = hosts.iter_mut().find(|h| h.id == host.id) {
         *existing = host.clone();
@@ -4193,13 +10275,196 @@ pub fn upsert_ssh_host(host: SshHostConfig) -> Result<SshHostConfig, String> {
 }
 
 #[tauri::command]
-pub fn delete_ssh_host(host_id: String) -> Result<bool, String> {
+pub fn delete_ssh_host(host_id: String) -> Result<bool, String> {
+    let mut hosts = read_hosts_from_disk()?;
+    let before = hosts.len();
+    hosts.retain(|h| h.id != host_id);
+    let removed = hosts.len() < before;
+    write_hosts_to_disk(&hosts)?;
+    Ok(removed)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedKeypair {
+    /// Use as `keyPath` when saving the host with `upsert_ssh_host`.
+    pub private_key_path: String,
+    pub public_key: String,
+}
+
+/// Create a passphrase-less keypair in `~/.clawpal/keys/<label>` for a new
+/// remote. Existing keys with the same label are only replaced with `force`.
+#[tauri::command]
+pub fn generate_ssh_keypair(
+    label: String,
+    key_type: Option<String>,
+    force: Option<bool>,
+) -> Result<GeneratedKeypair, String> {
+    if label.is_empty() || !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Invalid key label: {label}"));
+    }
+    let key_type = key_type.unwrap_or_else(|| "ed25519".into());
+    if !["ed25519", "rsa", "ecdsa"].contains(&key_type.as_str()) {
+        return Err(format!("Unsupported key type: {key_type}"));
+    }
+    let keys_dir = resolve_paths().clawpal_dir.join("keys");
+    fs::create_dir_all(&keys_dir).map_err(|e| format!("Failed to create keys dir: {e}"))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&keys_dir, fs::Permissions::from_mode(0o700));
+    }
+    let private_path = keys_dir.join(&label);
+    let public_path = keys_dir.join(format!("{label}.pub"));
+    if private_path.exists() || public_path.exists() {
+        if !force.unwrap_or(false) {
+            return Err(format!("A key named {label} already exists"));
+        }
+        let _ = fs::remove_file(&private_path);
+        let _ = fs::remove_file(&public_path);
+    }
+
+    let mut cmd = Command::new("ssh-keygen");
+    cmd.args(["-q", "-t", &key_type, "-N", "", "-C", &format!("clawpal-{label}"), "-f"])
+        .arg(&private_path);
+    if key_type == "rsa" {
+        cmd.args(["-b", "4096"]);
+    }
+    let output = cmd.output().map_err(|e| format!("Failed to run ssh-keygen: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "ssh-keygen failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let public_key = fs::read_to_string(&public_path)
+        .map_err(|e| format!("Failed to read public key: {e}"))?
+        .trim()
+        .to_string();
+    Ok(GeneratedKeypair {
+        private_key_path: private_path.to_string_lossy().to_string(),
+        public_key,
+    })
+}
+
+/// Append `public_key` to the remote `~/.ssh/authorized_keys` (over the
+/// host's current auth, typically password). Returns false when the key was
+/// already present. With `key_path` the stored host is switched to key auth,
+/// but only after a key-only login succeeds; otherwise the password is kept.
+#[tauri::command]
+pub async fn ssh_install_public_key(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    public_key: String,
+    key_path: Option<String>,
+) -> Result<bool, String> {
+    let key = public_key.trim();
+    if key.is_empty()
+        || key.contains('\n')
+        || !(key.starts_with("ssh-") || key.starts_with("ecdsa-") || key.starts_with("sk-"))
+    {
+        return Err("Not a valid OpenSSH public key".into());
+    }
+    let mut hosts = read_hosts_from_disk()?;
+    let host = hosts
+        .iter()
+        .find(|h| h.id == host_id)
+        .cloned()
+        .ok_or_else(|| format!("No SSH host config with id: {host_id}"))?;
+    if !pool.is_connected(&host_id).await {
+        pool.connect(&host).await?;
+    }
+
+    let quoted = shell_escape(key);
+    let cmd = format!(
+        concat!(
+            "umask 077; mkdir -p ~/.ssh && chmod 700 ~/.ssh && ",
+            "touch ~/.ssh/authorized_keys && chmod 600 ~/.ssh/authorized_keys && ",
+            "if grep -qxF {key} ~/.ssh/authorized_keys; then echo exists; ",
+            "else printf '%s\\n' {key} >> ~/.ssh/authorized_keys && echo added; fi"
+        ),
+        key = quoted
+    );
+    let result = pool.exec(&host_id, &cmd).await?;
+    if result.exit_code != 0 {
+        return Err(format!("Failed to install public key: {}", result.stderr.trim()));
+    }
+    let added = result.stdout.trim() == "added";
+
+    if let Some(path) = key_path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()) {
+        let mut candidate = host.clone();
+        candidate.auth_method = "key".into();
+        candidate.key_path = Some(path);
+        candidate.password = None;
+        // Log in with the key on a separate pool so a failure leaves the
+        // current session and the stored password untouched.
+        let probe = SshConnectionPool::new();
+        probe.connect(&candidate).await.map_err(|e| {
+            format!("Public key installed, but logging in with it failed; keeping password auth: {e}")
+        })?;
+        let _ = probe.disconnect(&candidate.id).await;
+        if let Some(stored) = hosts.iter_mut().find(|h| h.id == host_id) {
+            *stored = candidate;
+        }
+        write_hosts_to_disk(&hosts)?;
+        // Reconnect so the pool stops using the password transport.
+        pool.disconnect(&host_id).await?;
+    }
+    Ok(added)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostFingerprint {
+    /// Fingerprints of every host key the server currently presents.
+    pub fingerprints: Vec<String>,
+    pub pinned: Option<String>,
+    /// Whether the pinned value (if any) matches a presented key.
+    pub matches: Option<bool>,
+}
+
+/// Scan the remote host's keys without authenticating so the user can
+/// compare them against an out-of-band value before pinning one.
+#[tauri::command]
+pub async fn remote_host_fingerprint(host_id: String) -> Result<HostFingerprint, String> {
+    let host = read_hosts_from_disk()?
+        .into_iter()
+        .find(|h| h.id == host_id)
+        .ok_or_else(|| format!("No SSH host config with id: {host_id}"))?;
+    let fingerprints = crate::ssh::scan_host_fingerprints(&host).await?;
+    let pinned = crate::ssh::pinned_fingerprint(&host).map(str::to_string);
+    let matches = pinned
+        .as_ref()
+        .map(|pinned| fingerprints.iter().any(|fp| fp == pinned));
+    Ok(HostFingerprint {
+        fingerprints,
+        pinned,
+        matches,
+    })
+}
+
+/// Pin (or with `None`, unpin) the expected host key fingerprint. Pinned
+/// hosts are connected with strict host key verification.
+#[tauri::command]
+pub fn set_host_fingerprint(host_id: String, fingerprint: Option<String>) -> Result<SshHostConfig, String> {
+    let fingerprint = fingerprint
+        .map(|fp| fp.trim().to_string())
+        .filter(|fp| !fp.is_empty());
+    if let Some(fp) = &fingerprint {
+        // Host keys are scanned as SHA256, so an MD5 pin could never match.
+        if !fp.starts_with("SHA256:") {
+            return Err(format!("Fingerprint must be a SHA256: fingerprint, got {fp}"));
+        }
+    }
     let mut hosts = read_hosts_from_disk()?;
-    let before = hosts.len();
-    hosts.retain(|h| h.id != host_id);
-    let removed = hosts.len() < before;
+    let host = hosts
+        .iter_mut()
+        .find(|h| h.id == host_id)
+        .ok_or_else(|| format!("No SSH host config with id: {host_id}"))?;
+    host.host_fingerprint = fingerprint;
+    let updated = host.clone();
     write_hosts_to_disk(&hosts)?;
-    Ok(removed)
+    Ok(updated)
 }
 
 // ---------------------------------------------------------------------------
@@ -4220,7 +10485,12 @@ pub async fn ssh_connect(pool: State<'_, SshConnectionPool>, host_id: String) ->
 }
 
 #[tauri::command]
-pub async fn ssh_disconnect(pool: State<'_, SshConnectionPool>, host_id: String) -> Result<bool, String> {
+pub async fn ssh_disconnect(
+    pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
+    host_id: String,
+) -> Result<bool, String> {
+    cache.invalidate(&remote_agents_overview_cache_key(&host_id));
     pool.disconnect(&host_id).await?;
     Ok(true)
 }
@@ -4234,10 +10504,21 @@ pub async fn ssh_status(pool: State<'_, SshConnectionPool>, host_id: String) ->
     }
 }
 
+#[tauri::command]
+pub async fn ssh_health(pool: State<'_, SshConnectionPool>, host_id: String) -> Result<crate::ssh::SshHealth, String> {
+    Ok(pool.health(&host_id).await)
+}
+
 // ---------------------------------------------------------------------------
 // Task 5: SSH exec and SFTP Tauri commands
 // ---------------------------------------------------------------------------
 
+/// Abort a long-running remote command started with an `op_id`.
+#[tauri::command]
+pub async fn remote_cancel_operation(pool: State<'_, SshConnectionPool>, op_id: String) -> Result<bool, String> {
+    pool.cancel_operation(&op_id).await
+}
+
 #[tauri::command]
 pub async fn ssh_exec(pool: State<'_, SshConnectionPool>, host_id: String, command: String) -> Result<SshExecResult, String> {
     pool.exec(&host_id, &command).await
@@ -4323,6 +10604,107 @@ pub async fn remote_get_system_status(pool: State<'_, SshConnectionPool>, host_i
     })
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteStatusBundle {
+    pub openclaw_version: Option<String>,
+    pub gateway_running: bool,
+    pub agent_count: u32,
+    pub snapshot_count: u32,
+    pub openclaw_disk_bytes: u64,
+}
+
+/// Parse the JSON emitted by the `remote_get_status_bundle` script. The
+/// config travels base64-encoded and is parsed locally (it may be JSON5).
+fn parse_remote_status_bundle(stdout: &str) -> Result<RemoteStatusBundle, String> {
+    let raw: Value = serde_json::from_str(stdout.trim())
+        .map_err(|e| format!("Failed to parse remote status: {e}"))?;
+    let config_text = raw
+        .get("config")
+        .and_then(Value::as_str)
+        .and_then(|b64| {
+            use base64::Engine;
+            base64::engine::general_purpose::STANDARD.decode(b64).ok()
+        })
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default();
+    let agent_count = json5::from_str::<Value>(&config_text)
+        .ok()
+        .map(|cfg| collect_agent_ids(&cfg).len() as u32)
+        .unwrap_or(0);
+    Ok(RemoteStatusBundle {
+        openclaw_version: raw
+            .get("version")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string),
+        gateway_running: raw.get("running").and_then(Value::as_u64) == Some(1),
+        agent_count,
+        snapshot_count: raw.get("snapshots").and_then(Value::as_u64).unwrap_or(0) as u32,
+        openclaw_disk_bytes: raw.get("diskKb").and_then(Value::as_u64).unwrap_or(0) * 1024,
+    })
+}
+
+/// Script behind `remote_get_status_bundle`. It must start with `openclaw`
+/// itself: `exec_login` takes the first word as the binary to put on PATH.
+const REMOTE_STATUS_BUNDLE_SCRIPT: &str = r#"openclaw --version 2>/dev/null | head -n 1 | sed 's/\\/\\\\/g; s/"/\\"/g' | {
+read -r v
+if pgrep -f '[o]penclaw-gateway' >/dev/null 2>&1; then running=1; else running=0; fi
+snaps=$(ls -1 "$HOME/.clawpal/snapshots" 2>/dev/null | grep -c '\.json$' || true)
+[ -z "$snaps" ] && snaps=0
+kb=$(du -sk "$HOME/.openclaw" 2>/dev/null | awk '{print $1}')
+[ -z "$kb" ] && kb=0
+cfg=$(base64 < "$HOME/.openclaw/openclaw.json" 2>/dev/null | tr -d '\n')
+printf '{"version":"%s","running":%s,"snapshots":%s,"diskKb":%s,"config":"%s"}\n' "$v" "$running" "$snaps" "$kb" "$cfg"
+}
+"#;
+
+/// Version, gateway state, agent/snapshot counts and disk usage in one
+/// round trip, for dashboards that would otherwise issue a call per field.
+#[tauri::command]
+pub async fn remote_get_status_bundle(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+) -> Result<RemoteStatusBundle, String> {
+    let result = pool.exec_login(&host_id, REMOTE_STATUS_BUNDLE_SCRIPT).await?;
+    parse_remote_status_bundle(&result.stdout)
+}
+
+#[cfg(test)]
+mod remote_status_bundle_tests {
+    use super::*;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    #[test]
+    fn login_wrapped_status_script_is_valid_shell() {
+        let wrapped = crate::ssh::login_shell_wrap(REMOTE_STATUS_BUNDLE_SCRIPT);
+        let mut child = Command::new("sh")
+            .arg("-n")
+            .stdin(Stdio::piped())
+            .spawn()
+            .expect("spawn sh");
+        child.stdin.take().unwrap().write_all(wrapped.as_bytes()).unwrap();
+        assert!(child.wait().unwrap().success(), "sh -n rejected:\n{wrapped}");
+    }
+
+    #[test]
+    fn status_bundle_counts_agents_from_the_encoded_config() {
+        use base64::Engine;
+        let cfg = base64::engine::general_purpose::STANDARD
+            .encode(r#"{ agents: { list: [{ id: "main" }, { id: "ops" },] } }"#);
+        let stdout = format!(
+            r#"{{"version":"1.2.3","running":1,"snapshots":4,"diskKb":2,"config":"{cfg}"}}"#
+        );
+        let bundle = parse_remote_status_bundle(&stdout).unwrap();
+        assert_eq!(bundle.openclaw_version.as_deref(), Some("1.2.3"));
+        assert!(bundle.gateway_running);
+        assert_eq!(bundle.agent_count, 2);
+        assert_eq!(bundle.openclaw_disk_bytes, 2048);
+    }
+}
+
 /// Tier 2: slow, optional — openclaw version + duplicate detection (2 SSH calls in parallel).
 /// Called once on mount and on-demand (e.g., after upgrade), not in poll loop.
 #[tauri::command]
@@ -4370,6 +10752,99 @@ pub async fn remote_get_status_extra(pool: State<'_, SshConnectionPool>, host_id
     })
 }
 
+// ---- Version pin ----
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClawpalSettings {
+    /// Pinned openclaw versions, keyed by "local" or a remote host id.
+    #[serde(default)]
+    version_pins: BTreeMap<String, String>,
+}
+
+fn clawpal_settings_path(paths: &crate::models::OpenClawPaths) -> PathBuf {
+    paths.clawpal_dir.join("settings.json")
+}
+
+fn load_clawpal_settings(paths: &crate::models::OpenClawPaths) -> ClawpalSettings {
+    fs::read_to_string(clawpal_settings_path(paths))
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_clawpal_settings(paths: &crate::models::OpenClawPaths, settings: &ClawpalSettings) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    write_text(&clawpal_settings_path(paths), &text)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionPinCheck {
+    pub current: String,
+    pub pinned: String,
+    pub matches: bool,
+    /// "newer", "older" or "same" relative to the pin; "unknown" if either
+    /// version could not be parsed.
+    pub direction: String,
+}
+
+/// Store `pinned` under `scope` when given, otherwise fall back to the saved
+/// pin for that scope.
+fn resolve_version_pin(scope: &str, pinned: Option<String>) -> Result<String, String> {
+    let paths = resolve_paths();
+    let mut settings = load_clawpal_settings(&paths);
+    match pinned.map(|v| v.trim().to_string()).filter(|v| !v.is_empty()) {
+        Some(pin) => {
+            if normalize_semver_components(&pin).is_none() {
+                return Err(format!("invalid version: {pin}"));
+            }
+            settings.version_pins.insert(scope.to_string(), pin.clone());
+            save_clawpal_settings(&paths, &settings)?;
+            Ok(pin)
+        }
+        None => settings
+            .version_pins
+            .remove(scope)
+            .ok_or_else(|| "no pinned version set".to_string()),
+    }
+}
+
+fn build_version_pin_check(current: String, pinned: String) -> VersionPinCheck {
+    let direction = match semver_ordering(&current, &pinned) {
+        Some(std::cmp::Ordering::Greater) => "newer",
+        Some(std::cmp::Ordering::Less) => "older",
+        Some(std::cmp::Ordering::Equal) => "same",
+        None => "unknown",
+    };
+    VersionPinCheck {
+        matches: direction == "same",
+        direction: direction.into(),
+        current,
+        pinned,
+    }
+}
+
+#[tauri::command]
+pub fn check_version_pin(pinned: Option<String>) -> Result<VersionPinCheck, String> {
+    let pinned = resolve_version_pin("local", pinned)?;
+    Ok(build_version_pin_check(resolve_openclaw_version(), pinned))
+}
+
+#[tauri::command]
+pub async fn remote_check_version_pin(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    pinned: Option<String>,
+) -> Result<VersionPinCheck, String> {
+    let pinned = resolve_version_pin(&host_id, pinned)?;
+    let current = match pool.exec_login(&host_id, "openclaw --version").await {
+        Ok(r) => extract_version_from_text(r.stdout.trim()).unwrap_or_else(|| "unknown".into()),
+        Err(_) => "unknown".into(),
+    };
+    Ok(build_version_pin_check(current, pinned))
+}
+
 #[tauri::command]
 pub async fn remote_check_openclaw_update(
     pool: State<'_, SshConnectionPool>,
@@ -4413,8 +10888,29 @@ pub async fn remote_check_openclaw_update(
     }))
 }
 
+/// How long a host's agents overview is served from `CliCache` before the
+/// CLI and IDENTITY.md reads are repeated.
+const REMOTE_AGENTS_OVERVIEW_TTL: std::time::Duration = std::time::Duration::from_secs(15);
+
+pub(crate) fn remote_agents_overview_cache_key(host_id: &str) -> String {
+    format!("remote:{host_id}:agents-overview")
+}
+
 #[tauri::command]
-pub async fn remote_list_agents_overview(pool: State<'_, SshConnectionPool>, host_id: String) -> Result<Vec<AgentOverview>, String> {
+pub async fn remote_list_agents_overview(
+    pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
+    host_id: String,
+    force: Option<bool>,
+) -> Result<Vec<AgentOverview>, String> {
+    let cache_key = remote_agents_overview_cache_key(&host_id);
+    if !force.unwrap_or(false) {
+        if let Some(cached) = cache.get(&cache_key, Some(REMOTE_AGENTS_OVERVIEW_TTL)) {
+            if let Ok(agents) = serde_json::from_str(&cached) {
+                return Ok(agents);
+            }
+        }
+    }
     let output = crate::cli_runner::run_openclaw_remote(&pool, &host_id, &["agents", "list", "--json"]).await?;
     let json = crate::cli_runner::parse_json_output(&output)?;
     // Check which agents have sessions remotely (single command, batch check)
@@ -4431,7 +10927,108 @@ pub async fn remote_list_agents_overview(pool: State<'_, SshConnectionPool>, hos
         }
         Err(_) => std::collections::HashSet::new(), // fallback: all offline
     };
-    parse_agents_cli_output(&json, Some(&online_set))
+    let mut agents = parse_agents_cli_output(&json, Some(&online_set))?;
+    let mut seen = HashSet::new();
+    agents.retain(|a| seen.insert(a.id.clone()));
+    fill_remote_agent_identities(&pool, &host_id, &mut agents).await;
+    if let Ok(serialized) = serde_json::to_string(&agents) {
+        cache.set(cache_key, serialized);
+    }
+    Ok(agents)
+}
+
+const IDENTITY_BATCH_MARKER: &str = "---clawpal-identity:";
+
+/// One shell command that prints each `(index, path)` file behind a marker
+/// line, so every IDENTITY.md is read in a single round trip. A leading `~/`
+/// is expanded by the remote shell.
+fn identity_batch_script(files: &[(usize, String)]) -> String {
+    files
+        .iter()
+        .map(|(i, path)| {
+            let quoted = match path.strip_prefix("~/") {
+                Some(rest) => format!("\"$HOME\"/{}", shell_escape(rest)),
+                None => shell_escape(path),
+            };
+            format!("printf '\\n{IDENTITY_BATCH_MARKER}%s---\\n' {i}; cat {quoted} 2>/dev/null")
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Split `identity_batch_script` output back into `(index, content)` pairs,
+/// skipping files that were missing or empty.
+fn split_identity_batch(stdout: &str) -> Vec<(usize, String)> {
+    let mut out: Vec<(usize, String)> = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for line in stdout.lines() {
+        let index = line
+            .strip_prefix(IDENTITY_BATCH_MARKER)
+            .and_then(|rest| rest.strip_suffix("---"))
+            .and_then(|i| i.parse().ok());
+        if let Some(i) = index {
+            out.extend(current.replace((i, String::new())));
+        } else if let Some((_, content)) = current.as_mut() {
+            content.push_str(line);
+            content.push('\n');
+        }
+    }
+    out.extend(current);
+    out.retain(|(_, content)| !content.trim().is_empty());
+    out
+}
+
+#[cfg(test)]
+mod remote_identity_batch_tests {
+    use super::{identity_batch_script, split_identity_batch};
+    use crate::doctor::parse_identity_content;
+
+    #[test]
+    fn identity_batch_round_trips_through_markers() {
+        let files = vec![(0, "~/ws a/IDENTITY.md".to_string()), (2, "/srv/it's/IDENTITY.md".to_string())];
+        let script = identity_batch_script(&files);
+        assert!(script.contains("cat \"$HOME\"/'ws a/IDENTITY.md'"));
+        assert!(script.contains("'/srv/it'\\''s/IDENTITY.md'"));
+
+        let stdout = "\n---clawpal-identity:0---\n- Name: Ada\n- Emoji: 🦀\n---clawpal-identity:2---\n";
+        let parsed = split_identity_batch(stdout);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].0, 0);
+        assert_eq!(parse_identity_content(&parsed[0].1), (Some("Ada".into()), Some("🦀".into())));
+    }
+}
+
+/// Fill in name/emoji for agents the CLI returned without identity fields by
+/// reading their workspace IDENTITY.md in one batched exec. Agents without a
+/// workspace or identity file are left unchanged.
+async fn fill_remote_agent_identities(pool: &SshConnectionPool, host_id: &str, agents: &mut [AgentOverview]) {
+    let pending: Vec<(usize, String)> = agents
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| a.name.is_none() || a.emoji.is_none())
+        .filter_map(|(i, a)| {
+            let ws = a.workspace.as_deref()?.trim_end_matches('/');
+            Some((i, format!("{ws}/IDENTITY.md")))
+        })
+        .collect();
+    if pending.is_empty() {
+        return;
+    }
+    let Ok(result) = pool.exec(host_id, &identity_batch_script(&pending)).await else {
+        return;
+    };
+    for (i, content) in split_identity_batch(&result.stdout) {
+        let Some(agent) = agents.get_mut(i) else {
+            continue;
+        };
+        let (name, emoji) = crate::doctor::parse_identity_content(&content);
+        if agent.name.is_none() {
+            agent.name = name;
+        }
+        if agent.emoji.is_none() {
+            agent.emoji = emoji;
+        }
+    }
 }
 
 #[tauri::command]
@@ -4505,6 +11102,7 @@ pub async fn remote_restart_gateway(
 #[tauri::command]
 pub async fn remote_apply_config_patch(
     pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
     host_id: String,
     patch_template: String,
     params: Map<String, Value>,
@@ -4517,6 +11115,7 @@ pub async fn remote_apply_config_patch(
         build_candidate_config_from_template(&current, &patch_template, &params)?;
     remote_write_config_with_snapshot(&pool, &host_id, &current_text, &candidate, "config-patch")
         .await?;
+    cache.invalidate(&remote_agents_overview_cache_key(&host_id));
     Ok(ApplyResult {
         ok: true,
         snapshot_id: None,
@@ -4551,6 +11150,80 @@ pub async fn remote_run_doctor(
     }))
 }
 
+/// Remote counterpart of `run_openclaw`; each argument is shell-quoted.
+#[tauri::command]
+pub async fn remote_run_openclaw(
+    pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
+    host_id: String,
+    args: Vec<String>,
+    timeout_secs: Option<u64>,
+) -> Result<OpenclawCommandOutput, String> {
+    validate_openclaw_args(&args)?;
+    let mut cmd = String::from("openclaw");
+    for arg in &args {
+        cmd.push(' ');
+        cmd.push_str(&shell_escape(arg));
+    }
+    let run = pool.exec_login(&host_id, &cmd);
+    let result = match timeout_secs {
+        Some(secs) => tokio::time::timeout(std::time::Duration::from_secs(secs), run)
+            .await
+            .map_err(|_| format!("Command timed out after {secs}s"))??,
+        None => run.await?,
+    };
+    // Passthrough commands such as `agents add` can change what the overview shows.
+    cache.invalidate(&remote_agents_overview_cache_key(&host_id));
+    Ok(OpenclawCommandOutput {
+        stdout: result.stdout.trim_end().to_string(),
+        stderr: result.stderr.trim_end().to_string(),
+        exit_code: result.exit_code as i32,
+    })
+}
+
+/// Remote clocks further off than this make snapshot ordering unreliable.
+const CLOCK_SKEW_WARN_SECS: i64 = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockSkew {
+    pub local_time: i64,
+    pub remote_time: i64,
+    /// Remote minus local, adjusted for half the round trip.
+    pub skew_seconds: i64,
+    pub warning: Option<String>,
+}
+
+#[tauri::command]
+pub async fn remote_check_clock_skew(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+) -> Result<ClockSkew, String> {
+    let started = std::time::Instant::now();
+    let result = pool.exec(&host_id, "date +%s").await?;
+    let round_trip = started.elapsed().as_secs() as i64;
+    let remote_time: i64 = result
+        .stdout
+        .trim()
+        .parse()
+        .map_err(|_| format!("Unexpected output from remote date: {}", result.stdout.trim()))?;
+    let local_time = unix_timestamp_secs() as i64 - round_trip / 2;
+    let skew_seconds = remote_time - local_time;
+    let warning = (skew_seconds.abs() > CLOCK_SKEW_WARN_SECS).then(|| {
+        format!(
+            "Remote clock is {}s {} local time; snapshot history may sort out of order",
+            skew_seconds.abs(),
+            if skew_seconds > 0 { "ahead of" } else { "behind" }
+        )
+    });
+    Ok(ClockSkew {
+        local_time,
+        remote_time,
+        skew_seconds,
+        warning,
+    })
+}
+
 #[tauri::command]
 pub async fn remote_list_history(
     pool: State<'_, SshConnectionPool>,
@@ -4564,32 +11237,141 @@ pub async fn remote_list_history(
         if entry.name.starts_with('.') || entry.is_dir {
             continue;
         }
-        // Parse filename: {unix_ts}-{source}-{summary}.json
-        let stem = entry.name.trim_end_matches(".json");
-        let parts: Vec<&str> = stem.splitn(3, '-').collect();
-        let ts_str = parts.first().unwrap_or(&"0");
-        let source = parts.get(1).unwrap_or(&"unknown");
-        let recipe_id = parts.get(2).map(|s| s.to_string());
-        let created_at = ts_str.parse::<i64>().unwrap_or(0);
-        // Convert Unix timestamp to ISO 8601 format for frontend compatibility
-        let created_at_iso = chrono::DateTime::from_timestamp(created_at, 0)
-            .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
-            .unwrap_or_else(|| created_at.to_string());
-        let is_rollback = *source == "rollback";
-        items.push(serde_json::json!({
-            "id": entry.name,
-            "recipeId": recipe_id,
-            "createdAt": created_at_iso,
-            "source": source,
-            "canRollback": !is_rollback,
-        }));
+        items.push(remote_snapshot_item(&entry.name));
+    }
+    sort_remote_snapshot_items(&mut items);
+    Ok(serde_json::json!({ "items": items }))
+}
+
+/// Delete remote snapshots in `~/.clawpal/snapshots` outside the retention
+/// window. Remote snapshots carry no rollback links, so only age and count apply.
+#[tauri::command]
+pub async fn remote_prune_snapshots(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    keep_last: Option<usize>,
+    older_than_days: Option<u64>,
+) -> Result<usize, String> {
+    if keep_last.is_none() && older_than_days.is_none() {
+        return Err("specify keep_last and/or older_than_days".into());
+    }
+    let entries = pool.sftp_list(&host_id, "~/.clawpal/snapshots").await?;
+    let mut dated: Vec<(String, Option<i64>, Option<String>)> = entries
+        .into_iter()
+        .filter(|e| !e.is_dir && !e.name.starts_with('.') && e.name.ends_with(".json"))
+        .map(|e| {
+            let ts = e.name.split('-').next().and_then(|t| t.parse::<i64>().ok());
+            (e.name, ts, None)
+        })
+        .collect();
+    dated.sort_by(|a, b| b.1.cmp(&a.1));
+    let doomed = snapshots_outside_retention(&dated, keep_last, older_than_days);
+    if doomed.is_empty() {
+        return Ok(0);
     }
-    // Sort newest first
+    let files: Vec<String> = doomed
+        .iter()
+        .map(|name| format!("\"$HOME/.clawpal/snapshots/\"{}", shell_escape(name)))
+        .collect();
+    let result = pool.exec(&host_id, &format!("rm -f {}", files.join(" "))).await?;
+    if result.exit_code != 0 {
+        return Err(format!("Failed to prune remote snapshots: {}", result.stderr.trim()));
+    }
+    Ok(doomed.len())
+}
+
+/// History item for a remote snapshot file named `{unix_ts}-{source}-{summary}.json`.
+fn remote_snapshot_item(name: &str) -> Value {
+    let stem = name.trim_end_matches(".json");
+    let parts: Vec<&str> = stem.splitn(3, '-').collect();
+    let ts_str = parts.first().unwrap_or(&"0");
+    let source = parts.get(1).unwrap_or(&"unknown");
+    let recipe_id = parts.get(2).map(|s| s.to_string());
+    let created_at = ts_str.parse::<i64>().unwrap_or(0);
+    // Convert Unix timestamp to ISO 8601 format for frontend compatibility
+    let created_at_iso = chrono::DateTime::from_timestamp(created_at, 0)
+        .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        .unwrap_or_else(|| created_at.to_string());
+    let is_rollback = *source == "rollback";
+    serde_json::json!({
+        "id": name,
+        "recipeId": recipe_id,
+        "createdAt": created_at_iso,
+        "source": source,
+        "canRollback": !is_rollback,
+    })
+}
+
+/// Sort newest first.
+fn sort_remote_snapshot_items(items: &mut [Value]) {
     items.sort_by(|a, b| {
         let ta = a["createdAt"].as_str().unwrap_or("");
         let tb = b["createdAt"].as_str().unwrap_or("");
         tb.cmp(ta)
     });
+}
+
+fn remote_history_mirror_dir(paths: &crate::models::OpenClawPaths, host_id: &str) -> PathBuf {
+    let safe_host_id: String = host_id
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' { ch } else { '_' })
+        .collect();
+    paths.clawpal_dir.join("remote-snapshots").join(safe_host_id)
+}
+
+/// Copy the remote `~/.clawpal/snapshots` directory into local storage so the
+/// history survives the host. Files already mirrored with the same size are
+/// skipped; returns how many were downloaded.
+#[tauri::command]
+pub async fn mirror_remote_history_local(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+) -> Result<usize, String> {
+    let paths = resolve_paths();
+    let mirror_dir = remote_history_mirror_dir(&paths, &host_id);
+    fs::create_dir_all(&mirror_dir).map_err(|e| e.to_string())?;
+
+    let entries = pool.sftp_list(&host_id, "~/.clawpal/snapshots").await?;
+    let mut mirrored = 0usize;
+    for entry in entries {
+        if entry.is_dir || entry.name.starts_with('.') || !entry.name.ends_with(".json") {
+            continue;
+        }
+        if entry.name.contains('/') || entry.name.contains("..") {
+            continue;
+        }
+        let local_path = mirror_dir.join(&entry.name);
+        if fs::metadata(&local_path).map(|m| m.len() == entry.size).unwrap_or(false) {
+            continue;
+        }
+        let text = pool
+            .sftp_read(&host_id, &format!("~/.clawpal/snapshots/{}", entry.name))
+            .await?;
+        write_text(&local_path, &text)?;
+        mirrored += 1;
+    }
+    Ok(mirrored)
+}
+
+/// List a host's locally mirrored snapshots, in the same shape as
+/// `remote_list_history`. Works without a connection to the host.
+#[tauri::command]
+pub fn list_remote_history_local(host_id: String) -> Result<Value, String> {
+    let paths = resolve_paths();
+    let mirror_dir = remote_history_mirror_dir(&paths, &host_id);
+    let mut items: Vec<Value> = Vec::new();
+    if let Ok(entries) = fs::read_dir(&mirror_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || !name.ends_with(".json") {
+                continue;
+            }
+            let mut item = remote_snapshot_item(&name);
+            item["path"] = Value::String(entry.path().to_string_lossy().to_string());
+            items.push(item);
+        }
+    }
+    sort_remote_snapshot_items(&mut items);
     Ok(serde_json::json!({ "items": items }))
 }
 
@@ -4626,6 +11408,7 @@ pub async fn remote_preview_rollback(
 #[tauri::command]
 pub async fn remote_rollback(
     pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
     host_id: String,
     snapshot_id: String,
 ) -> Result<ApplyResult, String> {
@@ -4636,6 +11419,7 @@ pub async fn remote_rollback(
 
     let current_text = pool.sftp_read(&host_id, "~/.openclaw/openclaw.json").await?;
     remote_write_config_with_snapshot(&pool, &host_id, &current_text, &target, "rollback").await?;
+    cache.invalidate(&remote_agents_overview_cache_key(&host_id));
 
     Ok(ApplyResult {
         ok: true,
@@ -4820,6 +11604,7 @@ pub async fn remote_list_discord_guild_channels(
 #[tauri::command]
 pub async fn remote_write_raw_config(
     pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
     host_id: String,
     content: String,
 ) -> Result<bool, String> {
@@ -4832,16 +11617,58 @@ pub async fn remote_write_raw_config(
         .await
         .unwrap_or_default();
     remote_write_config_with_snapshot(&pool, &host_id, &current, &next, "raw-edit").await?;
+    cache.invalidate(&remote_agents_overview_cache_key(&host_id));
     Ok(true)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentSessionCounts {
+    pub agent: String,
+    pub session_files: usize,
+    pub archive_files: usize,
+}
+
+/// Lightweight per-agent `.jsonl` counts for frequently polled overviews;
+/// `remote_analyze_sessions` is the heavy per-file variant.
+#[tauri::command]
+pub async fn remote_session_counts(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+) -> Result<Vec<AgentSessionCounts>, String> {
+    let script = r#"
+cd ~/.openclaw/agents 2>/dev/null || { echo '[]'; exit 0; }
+sep=""
+echo "["
+for agent_dir in */; do
+  [ -d "$agent_dir" ] || continue
+  agent="${agent_dir%/}"
+  safe_agent=$(printf '%s' "$agent" | sed 's/\\/\\\\/g; s/"/\\"/g')
+  s=$(ls -1 "$agent_dir"sessions 2>/dev/null | grep -c '\.jsonl$')
+  a=$(ls -1 "$agent_dir"sessions_archive 2>/dev/null | grep -c '\.jsonl$')
+  printf '%s{"agent":"%s","sessionFiles":%s,"archiveFiles":%s}' "$sep" "$safe_agent" "${s:-0}" "${a:-0}"
+  sep=","
+done
+echo "]"
+"#;
+    let result = pool.exec(&host_id, script).await?;
+    if result.stdout.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(result.stdout.trim())
+        .map_err(|e| format!("Failed to parse session counts: {e}"))
+}
+
 #[tauri::command]
 pub async fn remote_analyze_sessions(
     pool: State<'_, SshConnectionPool>,
     host_id: String,
+    op_id: Option<String>,
+    since_mtime: Option<u64>,
 ) -> Result<Vec<AgentSessionAnalysis>, String> {
     // Run a shell script via SSH that scans session files and outputs JSON.
-    // This is MUCH faster than doing per-file SFTP reads.
+    // This is MUCH faster than doing per-file SFTP reads. With `since_mtime`
+    // only files modified after that timestamp are scanned and returned.
     let script = r#"
 setopt nonomatch 2>/dev/null; shopt -s nullglob 2>/dev/null
 cd ~/.openclaw/agents 2>/dev/null || { echo '[]'; exit 0; }
@@ -4858,6 +11685,8 @@ for agent_dir in */; do
     [ -d "$dir" ] || continue
     for f in "$dir"/*.jsonl; do
       [ -f "$f" ] || continue
+      mtime=$(stat -c %Y "$f" 2>/dev/null || stat -f %m "$f" 2>/dev/null || echo 0)
+      __SINCE_FILTER__
       fname=$(basename "$f" .jsonl)
       safe_fname=$(printf '%s' "$fname" | sed 's/\\/\\\\/g; s/"/\\"/g')
       size=$(wc -c < "$f" 2>/dev/null | tr -d ' ')
@@ -4867,18 +11696,23 @@ for agent_dir in */; do
       [ -z "$user_msgs" ] && user_msgs=0
       asst_msgs=$(grep -c '"role":"assistant"' "$f" 2>/dev/null || true)
       [ -z "$asst_msgs" ] && asst_msgs=0
-      mtime=$(stat -c %Y "$f" 2>/dev/null || stat -f %m "$f" 2>/dev/null || echo 0)
       age_days=$(( (now - mtime) / 86400 ))
-      printf '%s{"agent":"%s","sessionId":"%s","sizeBytes":%s,"messageCount":%s,"userMessageCount":%s,"assistantMessageCount":%s,"ageDays":%s,"kind":"%s"}' \
-        "$sep" "$safe_agent" "$safe_fname" "$size" "$msgs" "$user_msgs" "$asst_msgs" "$age_days" "$kind"
+      printf '%s{"agent":"%s","sessionId":"%s","sizeBytes":%s,"messageCount":%s,"userMessageCount":%s,"assistantMessageCount":%s,"ageDays":%s,"kind":"%s","mtime":%s}' \
+        "$sep" "$safe_agent" "$safe_fname" "$size" "$msgs" "$user_msgs" "$asst_msgs" "$age_days" "$kind" "$mtime"
       sep=","
     done
   done
 done
 echo "]"
 "#;
+    // The mtime filter is only emitted when asked for: files whose mtime
+    // can't be read report 0 and must still be listed on a full scan.
+    let since_filter = since_mtime
+        .map(|since| format!("[ \"$mtime\" -le {since} ] && continue"))
+        .unwrap_or_default();
+    let script = script.replace("__SINCE_FILTER__", &since_filter);
 
-    let result = pool.exec(&host_id, script).await?;
+    let result = pool.exec_cancellable(&host_id, op_id.as_deref(), &script, false).await?;
     if result.exit_code != 0 && result.stdout.trim().is_empty() {
         // No agents directory — return empty
         return Ok(Vec::new());
@@ -4900,6 +11734,7 @@ echo "]"
         let assistant_message_count = val.get("assistantMessageCount").and_then(Value::as_u64).unwrap_or(0) as usize;
         let age_days = val.get("ageDays").and_then(Value::as_f64).unwrap_or(0.0);
         let kind = val.get("kind").and_then(Value::as_str).unwrap_or("sessions").to_string();
+        let mtime = val.get("mtime").and_then(Value::as_u64).unwrap_or(0);
 
         let category = if size_bytes < 500 || message_count == 0 {
             "empty"
@@ -4920,9 +11755,11 @@ echo "]"
             last_activity: None,
             age_days,
             total_tokens: 0,
+            tokens_estimated: false,
             model: None,
             category: category.to_string(),
             kind,
+            mtime,
         });
     }
 
@@ -5078,7 +11915,9 @@ pub async fn remote_preview_session(
     host_id: String,
     agent_id: String,
     session_id: String,
-) -> Result<Vec<Value>, String> {
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<SessionPreviewPage, String> {
     if agent_id.contains("..") || agent_id.contains('/') || session_id.contains("..") || session_id.contains('/') {
         return Err("invalid id".into());
     }
@@ -5091,48 +11930,25 @@ pub async fn remote_preview_session(
     ];
 
     let mut content = String::new();
-    for path in &paths {
-        if let Ok(c) = pool.sftp_read(&host_id, path).await {
-            content = c;
-            break;
-        }
-    }
-    if content.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    let mut messages: Vec<Value> = Vec::new();
-    for line in content.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let obj: Value = match serde_json::from_str(line) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        if obj.get("type").and_then(Value::as_str) == Some("message") {
-            let role = obj.pointer("/message/role").and_then(Value::as_str).unwrap_or("unknown");
-            let content_val = obj.pointer("/message/content")
-                .map(|c| {
-                    if let Some(arr) = c.as_array() {
-                        arr.iter()
-                            .filter_map(|item| item.get("text").and_then(Value::as_str))
-                            .collect::<Vec<_>>()
-                            .join("\n")
-                    } else if let Some(s) = c.as_str() {
-                        s.to_string()
-                    } else {
-                        String::new()
-                    }
-                })
-                .unwrap_or_default();
-            messages.push(serde_json::json!({
-                "role": role,
-                "content": content_val,
-            }));
+    for path in &paths {
+        if let Ok(c) = pool.sftp_read(&host_id, path).await {
+            content = c;
+            break;
         }
     }
-    Ok(messages)
+    Ok(session_preview_page(content.lines(), offset, limit))
+}
+
+#[tauri::command]
+pub async fn remote_export_session(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    agent_id: String,
+    session_id: String,
+    format: String,
+) -> Result<String, String> {
+    let page = remote_preview_session(pool, host_id, agent_id, session_id, None, None).await?;
+    render_session_transcript(&page.messages, &format)
 }
 
 #[tauri::command]
@@ -5147,10 +11963,103 @@ pub async fn remote_list_model_profiles(
         #[serde(default)]
         profiles: Vec<ModelProfile>,
     }
-    let parsed: Storage = serde_json::from_str(&content).unwrap_or(Storage { profiles: Vec::new() });
+    let mut parsed: Storage = serde_json::from_str(&content).unwrap_or(Storage { profiles: Vec::new() });
+    sort_favorites_first(&mut parsed.profiles);
     Ok(parsed.profiles)
 }
 
+#[tauri::command]
+pub async fn remote_toggle_model_profile_favorite(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    profile_id: String,
+) -> Result<bool, String> {
+    let content = pool.sftp_read(&host_id, "~/.clawpal/model-profiles.json").await?;
+    let mut storage: Value = serde_json::from_str(&content).map_err(|e| format!("Failed to parse remote profiles: {e}"))?;
+    let profile = storage
+        .get_mut("profiles")
+        .and_then(Value::as_array_mut)
+        .and_then(|profiles| {
+            profiles
+                .iter_mut()
+                .find(|p| p.get("id").and_then(Value::as_str) == Some(profile_id.as_str()))
+        })
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| "Profile not found".to_string())?;
+    let favorite = !profile.get("favorite").and_then(Value::as_bool).unwrap_or(false);
+    profile.insert("favorite".into(), Value::Bool(favorite));
+    let text = serde_json::to_string_pretty(&storage).map_err(|e| e.to_string())?;
+    pool.sftp_write(&host_id, "~/.clawpal/model-profiles.json", &text).await?;
+    Ok(favorite)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelProfileDiff {
+    pub local_only: Vec<ModelProfile>,
+    pub remote_only: Vec<ModelProfile>,
+    /// Pairs of (local, remote) profiles with the same id that point at a
+    /// different provider, model or base URL.
+    pub changed: Vec<(ModelProfile, ModelProfile)>,
+}
+
+/// Whether two profiles resolve to the same endpoint. Keys and display
+/// fields are ignored since they are expected to differ between machines.
+fn profiles_equivalent(a: &ModelProfile, b: &ModelProfile) -> bool {
+    let base = |p: &ModelProfile| {
+        p.base_url
+            .as_deref()
+            .map(|u| u.trim().trim_end_matches('/').to_string())
+            .filter(|u| !u.is_empty())
+    };
+    a.provider.trim().eq_ignore_ascii_case(b.provider.trim())
+        && a.model.trim() == b.model.trim()
+        && base(a) == base(b)
+}
+
+fn diff_profile_sets(local: Vec<ModelProfile>, remote: Vec<ModelProfile>) -> ModelProfileDiff {
+    let mut remote_by_id: HashMap<String, ModelProfile> =
+        remote.into_iter().map(|p| (p.id.clone(), p)).collect();
+    let mut diff = ModelProfileDiff {
+        local_only: Vec::new(),
+        remote_only: Vec::new(),
+        changed: Vec::new(),
+    };
+    for mut profile in local {
+        profile.api_key = None;
+        match remote_by_id.remove(&profile.id) {
+            Some(mut other) => {
+                if !profiles_equivalent(&profile, &other) {
+                    other.api_key = None;
+                    diff.changed.push((profile, other));
+                }
+            }
+            None => diff.local_only.push(profile),
+        }
+    }
+    diff.remote_only = remote_by_id
+        .into_values()
+        .map(|mut p| {
+            p.api_key = None;
+            p
+        })
+        .collect();
+    diff.remote_only.sort_by(|a, b| a.id.cmp(&b.id));
+    diff
+}
+
+/// Compare local model profiles with the remote host's. Reconcile by
+/// upserting `localOnly`/`changed` entries with `remote_upsert_model_profile`.
+#[tauri::command]
+pub async fn diff_model_profiles(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+) -> Result<ModelProfileDiff, String> {
+    let local = load_model_profiles(&resolve_paths());
+    let remote = remote_list_model_profiles(pool, host_id).await?;
+    Ok(diff_profile_sets(local, remote))
+}
+
 #[tauri::command]
 pub async fn remote_upsert_model_profile(
     pool: State<'_, SshConnectionPool>,
@@ -5183,13 +12092,13 @@ pub async fn remote_upsert_model_profile(
     let id = profile.id.clone();
     if let Some(existing) = storage.profiles.iter_mut().find(|p| p.id == id) {
         // Preserve existing API key if new one is empty
-        if profile.api_key.as_ref().map_or(true, |k| k.trim().is_empty()) {
+        if profile.api_key.as_ref().is_none_or(|k| k.trim().is_empty()) {
             profile.api_key = existing.api_key.clone();
         }
         *existing = profile.clone();
     } else {
         // New profile: if no API key provided, try to reuse from same-provider profile
-        if profile.api_key.as_ref().map_or(true, |k| k.trim().is_empty()) {
+        if profile.api_key.as_ref().is_none_or(|k| k.trim().is_empty()) {
             if let Some(donor) = storage.profiles.iter().find(|p| {
                 p.provider == profile.provider
                     && p.api_key.as_ref().is_some_and(|k| !k.trim().is_empty())
@@ -5212,6 +12121,7 @@ pub async fn remote_delete_model_profile(
     pool: State<'_, SshConnectionPool>,
     host_id: String,
     profile_id: String,
+    force: Option<bool>,
 ) -> Result<bool, String> {
     let content = pool.sftp_read(&host_id, "~/.clawpal/model-profiles.json").await
         .unwrap_or_else(|_| r#"{"profiles":[]}"#.to_string());
@@ -5224,6 +12134,13 @@ pub async fn remote_delete_model_profile(
     }
     fn default_version() -> u8 { 1 }
     let mut storage: Storage = serde_json::from_str(&content).unwrap_or(Storage { profiles: Vec::new(), version: 1 });
+    if !force.unwrap_or(false) {
+        if let Some(profile) = storage.profiles.iter().find(|p| p.id == profile_id) {
+            let raw = pool.sftp_read(&host_id, "~/.openclaw/openclaw.json").await?;
+            let cfg: Value = serde_json::from_str(&raw).map_err(|e| format!("Failed to parse config: {e}"))?;
+            ensure_profile_unused(&cfg, profile)?;
+        }
+    }
     let before = storage.profiles.len();
     storage.profiles.retain(|p| p.id != profile_id);
     if storage.profiles.len() == before {
@@ -5276,6 +12193,7 @@ pub async fn remote_resolve_api_keys(
 pub async fn remote_extract_model_profiles_from_config(
     pool: State<'_, SshConnectionPool>,
     host_id: String,
+    approved: Option<Vec<String>>,
 ) -> Result<ExtractModelProfilesResult, String> {
     let raw = pool.sftp_read(&host_id, "~/.openclaw/openclaw.json").await?;
     let cfg: Value = serde_json::from_str(&raw).map_err(|e| format!("Failed to parse remote config: {e}"))?;
@@ -5288,61 +12206,11 @@ pub async fn remote_extract_model_profiles_from_config(
         profiles: Vec<ModelProfile>,
     }
     let existing: StorageIn = serde_json::from_str(&profiles_raw).unwrap_or(StorageIn { profiles: Vec::new() });
-    let profiles = existing.profiles;
-
-    let bindings = collect_model_bindings(&cfg, &profiles);
-    let mut created = 0usize;
-    let mut reused = 0usize;
-    let mut skipped_invalid = 0usize;
-    let mut seen = HashSet::new();
-
-    let mut next_profiles = profiles;
-    let mut model_profile_map: HashMap<String, String> = HashMap::new();
-    for profile in &next_profiles {
-        model_profile_map.insert(normalize_model_ref(&profile_to_model_value(profile)), profile.id.clone());
-    }
-
-    for binding in bindings {
-        let scope_label = match binding.scope.as_str() {
-            "global" => "global".to_string(),
-            "agent" => format!("agent:{}", binding.scope_id),
-            "channel" => format!("channel:{}", binding.scope_id),
-            _ => binding.scope_id,
-        };
-        let Some(model_ref) = binding.model_value else { continue };
-        let model_ref = normalize_model_ref(&model_ref);
-        if model_ref.trim().is_empty() { continue }
-        if model_profile_map.contains_key(&model_ref) || seen.contains(&model_ref) {
-            reused += 1;
-            continue;
-        }
-        let mut parts = model_ref.splitn(2, '/');
-        let provider = parts.next().unwrap_or("").trim();
-        let model = parts.next().unwrap_or("").trim();
-        if provider.is_empty() || model.is_empty() {
-            skipped_invalid += 1;
-            continue;
-        }
-        let auth_ref = resolve_auth_ref_for_provider(&cfg, provider)
-            .unwrap_or_else(|| format!("{provider}:default"));
-        let base_url = resolve_model_provider_base_url(&cfg, provider);
-        let new_profile = ModelProfile {
-            id: uuid::Uuid::new_v4().to_string(),
-            name: format!("{scope_label} model profile"),
-            provider: provider.to_string(),
-            model: model.to_string(),
-            auth_ref,
-            api_key: None,
-            base_url,
-            description: Some(format!("Extracted from config ({scope_label})")),
-            enabled: true,
-        };
-        let key = profile_to_model_value(&new_profile);
-        model_profile_map.insert(normalize_model_ref(&key), new_profile.id.clone());
-        next_profiles.push(new_profile);
-        seen.insert(model_ref);
-        created += 1;
-    }
+    let mut next_profiles = existing.profiles;
+    let plan = plan_extracted_profiles(&cfg, &next_profiles);
+    let reused = plan.reused;
+    let skipped_invalid = plan.skipped_invalid;
+    let created = apply_extracted_profiles(plan, approved.as_deref(), &mut next_profiles);
 
     if created > 0 {
         #[derive(serde::Serialize)]
@@ -5424,7 +12292,9 @@ pub async fn run_openclaw_upgrade() -> Result<String, String> {
 #[tauri::command]
 pub async fn remote_run_openclaw_upgrade(
     pool: State<'_, SshConnectionPool>,
+    cache: tauri::State<'_, crate::cli_runner::CliCache>,
     host_id: String,
+    op_id: Option<String>,
 ) -> Result<String, String> {
     // Use the official install script with --no-prompt for non-interactive SSH.
     // The script handles npm prefix/permissions, bin links, and PATH fixups
@@ -5433,7 +12303,7 @@ pub async fn remote_run_openclaw_upgrade(
         .map(|r| r.stdout.trim().to_string()).unwrap_or_default();
 
     let install_cmd = "curl -fsSL --proto '=https' --tlsv1.2 https://openclaw.ai/install.sh | bash -s -- --no-prompt --no-onboard 2>&1";
-    let result = pool.exec_login(&host_id, install_cmd).await?;
+    let result = pool.exec_cancellable(&host_id, op_id.as_deref(), install_cmd, true).await?;
     let combined = if result.stderr.is_empty() {
         result.stdout.clone()
     } else {
@@ -5443,6 +12313,7 @@ pub async fn remote_run_openclaw_upgrade(
     if result.exit_code != 0 {
         return Err(combined);
     }
+    cache.invalidate(&remote_agents_overview_cache_key(&host_id));
 
     // Restart gateway after successful upgrade (best-effort)
     let _ = pool.exec_login(&host_id, "openclaw gateway restart 2>/dev/null || true").await;
@@ -5584,6 +12455,292 @@ pub fn delete_cron_job(job_id: String) -> Result<String, String> {
     }
 }
 
+/// Validate a 5-field (or 6-field, with leading seconds) cron expression.
+/// Accepts `*`, numbers, ranges, steps, lists and month/weekday names.
+fn validate_cron_expr(expr: &str) -> Result<(), String> {
+    const NAMES: [&str; 19] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+        "sun", "mon", "tue", "wed", "thu", "fri", "sat",
+    ];
+    let fields: Vec<&str> = expr.split_whitespace().collect();
+    let bounds: &[(u32, u32)] = match fields.len() {
+        5 => &[(0, 59), (0, 23), (1, 31), (1, 12), (0, 7)],
+        6 => &[(0, 59), (0, 59), (0, 23), (1, 31), (1, 12), (0, 7)],
+        n => return Err(format!("cron expression must have 5 or 6 fields, got {n}")),
+    };
+    let value_ok = |v: &str, (lo, hi): (u32, u32)| {
+        v.parse::<u32>().map(|n| n >= lo && n <= hi).unwrap_or(false)
+            || NAMES.contains(&v.to_ascii_lowercase().as_str())
+    };
+    for (field, &range) in fields.iter().zip(bounds) {
+        for part in field.split(',') {
+            let (base, step) = match part.split_once('/') {
+                Some((b, s)) => (b, Some(s)),
+                None => (part, None),
+            };
+            if step.is_some_and(|s| s.parse::<u32>().map(|n| n == 0).unwrap_or(true)) {
+                return Err(format!("invalid step in cron field: {field}"));
+            }
+            let ok = base == "*"
+                || match base.split_once('-') {
+                    Some((a, b)) => value_ok(a, range) && value_ok(b, range),
+                    None => value_ok(base, range),
+                };
+            if !ok {
+                return Err(format!("invalid cron field: {field}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Translate a job object (as returned by `list_cron_jobs`) into
+/// `openclaw cron add|update` arguments. Returns the args and the existing
+/// job id, if this is an update.
+fn cron_job_cli_args(job: &Value, agent_ids: &[String]) -> Result<(Vec<String>, Option<String>), String> {
+    let str_field = |key: &str| {
+        job.get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let job_id = str_field("jobId").or_else(|| str_field("id"));
+    let mut args: Vec<String> = match &job_id {
+        Some(id) => vec!["cron".into(), "update".into(), id.clone()],
+        None => vec!["cron".into(), "add".into()],
+    };
+    if let Some(name) = str_field("name") {
+        args.extend(["--name".into(), name]);
+    } else if job_id.is_none() {
+        return Err("cron job name is required".into());
+    }
+
+    match job.get("schedule") {
+        Some(schedule) => {
+            let kind = schedule.get("kind").and_then(Value::as_str).unwrap_or("cron");
+            match kind {
+                "cron" => {
+                    let expr = schedule
+                        .get("expr")
+                        .and_then(Value::as_str)
+                        .ok_or("cron schedule requires expr")?;
+                    validate_cron_expr(expr)?;
+                    args.extend(["--cron".into(), expr.trim().to_string()]);
+                    if let Some(tz) = schedule.get("tz").and_then(Value::as_str) {
+                        args.extend(["--tz".into(), tz.to_string()]);
+                    }
+                }
+                "every" => {
+                    let ms = schedule
+                        .get("everyMs")
+                        .and_then(Value::as_u64)
+                        .filter(|ms| *ms > 0)
+                        .ok_or("every schedule requires a positive everyMs")?;
+                    args.extend(["--every".into(), format!("{ms}ms")]);
+                }
+                "at" => {
+                    let at = schedule
+                        .get("at")
+                        .and_then(Value::as_str)
+                        .ok_or("at schedule requires at")?;
+                    args.extend(["--at".into(), at.to_string()]);
+                }
+                other => return Err(format!("unknown schedule kind: {other}")),
+            }
+        }
+        None if job_id.is_none() => return Err("cron job schedule is required".into()),
+        None => {}
+    }
+
+    if let Some(agent) = str_field("agentId") {
+        if !agent_ids.contains(&agent) {
+            return Err(format!("agent not found: {agent}"));
+        }
+        args.extend(["--agent".into(), agent]);
+    }
+    let prompt = str_field("prompt")
+        .or_else(|| str_field("message"))
+        .or_else(|| job.pointer("/payload/message").and_then(Value::as_str).map(str::to_string));
+    match prompt {
+        Some(prompt) => args.extend(["--message".into(), prompt]),
+        None if job_id.is_none() => return Err("cron job prompt is required".into()),
+        None => {}
+    }
+    if let Some(target) = str_field("sessionTarget") {
+        args.extend(["--session".into(), target]);
+    }
+    if let Some(desc) = str_field("description") {
+        args.extend(["--description".into(), desc]);
+    }
+    if job_id.is_none() && job.get("enabled").and_then(Value::as_bool) == Some(false) {
+        args.push("--disabled".into());
+    }
+    Ok((args, job_id))
+}
+
+/// Work out the id of the job just written: the CLI's JSON output when it
+/// has one, otherwise the newest job with the same name in jobs.json.
+fn resolve_upserted_cron_job_id(stdout: &str, jobs_text: Option<&str>, name: Option<&str>) -> Option<String> {
+    let from_stdout = stdout
+        .lines()
+        .filter_map(|l| serde_json::from_str::<Value>(l.trim()).ok())
+        .chain(serde_json::from_str::<Value>(stdout.trim()).ok())
+        .find_map(|v| {
+            v.get("id")
+                .or_else(|| v.get("jobId"))
+                .or_else(|| v.pointer("/job/id"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        });
+    from_stdout.or_else(|| {
+        let jobs = parse_cron_jobs(jobs_text?);
+        jobs.as_array()?
+            .iter()
+            .rev()
+            .find(|j| name.is_some() && j.get("name").and_then(Value::as_str) == name)
+            .and_then(|j| j.get("jobId").and_then(Value::as_str))
+            .map(str::to_string)
+    })
+}
+
+/// Create (no `jobId`) or update a cron job through the openclaw CLI and
+/// return its id.
+#[tauri::command]
+pub async fn upsert_cron_job(job: Value) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let paths = resolve_paths();
+        let cfg = read_openclaw_config(&paths)?;
+        let (args, job_id) = cron_job_cli_args(&job, &collect_agent_ids(&cfg))?;
+        let output = std::process::Command::new(resolve_openclaw_bin())
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to run openclaw: {e}"))?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if !output.status.success() {
+            return Err(strip_doctor_banner(&format!("{stdout}\n{stderr}")));
+        }
+        if let Some(id) = job_id {
+            return Ok(id);
+        }
+        let jobs_text = fs::read_to_string(paths.base_dir.join("cron").join("jobs.json")).ok();
+        resolve_upserted_cron_job_id(&stdout, jobs_text.as_deref(), job.get("name").and_then(Value::as_str))
+            .ok_or_else(|| "Cron job created but its id could not be determined".to_string())
+    })
+    .await
+    .map_err(|e| format!("Task failed: {e}"))?
+}
+
+#[cfg(test)]
+mod cron_job_tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_cron_expr() {
+        assert!(validate_cron_expr("*/15 9-17 * * MON-FRI").is_ok());
+        assert!(validate_cron_expr("0 0 1,15 * *").is_ok());
+        assert!(validate_cron_expr("0 0 * * * *").is_ok());
+        assert!(validate_cron_expr("61 * * * *").is_err());
+        assert!(validate_cron_expr("* * *").is_err());
+        assert!(validate_cron_expr("*/0 * * * *").is_err());
+    }
+
+    #[test]
+    fn test_cron_job_cli_args_add_requires_known_agent() {
+        let agents = vec!["main".to_string()];
+        let job = serde_json::json!({
+            "name": "daily",
+            "schedule": { "kind": "cron", "expr": "0 9 * * *" },
+            "agentId": "main",
+            "prompt": "summarize",
+        });
+        let (args, id) = cron_job_cli_args(&job, &agents).unwrap();
+        assert!(id.is_none());
+        assert_eq!(&args[..2], ["cron", "add"]);
+        assert!(args.windows(2).any(|w| w[0] == "--cron" && w[1] == "0 9 * * *"));
+
+        let mut other = job.clone();
+        other["agentId"] = Value::String("ghost".into());
+        assert!(cron_job_cli_args(&other, &agents).is_err());
+    }
+}
+
+/// Current `enabled` flag of a job in jobs.json text (jobs default to enabled).
+fn cron_job_enabled_state(jobs_text: &str, job_id: &str) -> Option<bool> {
+    let jobs = parse_cron_jobs(jobs_text);
+    jobs.as_array()?
+        .iter()
+        .find(|j| j.get("jobId").and_then(Value::as_str) == Some(job_id))
+        .map(|j| j.get("enabled").and_then(Value::as_bool).unwrap_or(true))
+}
+
+/// Rewrite the `enabled` flag of one job in jobs.json text, keeping whichever
+/// layout (wrapper, array or id map) the file already uses.
+fn set_cron_job_enabled_in_text(jobs_text: &str, job_id: &str, enabled: bool) -> Result<String, String> {
+    let mut doc: Value = serde_json::from_str(jobs_text).map_err(|e| format!("Failed to parse jobs.json: {e}"))?;
+    let wrapped = doc.get("jobs").is_some();
+    let jobs = if wrapped {
+        doc.get_mut("jobs").ok_or("jobs.json has no jobs list")?
+    } else {
+        &mut doc
+    };
+    let job = match jobs {
+        Value::Array(arr) => arr
+            .iter_mut()
+            .find(|j| j.get("id").or_else(|| j.get("jobId")).and_then(Value::as_str) == Some(job_id)),
+        Value::Object(map) => map.get_mut(job_id),
+        _ => None,
+    }
+    .ok_or_else(|| format!("cron job not found: {job_id}"))?;
+    job.as_object_mut()
+        .ok_or_else(|| format!("cron job {job_id} is not an object"))?
+        .insert("enabled".into(), Value::Bool(enabled));
+    serde_json::to_string_pretty(&doc).map_err(|e| e.to_string())
+}
+
+/// Whether CLI output says the subcommand does not exist, as opposed to the
+/// command running and failing.
+fn cli_reports_unknown_command(output: &str) -> bool {
+    let output = output.to_lowercase();
+    ["unknown command", "unknown subcommand", "unrecognized command", "unrecognized subcommand"]
+        .iter()
+        .any(|marker| output.contains(marker))
+}
+
+/// Pause or resume a cron job. Uses `openclaw cron enable|disable`, falling
+/// back to editing jobs.json only when the CLI lacks those subcommands.
+#[tauri::command]
+pub async fn set_cron_job_enabled(job_id: String, enabled: bool) -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let paths = resolve_paths();
+        let jobs_path = paths.base_dir.join("cron").join("jobs.json");
+        let jobs_text = fs::read_to_string(&jobs_path).map_err(|e| format!("Failed to read jobs.json: {e}"))?;
+        match cron_job_enabled_state(&jobs_text, &job_id) {
+            None => return Err(format!("cron job not found: {job_id}")),
+            Some(current) if current == enabled => return Ok(true),
+            Some(_) => {}
+        }
+        let sub = if enabled { "enable" } else { "disable" };
+        let output = std::process::Command::new(resolve_openclaw_bin())
+            .args(["cron", sub, &job_id])
+            .output()
+            .map_err(|e| format!("Failed to run openclaw cron {sub}: {e}"))?;
+        if output.status.success() {
+            return Ok(true);
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !cli_reports_unknown_command(&format!("{stderr}\n{stdout}")) {
+            return Err(format!("openclaw cron {sub} failed: {}", stderr.trim()));
+        }
+        write_text(&jobs_path, &set_cron_job_enabled_in_text(&jobs_text, &job_id, enabled)?)?;
+        Ok(true)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {e}"))?
+}
+
 // ---------------------------------------------------------------------------
 // Remote cron jobs
 // ---------------------------------------------------------------------------
@@ -5636,6 +12793,79 @@ pub async fn remote_delete_cron_job(pool: State<'_, SshConnectionPool>, host_id:
     }
 }
 
+#[tauri::command]
+pub async fn remote_upsert_cron_job(pool: State<'_, SshConnectionPool>, host_id: String, job: Value) -> Result<String, String> {
+    let config_text = pool.sftp_read(&host_id, "~/.openclaw/openclaw.json").await?;
+    let cfg: Value = json5::from_str(&config_text).map_err(|e| format!("Failed to parse remote config: {e}"))?;
+    let (args, job_id) = cron_job_cli_args(&job, &collect_agent_ids(&cfg))?;
+    let cmd = std::iter::once("openclaw".to_string())
+        .chain(args.iter().map(|a| shell_escape(a)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let result = pool.exec_login(&host_id, &cmd).await?;
+    if result.exit_code != 0 {
+        return Err(format!("{}\n{}", result.stdout, result.stderr));
+    }
+    if let Some(id) = job_id {
+        return Ok(id);
+    }
+    let jobs_text = pool.sftp_read(&host_id, "~/.openclaw/cron/jobs.json").await.ok();
+    resolve_upserted_cron_job_id(&result.stdout, jobs_text.as_deref(), job.get("name").and_then(Value::as_str))
+        .ok_or_else(|| "Cron job created but its id could not be determined".to_string())
+}
+
+#[tauri::command]
+pub async fn remote_set_cron_job_enabled(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    job_id: String,
+    enabled: bool,
+) -> Result<bool, String> {
+    let jobs_path = "~/.openclaw/cron/jobs.json";
+    let jobs_text = pool.sftp_read(&host_id, jobs_path).await?;
+    match cron_job_enabled_state(&jobs_text, &job_id) {
+        None => return Err(format!("cron job not found: {job_id}")),
+        Some(current) if current == enabled => return Ok(true),
+        Some(_) => {}
+    }
+    let sub = if enabled { "enable" } else { "disable" };
+    let result = pool
+        .exec_login(&host_id, &format!("openclaw cron {sub} {}", shell_escape(&job_id)))
+        .await?;
+    if result.exit_code == 0 {
+        return Ok(true);
+    }
+    if !cli_reports_unknown_command(&format!("{}\n{}", result.stderr, result.stdout)) {
+        return Err(format!("openclaw cron {sub} failed: {}", result.stderr.trim()));
+    }
+    let updated = set_cron_job_enabled_in_text(&jobs_text, &job_id, enabled)?;
+    pool.sftp_write(&host_id, jobs_path, &updated).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod cron_job_toggle_tests {
+    use super::*;
+
+    #[test]
+    fn test_set_cron_job_enabled_in_wrapper() {
+        let text = r#"{"version":1,"jobs":[{"id":"a","name":"x"},{"id":"b","enabled":false}]}"#;
+        assert_eq!(cron_job_enabled_state(text, "a"), Some(true));
+        assert_eq!(cron_job_enabled_state(text, "b"), Some(false));
+        let updated = set_cron_job_enabled_in_text(text, "a", false).unwrap();
+        assert_eq!(cron_job_enabled_state(&updated, "a"), Some(false));
+        assert!(set_cron_job_enabled_in_text(text, "zzz", true).is_err());
+    }
+
+    #[test]
+    fn test_cron_fallback_only_for_unknown_command() {
+        assert!(cli_reports_unknown_command("error: unknown command 'enable'"));
+        assert!(cli_reports_unknown_command("Error: Unrecognized subcommand: disable"));
+        assert!(!cli_reports_unknown_command("Error: gateway not running"));
+        assert!(!cli_reports_unknown_command(""));
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Watchdog management
 // ---------------------------------------------------------------------------
@@ -5795,73 +13025,228 @@ pub fn uninstall_watchdog() -> Result<bool, String> {
 // ---------------------------------------------------------------------------
 
 #[tauri::command]
-pub fn read_app_log(lines: Option<usize>) -> Result<String, String> {
-    crate::logging::read_log_tail("app.log", lines.unwrap_or(200))
+pub fn read_app_log(lines: Option<usize>, min_level: Option<String>) -> Result<String, String> {
+    let text = crate::logging::read_log_tail("app.log", lines.unwrap_or(200))?;
+    crate::logging::filter_min_level(text, min_level.as_deref())
+}
+
+#[tauri::command]
+pub fn read_error_log(lines: Option<usize>, min_level: Option<String>) -> Result<String, String> {
+    let text = crate::logging::read_log_tail("error.log", lines.unwrap_or(200))?;
+    crate::logging::filter_min_level(text, min_level.as_deref())
 }
 
 #[tauri::command]
-pub fn read_error_log(lines: Option<usize>) -> Result<String, String> {
-    crate::logging::read_log_tail("error.log", lines.unwrap_or(200))
+pub fn read_gateway_log(lines: Option<usize>, min_level: Option<String>) -> Result<String, String> {
+    let paths = crate::models::resolve_paths();
+    let path = paths.openclaw_dir.join("logs/gateway.log");
+    let text = crate::logging::read_file_tail(&path, lines.unwrap_or(200))?;
+    crate::logging::filter_min_level(text, min_level.as_deref())
 }
 
+/// Last `lines` lines of the live gateway log, read from the end of the file.
 #[tauri::command]
-pub fn read_gateway_log(lines: Option<usize>) -> Result<String, String> {
+pub fn tail_gateway_log(lines: usize) -> Result<String, String> {
     let paths = crate::models::resolve_paths();
     let path = paths.openclaw_dir.join("logs/gateway.log");
     if !path.exists() {
         return Ok(String::new());
     }
-    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let all_lines: Vec<&str> = content.lines().collect();
-    let n = lines.unwrap_or(200);
-    let start = all_lines.len().saturating_sub(n);
-    Ok(all_lines[start..].join("\n"))
+    crate::logging::read_last_lines(&path, lines)
 }
 
+/// Stop flag of the running gateway log follower, if any.
+static GATEWAY_LOG_FOLLOW: std::sync::Mutex<Option<std::sync::Arc<std::sync::atomic::AtomicBool>>> =
+    std::sync::Mutex::new(None);
+
+/// Complete lines appended to `path` since `offset`. A trailing line without
+/// a newline stays in `partial` until the writer finishes it; a file shorter
+/// than `offset` was truncated or rotated, so reading restarts from the top.
+fn read_appended_lines(path: &Path, offset: &mut u64, partial: &mut Vec<u8>) -> Vec<String> {
+    use std::io::{Read, Seek, SeekFrom};
+    let Ok(mut file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if len < *offset {
+        *offset = 0;
+        partial.clear();
+    }
+    if len == *offset || file.seek(SeekFrom::Start(*offset)).is_err() {
+        return Vec::new();
+    }
+    let mut bytes = Vec::new();
+    if file.read_to_end(&mut bytes).is_err() {
+        return Vec::new();
+    }
+    *offset += bytes.len() as u64;
+    partial.extend_from_slice(&bytes);
+    // Decode only up to the last newline, so a multi-byte character split
+    // across two reads is never decoded half-way.
+    let Some(last_newline) = partial.iter().rposition(|b| *b == b'\n') else {
+        return Vec::new();
+    };
+    let complete: Vec<u8> = partial.drain(..=last_newline).collect();
+    String::from_utf8_lossy(&complete).lines().map(str::to_string).collect()
+}
+
+/// Emit lines appended to the gateway log as `gateway-log:lines` events
+/// until `stop_tail_gateway_log` is called. Starting again replaces any
+/// running follower.
 #[tauri::command]
-pub fn read_gateway_error_log(lines: Option<usize>) -> Result<String, String> {
+pub fn follow_gateway_log(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    use tauri::Emitter;
     let paths = crate::models::resolve_paths();
-    let path = paths.openclaw_dir.join("logs/gateway.err.log");
-    if !path.exists() {
-        return Ok(String::new());
+    let path = paths.openclaw_dir.join("logs/gateway.log");
+    let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    if let Some(previous) = GATEWAY_LOG_FOLLOW.lock().unwrap().replace(stop.clone()) {
+        previous.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+    std::thread::spawn(move || {
+        let mut offset = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let mut partial = Vec::new();
+        while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+            let lines = read_appended_lines(&path, &mut offset, &mut partial);
+            if !lines.is_empty() {
+                let _ = app_handle.emit("gateway-log:lines", &lines);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(500));
+        }
+    });
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn stop_tail_gateway_log() -> Result<bool, String> {
+    match GATEWAY_LOG_FOLLOW.lock().unwrap().take() {
+        Some(stop) => {
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
     }
-    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let all_lines: Vec<&str> = content.lines().collect();
-    let n = lines.unwrap_or(200);
-    let start = all_lines.len().saturating_sub(n);
-    Ok(all_lines[start..].join("\n"))
+}
+
+#[cfg(test)]
+mod log_follow_tests {
+    use super::*;
+
+    #[test]
+    fn split_multibyte_characters_survive_between_reads() {
+        use std::io::Write;
+        let path = std::env::temp_dir().join(format!("clawpal-follow-test-{}.log", std::process::id()));
+        fs::write(&path, b"a\nh\xC3").unwrap();
+        let (mut offset, mut partial) = (0, Vec::new());
+        assert_eq!(read_appended_lines(&path, &mut offset, &mut partial), vec!["a".to_string()]);
+        fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"\xA9llo\n").unwrap();
+        assert_eq!(read_appended_lines(&path, &mut offset, &mut partial), vec!["h\u{e9}llo".to_string()]);
+        assert!(partial.is_empty());
+        let _ = fs::remove_file(&path);
+    }
+}
+
+#[tauri::command]
+pub fn read_gateway_error_log(lines: Option<usize>, min_level: Option<String>) -> Result<String, String> {
+    let paths = crate::models::resolve_paths();
+    let path = paths.openclaw_dir.join("logs/gateway.err.log");
+    let text = crate::logging::read_file_tail(&path, lines.unwrap_or(200))?;
+    crate::logging::filter_min_level(text, min_level.as_deref())
 }
 
 #[tauri::command]
-pub async fn remote_read_app_log(pool: State<'_, SshConnectionPool>, host_id: String, lines: Option<usize>) -> Result<String, String> {
+pub async fn remote_read_app_log(pool: State<'_, SshConnectionPool>, host_id: String, lines: Option<usize>, min_level: Option<String>) -> Result<String, String> {
     let n = lines.unwrap_or(200);
-    let cmd = format!("tail -n {n} ~/.clawpal/logs/app.log 2>/dev/null || echo ''");
+    let cmd = remote_log_tail_command("~/.clawpal/logs/app.log", n);
     let result = pool.exec(&host_id, &cmd).await?;
-    Ok(result.stdout)
+    crate::logging::filter_min_level(result.stdout, min_level.as_deref())
 }
 
 #[tauri::command]
-pub async fn remote_read_error_log(pool: State<'_, SshConnectionPool>, host_id: String, lines: Option<usize>) -> Result<String, String> {
+pub async fn remote_read_error_log(pool: State<'_, SshConnectionPool>, host_id: String, lines: Option<usize>, min_level: Option<String>) -> Result<String, String> {
     let n = lines.unwrap_or(200);
-    let cmd = format!("tail -n {n} ~/.clawpal/logs/error.log 2>/dev/null || echo ''");
+    let cmd = remote_log_tail_command("~/.clawpal/logs/error.log", n);
     let result = pool.exec(&host_id, &cmd).await?;
-    Ok(result.stdout)
+    crate::logging::filter_min_level(result.stdout, min_level.as_deref())
 }
 
 #[tauri::command]
-pub async fn remote_read_gateway_log(pool: State<'_, SshConnectionPool>, host_id: String, lines: Option<usize>) -> Result<String, String> {
+pub async fn remote_read_gateway_log(pool: State<'_, SshConnectionPool>, host_id: String, lines: Option<usize>, min_level: Option<String>) -> Result<String, String> {
     let n = lines.unwrap_or(200);
-    let cmd = format!("tail -n {n} ~/.openclaw/logs/gateway.log 2>/dev/null || echo ''");
+    let cmd = remote_log_tail_command("~/.openclaw/logs/gateway.log", n);
     let result = pool.exec(&host_id, &cmd).await?;
-    Ok(result.stdout)
+    crate::logging::filter_min_level(result.stdout, min_level.as_deref())
 }
 
 #[tauri::command]
-pub async fn remote_read_gateway_error_log(pool: State<'_, SshConnectionPool>, host_id: String, lines: Option<usize>) -> Result<String, String> {
+pub async fn remote_read_gateway_error_log(pool: State<'_, SshConnectionPool>, host_id: String, lines: Option<usize>, min_level: Option<String>) -> Result<String, String> {
     let n = lines.unwrap_or(200);
-    let cmd = format!("tail -n {n} ~/.openclaw/logs/gateway.err.log 2>/dev/null || echo ''");
+    let cmd = remote_log_tail_command("~/.openclaw/logs/gateway.err.log", n);
     let result = pool.exec(&host_id, &cmd).await?;
-    Ok(result.stdout)
+    crate::logging::filter_min_level(result.stdout, min_level.as_deref())
+}
+
+/// Log files ClawPal reads and rotates, relative to the user's home.
+const MANAGED_LOGS: &[&str] = &[
+    ".clawpal/logs/app.log",
+    ".clawpal/logs/error.log",
+    ".clawpal/watchdog/watchdog.log",
+    ".openclaw/logs/gateway.log",
+    ".openclaw/logs/gateway.err.log",
+];
+
+/// `tail` over the rotated copies (oldest first) followed by the live file.
+fn remote_log_tail_command(path: &str, lines: usize) -> String {
+    let mut files: Vec<String> = (1..=crate::logging::LOG_ROTATIONS)
+        .rev()
+        .map(|i| format!("{path}.{i}"))
+        .collect();
+    files.push(path.to_string());
+    format!("cat {} 2>/dev/null | tail -n {lines}", files.join(" "))
+}
+
+#[tauri::command]
+pub fn rotate_logs(max_bytes: u64) -> Result<Vec<String>, String> {
+    let paths = resolve_paths();
+    let candidates = [
+        crate::logging::logs_dir().join("app.log"),
+        crate::logging::logs_dir().join("error.log"),
+        paths.clawpal_dir.join("watchdog").join("watchdog.log"),
+        paths.openclaw_dir.join("logs").join("gateway.log"),
+        paths.openclaw_dir.join("logs").join("gateway.err.log"),
+    ];
+    let mut rotated = Vec::new();
+    for path in candidates {
+        if crate::logging::rotate_file(&path, max_bytes)? {
+            rotated.push(path.to_string_lossy().to_string());
+        }
+    }
+    Ok(rotated)
+}
+
+#[tauri::command]
+pub async fn remote_rotate_logs(
+    pool: State<'_, SshConnectionPool>,
+    host_id: String,
+    max_bytes: u64,
+) -> Result<Vec<String>, String> {
+    let keep = crate::logging::LOG_ROTATIONS;
+    let mut script = String::new();
+    for log in MANAGED_LOGS {
+        let path = format!("\"$HOME/{log}\"");
+        script.push_str(&format!(
+            "f={path}; if [ -f \"$f\" ] && [ $(wc -c < \"$f\") -gt {max_bytes} ]; then \
+             i={keep}; while [ $i -gt 1 ]; do p=$((i-1)); [ -f \"$f.$p\" ] && mv \"$f.$p\" \"$f.$i\"; i=$p; done; \
+             cp \"$f\" \"$f.1\" && : > \"$f\" && echo \"$f\"; fi; "
+        ));
+    }
+    let result = pool.exec(&host_id, &script).await?;
+    Ok(result
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
 }
 
 // ---------------------------------------------------------------------------
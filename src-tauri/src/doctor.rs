@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
+use crate::commands::{collect_agent_ids, collect_channel_nodes};
 use crate::config_io::read_openclaw_config;
 use crate::models::OpenClawPaths;
 use regex::Regex;
@@ -15,6 +18,52 @@ pub struct DoctorIssue {
     pub fix_hint: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionConsistency {
+    pub agent: String,
+    pub tracked_count: usize,
+    pub file_count: usize,
+    /// Session ids in sessions.json with no jsonl file on disk.
+    pub orphaned_metadata: Vec<String>,
+    /// jsonl session ids that sessions.json does not know about.
+    pub untracked_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityCheck {
+    pub agent_id: String,
+    pub path: String,
+    pub exists: bool,
+    pub name: Option<String>,
+    pub emoji: Option<String>,
+    /// Raw file content, only included when the name failed to parse.
+    pub raw_content: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayConfigCheck {
+    pub running: bool,
+    pub pid: Option<u32>,
+    /// Config file the gateway process resolved, when it could be determined.
+    pub loaded_path: Option<String>,
+    /// Where `loaded_path` came from: a CLI flag, an env var, or the default.
+    pub source: Option<String>,
+    pub expected_path: String,
+    pub mismatch: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceLocationIssue {
+    pub agent_id: String,
+    pub workspace: String,
+    pub resolved_path: String,
+    pub reason: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DoctorReport {
     pub ok: bool,
@@ -63,6 +112,10 @@ pub fn apply_auto_fixes(paths: &OpenClawPaths, issue_ids: &[String]) -> Vec<Stri
         fixed.push("field.port".into());
     }
 
+    if issue_ids.iter().any(|id| id == "bindings.missing_agent") && rebind_missing_agents_to_main(&mut current) > 0 {
+        fixed.push("bindings.missing_agent".into());
+    }
+
     let maybe_json = serde_json::to_string_pretty(&current).unwrap_or_else(|_| "{}".into());
     if !fixed.is_empty() {
         let _ = clean_and_write_json(paths, &maybe_json);
@@ -70,12 +123,697 @@ pub fn apply_auto_fixes(paths: &OpenClawPaths, issue_ids: &[String]) -> Vec<Stri
     fixed
 }
 
+/// Bindings whose `agentId` is neither in `agents.list` nor the implicit
+/// "main" agent, as (binding index, agent id).
+pub(crate) fn bindings_with_missing_agents(cfg: &Value) -> Vec<(usize, String)> {
+    let Some(bindings) = cfg.get("bindings").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    let known: BTreeSet<&str> = cfg
+        .pointer("/agents/list")
+        .and_then(Value::as_array)
+        .map(|list| list.iter().filter_map(|a| a.get("id").and_then(Value::as_str)).collect())
+        .unwrap_or_default();
+    bindings
+        .iter()
+        .enumerate()
+        .filter_map(|(index, binding)| {
+            let agent_id = binding.get("agentId").and_then(Value::as_str)?;
+            (agent_id != "main" && !known.contains(agent_id)).then(|| (index, agent_id.to_string()))
+        })
+        .collect()
+}
+
+/// Point bindings at missing agents back to "main", the same way
+/// `delete_agent` does. Returns how many bindings changed.
+fn rebind_missing_agents_to_main(cfg: &mut Value) -> usize {
+    let dangling = bindings_with_missing_agents(cfg);
+    let Some(bindings) = cfg.get_mut("bindings").and_then(Value::as_array_mut) else {
+        return 0;
+    };
+    for (index, _) in &dangling {
+        bindings[*index]["agentId"] = Value::String("main".into());
+    }
+    dangling.len()
+}
+
 fn clean_and_write_json(paths: &OpenClawPaths, text: &str) -> Result<(), String> {
     let trailing = Regex::new(r",(\s*[}\]])").map_err(|e| e.to_string())?;
     let normalized = trailing.replace_all(text, "$1");
     crate::config_io::write_text(&paths.config_path, normalized.as_ref())
 }
 
+/// Compare the session ids recorded in each agent's sessions.json with the
+/// `.jsonl` files actually present in its sessions directory.
+pub fn check_session_consistency(agents_root: &Path, agent_id: Option<&str>) -> Vec<SessionConsistency> {
+    let agents: Vec<String> = match agent_id {
+        Some(id) => vec![id.to_string()],
+        None => std::fs::read_dir(agents_root)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| e.path().is_dir())
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    let mut out = Vec::new();
+    for agent in agents {
+        let sessions_dir = agents_root.join(&agent).join("sessions");
+        let meta: serde_json::Map<String, Value> = std::fs::read_to_string(sessions_dir.join("sessions.json"))
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        let tracked: BTreeSet<String> = meta
+            .values()
+            .filter_map(|v| v.get("sessionId").and_then(Value::as_str))
+            .map(str::to_string)
+            .collect();
+
+        let mut files = BTreeSet::new();
+        if let Ok(entries) = std::fs::read_dir(&sessions_dir) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Some(id) = name.strip_suffix(".jsonl") else {
+                    continue;
+                };
+                // Topic files ("<id>-topic-<n>") belong to their base session.
+                let base = id.split("-topic-").next().unwrap_or(id);
+                files.insert(base.to_string());
+            }
+        }
+
+        out.push(SessionConsistency {
+            agent,
+            tracked_count: tracked.len(),
+            file_count: files.len(),
+            orphaned_metadata: tracked.difference(&files).cloned().collect(),
+            untracked_files: files.difference(&tracked).cloned().collect(),
+        });
+    }
+    out
+}
+
+/// Each configured agent with its workspace (own or inherited default).
+/// An empty agent list is treated as the implicit `main` agent.
+pub(crate) fn agent_workspaces(cfg: &Value) -> Vec<(String, Option<&str>)> {
+    let default_workspace = cfg
+        .pointer("/agents/defaults/workspace")
+        .or_else(|| cfg.pointer("/agents/default/workspace"))
+        .and_then(Value::as_str);
+    match cfg.pointer("/agents/list").and_then(Value::as_array) {
+        Some(list) if !list.is_empty() => list
+            .iter()
+            .filter_map(|agent| {
+                let id = agent.get("id").and_then(Value::as_str)?;
+                let ws = agent.get("workspace").and_then(Value::as_str).or(default_workspace);
+                Some((id.to_string(), ws))
+            })
+            .collect(),
+        _ => vec![("main".to_string(), default_workspace)],
+    }
+}
+
+/// Parse the `- Name: ...` / `- Emoji: ...` lines written by
+/// `setup_agent_identity`. Anything else yields `None` for that field.
+pub fn parse_identity_content(text: &str) -> (Option<String>, Option<String>) {
+    let mut name = None;
+    let mut emoji = None;
+    for line in text.lines() {
+        let Some(rest) = line.trim().strip_prefix('-') else {
+            continue;
+        };
+        let Some((key, value)) = rest.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        match key.trim().to_ascii_lowercase().as_str() {
+            "name" if name.is_none() => name = Some(value.to_string()),
+            "emoji" if emoji.is_none() => emoji = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    (name, emoji)
+}
+
+/// Inverse of `parse_identity_content`: rewrite the first `- Name:` and
+/// `- Emoji:` lines in place, appending them if missing. `None` leaves a
+/// field untouched; an empty emoji removes its line. Other lines are kept.
+pub fn update_identity_content(text: &str, name: Option<&str>, emoji: Option<&str>) -> String {
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let find = |lines: &[String], field: &str| {
+        lines.iter().position(|line| {
+            line.trim()
+                .strip_prefix('-')
+                .and_then(|rest| rest.split_once(':'))
+                .is_some_and(|(key, _)| key.trim().eq_ignore_ascii_case(field))
+        })
+    };
+
+    if let Some(name) = name {
+        let line = format!("- Name: {name}");
+        match find(&lines, "name") {
+            Some(idx) => lines[idx] = line,
+            None => lines.insert(0, line),
+        }
+    }
+    if let Some(emoji) = emoji {
+        let existing = find(&lines, "emoji");
+        if emoji.is_empty() {
+            if let Some(idx) = existing {
+                lines.remove(idx);
+            }
+        } else {
+            let line = format!("- Emoji: {emoji}");
+            match existing {
+                Some(idx) => lines[idx] = line,
+                None => {
+                    let after_name = find(&lines, "name").map(|idx| idx + 1).unwrap_or(0);
+                    lines.insert(after_name, line);
+                }
+            }
+        }
+    }
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Read IDENTITY.md from every configured agent's workspace and report
+/// whether the name and emoji parsed.
+pub fn check_agent_identities(cfg: &Value) -> Vec<IdentityCheck> {
+    let mut out = Vec::new();
+    for (agent_id, workspace) in agent_workspaces(cfg) {
+        let Some(workspace) = workspace else {
+            continue;
+        };
+        let path = Path::new(&shellexpand::tilde(workspace).to_string()).join("IDENTITY.md");
+        let content = std::fs::read_to_string(&path).ok();
+        let (name, emoji) = content.as_deref().map(parse_identity_content).unwrap_or((None, None));
+        let raw_content = if name.is_none() { content.clone() } else { None };
+        out.push(IdentityCheck {
+            agent_id,
+            path: path.to_string_lossy().to_string(),
+            exists: content.is_some(),
+            name,
+            emoji,
+            raw_content,
+        });
+    }
+    out
+}
+
+pub fn gateway_pid() -> Option<u32> {
+    let output = std::process::Command::new("pgrep")
+        .args(["-f", "[o]penclaw-gateway"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.trim().parse().ok())
+}
+
+/// (pid, command) pairs from `lsof -F pc` output.
+fn parse_lsof_listeners(text: &str) -> Vec<(u32, String)> {
+    let mut out = Vec::new();
+    let mut pid = None;
+    for line in text.lines() {
+        if let Some(p) = line.strip_prefix('p') {
+            pid = p.trim().parse().ok();
+        } else if let (Some(cmd), Some(p)) = (line.strip_prefix('c'), pid) {
+            out.push((p, cmd.trim().to_string()));
+        }
+    }
+    out
+}
+
+/// A process listening on `port` that is not the openclaw gateway, with the
+/// best command line we can get for it.
+pub fn foreign_port_listener(port: u16) -> Option<(u32, String)> {
+    let output = std::process::Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{port}"), "-sTCP:LISTEN", "-F", "pc"])
+        .output()
+        .ok()?;
+    let gateway = gateway_pid();
+    parse_lsof_listeners(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|(pid, _)| Some(*pid) != gateway)
+        .map(|(pid, cmd)| {
+            let (args, _) = process_args_and_env(pid);
+            let full = if args.is_empty() { cmd } else { args.join(" ") };
+            (pid, full)
+        })
+        .find(|(_, cmd)| !cmd.to_lowercase().contains("openclaw"))
+}
+
+/// Command-line arguments and environment of a running process.
+fn process_args_and_env(pid: u32) -> (Vec<String>, HashMap<String, String>) {
+    let split_nul = |bytes: Vec<u8>| -> Vec<String> {
+        bytes
+            .split(|b| *b == 0)
+            .filter(|part| !part.is_empty())
+            .map(|part| String::from_utf8_lossy(part).to_string())
+            .collect()
+    };
+    let parse_env = |entries: &[String]| -> HashMap<String, String> {
+        entries
+            .iter()
+            .filter_map(|entry| entry.split_once('='))
+            .filter(|(key, _)| !key.is_empty() && key.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    };
+
+    let proc_dir = PathBuf::from(format!("/proc/{pid}"));
+    if proc_dir.exists() {
+        let args = std::fs::read(proc_dir.join("cmdline")).map(split_nul).unwrap_or_default();
+        let env = std::fs::read(proc_dir.join("environ"))
+            .map(split_nul)
+            .map(|entries| parse_env(&entries))
+            .unwrap_or_default();
+        return (args, env);
+    }
+
+    // macOS: `ps eww` appends the environment to the command line.
+    let output = std::process::Command::new("ps")
+        .args(["eww", "-o", "command=", "-p", &pid.to_string()])
+        .output();
+    let text = output.map(|o| String::from_utf8_lossy(&o.stdout).to_string()).unwrap_or_default();
+    let tokens: Vec<String> = text.split_whitespace().map(str::to_string).collect();
+    let env = parse_env(&tokens);
+    let args = tokens.into_iter().take_while(|t| !t.contains('=') || t.starts_with('-')).collect();
+    (args, env)
+}
+
+/// Work out which config a gateway with these args/env would load, using the
+/// same precedence as the CLI: `--config`, then env overrides, then `$HOME`.
+fn resolve_gateway_config(args: &[String], env: &HashMap<String, String>) -> Option<(PathBuf, String)> {
+    let expand = |raw: &str| PathBuf::from(shellexpand::tilde(raw).to_string());
+    for (i, arg) in args.iter().enumerate() {
+        if let Some(value) = arg.strip_prefix("--config=") {
+            return Some((expand(value), "--config".into()));
+        }
+        if arg == "--config" {
+            if let Some(value) = args.get(i + 1) {
+                return Some((expand(value), "--config".into()));
+            }
+        }
+    }
+    if let Some(value) = env.get("OPENCLAW_CONFIG_PATH") {
+        return Some((expand(value), "OPENCLAW_CONFIG_PATH".into()));
+    }
+    if let Some(value) = env.get("OPENCLAW_STATE_DIR") {
+        return Some((expand(value).join("openclaw.json"), "OPENCLAW_STATE_DIR".into()));
+    }
+    if let Some(value) = env.get("OPENCLAW_HOME") {
+        return Some((expand(value).join(".openclaw").join("openclaw.json"), "OPENCLAW_HOME".into()));
+    }
+    env.get("HOME")
+        .map(|home| (PathBuf::from(home).join(".openclaw").join("openclaw.json"), "default".into()))
+}
+
+/// Inspect the running gateway process and compare the config it loaded with
+/// the one ClawPal edits.
+pub fn check_gateway_config(paths: &OpenClawPaths) -> GatewayConfigCheck {
+    let expected = paths.config_path.clone();
+    let Some(pid) = gateway_pid() else {
+        return GatewayConfigCheck {
+            running: false,
+            pid: None,
+            loaded_path: None,
+            source: None,
+            expected_path: expected.to_string_lossy().to_string(),
+            mismatch: false,
+        };
+    };
+    let (args, env) = process_args_and_env(pid);
+    let resolved = resolve_gateway_config(&args, &env);
+    let same_file = |a: &Path, b: &Path| match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    };
+    let mismatch = resolved
+        .as_ref()
+        .is_some_and(|(loaded, _)| !same_file(loaded, &expected));
+    GatewayConfigCheck {
+        running: true,
+        pid: Some(pid),
+        loaded_path: resolved.as_ref().map(|(p, _)| p.to_string_lossy().to_string()),
+        source: resolved.map(|(_, source)| source),
+        expected_path: expected.to_string_lossy().to_string(),
+        mismatch,
+    }
+}
+
+/// The agent that receives unbound traffic: the one marked `default: true`,
+/// otherwise the first in `agents.list` (or the implicit `main`).
+pub(crate) fn default_agent_id(cfg: &Value) -> String {
+    let list = cfg.pointer("/agents/list").and_then(Value::as_array);
+    list.and_then(|agents| {
+        agents
+            .iter()
+            .find(|a| a.get("default").and_then(Value::as_bool) == Some(true))
+            .or_else(|| agents.first())
+    })
+    .and_then(|a| a.get("id").and_then(Value::as_str))
+    .unwrap_or("main")
+    .to_string()
+}
+
+/// Agents that no binding routes to and that aren't the default agent, so
+/// they can never receive a message.
+pub fn find_unreachable_agents_in(cfg: &Value) -> Vec<String> {
+    let bound: BTreeSet<&str> = cfg
+        .get("bindings")
+        .and_then(Value::as_array)
+        .map(|bindings| bindings.iter().filter_map(|b| b.get("agentId").and_then(Value::as_str)).collect())
+        .unwrap_or_default();
+    let default_id = default_agent_id(cfg);
+    collect_agent_ids(cfg)
+        .into_iter()
+        .filter(|id| id != "main" && *id != default_id && !bound.contains(id.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod unreachable_agent_tests {
+    use super::*;
+
+    #[test]
+    fn unreachable_agents_skip_bound_and_default_agents() {
+        let cfg = serde_json::json!({
+            "agents": { "list": [
+                { "id": "main" },
+                { "id": "ops", "default": true },
+                { "id": "helper" },
+                { "id": "idle" }
+            ] },
+            "bindings": [{ "agentId": "helper", "match": { "channel": "discord" } }]
+        });
+        assert_eq!(find_unreachable_agents_in(&cfg), vec!["idle".to_string()]);
+        assert!(find_unreachable_agents_in(&serde_json::json!({})).is_empty());
+    }
+}
+
+pub(crate) fn escape_pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Keys the gateway recognizes in each section we check. Sections not listed
+/// here (e.g. per-channel settings) are free-form and never flagged.
+const KNOWN_CONFIG_KEYS: &[(&str, &[&str])] = &[
+    ("", &[
+        "gateway", "agents", "channels", "models", "bindings", "auth", "cron",
+        "meta", "wizard", "env", "logging", "messages", "commands", "session",
+        "tools", "plugins", "skills", "hooks", "browser", "ui", "update",
+    ]),
+    ("/agents", &["defaults", "default", "list"]),
+    ("/models", &["providers", "mode"]),
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnknownConfigKey {
+    pub pointer: String,
+    pub key: String,
+    pub suggestion: Option<String>,
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+/// Keys in the checked sections that the gateway would silently ignore,
+/// each with the closest known key when it is within a couple of edits.
+pub fn find_unknown_config_keys_in(cfg: &Value) -> Vec<UnknownConfigKey> {
+    let mut out = Vec::new();
+    for (section, known) in KNOWN_CONFIG_KEYS {
+        let node = if section.is_empty() { Some(cfg) } else { cfg.pointer(section) };
+        let Some(obj) = node.and_then(Value::as_object) else {
+            continue;
+        };
+        for key in obj.keys() {
+            if known.contains(&key.as_str()) {
+                continue;
+            }
+            let suggestion = known
+                .iter()
+                .map(|k| (edit_distance(&key.to_lowercase(), k), *k))
+                .filter(|(d, _)| *d <= 2)
+                .min_by_key(|(d, _)| *d)
+                .map(|(_, k)| k.to_string());
+            out.push(UnknownConfigKey {
+                pointer: format!("{section}/{}", escape_pointer_segment(key)),
+                key: key.clone(),
+                suggestion,
+            });
+        }
+    }
+    out
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedBinding {
+    pub index: usize,
+    pub agent_id: Option<String>,
+    pub channel: String,
+    pub peer_id: Option<String>,
+}
+
+/// Bindings whose `match.channel` names a platform that has no entry under
+/// `channels`, so messages for it can never be routed.
+pub fn find_orphaned_bindings(cfg: &Value) -> Vec<OrphanedBinding> {
+    let Some(bindings) = cfg.get("bindings").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    // Every channel node lives under `channels.<platform>`, so its second
+    // path segment names a configured platform.
+    let platforms: BTreeSet<String> = collect_channel_nodes(cfg)
+        .into_iter()
+        .filter_map(|node| node.path.split('.').nth(1).map(str::to_string))
+        .collect();
+
+    let mut out = Vec::new();
+    for (index, binding) in bindings.iter().enumerate() {
+        let Some(channel) = binding.pointer("/match/channel").and_then(Value::as_str) else {
+            continue;
+        };
+        if platforms.contains(channel) {
+            continue;
+        }
+        out.push(OrphanedBinding {
+            index,
+            agent_id: binding.get("agentId").and_then(Value::as_str).map(str::to_string),
+            channel: channel.to_string(),
+            peer_id: binding.pointer("/match/peer/id").and_then(Value::as_str).map(str::to_string),
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod orphaned_binding_tests {
+    use super::*;
+
+    #[test]
+    fn orphaned_bindings_follow_the_configured_channel_nodes() {
+        let cfg = serde_json::json!({
+            "channels": {
+                "discord": { "accounts": { "a1": { "token": "t" } } },
+                "slack": {}
+            },
+            "bindings": [
+                { "agentId": "helper", "match": { "channel": "discord" } },
+                { "agentId": "helper", "match": { "channel": "slack", "peer": { "id": "C1" } } },
+                { "agentId": "main", "match": { "channel": "telegram" } }
+            ]
+        });
+        let orphaned = find_orphaned_bindings(&cfg);
+        let channels: Vec<&str> = orphaned.iter().map(|b| b.channel.as_str()).collect();
+        assert_eq!(channels, vec!["slack", "telegram"]);
+        assert_eq!(orphaned[0].peer_id.as_deref(), Some("C1"));
+    }
+}
+
+/// Structural checks over the parsed config, plus the IDENTITY.md files of
+/// the workspaces it names. Pushes issues and returns the score penalty, so
+/// callers can run them against in-memory candidates as well as the file on
+/// disk.
+fn check_config_structure(cfg: &Value, issues: &mut Vec<DoctorIssue>) -> i32 {
+    let mut penalty = 0;
+    if cfg.get("agents").is_none() {
+        issues.push(DoctorIssue {
+            id: "field.agents".into(),
+            code: "required.field".into(),
+            severity: "warn".into(),
+            message: "Missing agents field; recommend initializing defaults".into(),
+            auto_fixable: true,
+            fix_hint: Some("Add agents.defaults with safe minimal values".into()),
+        });
+        penalty += 10;
+    }
+
+    let malformed: Vec<String> = check_agent_identities(cfg)
+        .into_iter()
+        .filter(|c| c.exists && c.name.is_none())
+        .map(|c| c.agent_id)
+        .collect();
+    if !malformed.is_empty() {
+        issues.push(DoctorIssue {
+            id: "identity.format".into(),
+            code: "identity.malformed".into(),
+            severity: "warn".into(),
+            message: format!("IDENTITY.md has no parsable name for agent(s): {}", malformed.join(", ")),
+            auto_fixable: false,
+            fix_hint: Some("Use lines like \"- Name: Bob\" and \"- Emoji: 🤖\"".into()),
+        });
+        penalty += 5;
+    }
+
+    let unknown: Vec<String> = find_unknown_config_keys_in(cfg)
+        .into_iter()
+        .map(|k| match k.suggestion {
+            Some(s) => format!("{} (did you mean \"{s}\"?)", k.pointer),
+            None => k.pointer,
+        })
+        .collect();
+    if !unknown.is_empty() {
+        issues.push(DoctorIssue {
+            id: "config.unknown_keys".into(),
+            code: "config.unknown_key".into(),
+            severity: "warn".into(),
+            message: format!("Unrecognized config keys will be ignored: {}", unknown.join(", ")),
+            auto_fixable: false,
+            fix_hint: Some("Rename or remove the listed keys".into()),
+        });
+        penalty += 5;
+    }
+
+    let unreachable = find_unreachable_agents_in(cfg);
+    if !unreachable.is_empty() {
+        issues.push(DoctorIssue {
+            id: "agents.unreachable".into(),
+            code: "agents.no_binding".into(),
+            severity: "warn".into(),
+            message: format!("Agents with no binding will never receive messages: {}", unreachable.join(", ")),
+            auto_fixable: false,
+            fix_hint: Some("Add a channel binding for these agents or remove them".into()),
+        });
+        penalty += 5;
+    }
+
+    let orphaned: Vec<String> = find_orphaned_bindings(cfg)
+        .into_iter()
+        .map(|b| format!("#{} {} -> {}", b.index, b.channel, b.agent_id.as_deref().unwrap_or("?")))
+        .collect();
+    if !orphaned.is_empty() {
+        issues.push(DoctorIssue {
+            id: "bindings.orphaned".into(),
+            code: "bindings.unknown_channel".into(),
+            severity: "warn".into(),
+            message: format!("Bindings reference channels that are not configured: {}", orphaned.join(", ")),
+            auto_fixable: false,
+            fix_hint: Some("Remove these bindings or restore the channel configuration".into()),
+        });
+        penalty += 5;
+    }
+
+    let missing_agents: Vec<String> = bindings_with_missing_agents(cfg)
+        .into_iter()
+        .map(|(index, agent_id)| format!("#{index} -> {agent_id}"))
+        .collect();
+    if !missing_agents.is_empty() {
+        issues.push(DoctorIssue {
+            id: "bindings.missing_agent".into(),
+            code: "bindings.missing_agent".into(),
+            severity: "warn".into(),
+            message: format!("Bindings route to agents that do not exist: {}", missing_agents.join(", ")),
+            auto_fixable: true,
+            fix_hint: Some("Rebind them to the main agent".into()),
+        });
+        penalty += 5;
+    }
+
+    if let Some(port) = cfg.pointer("/gateway/port").and_then(|v| v.as_u64()) {
+        if port > 65535 {
+            issues.push(DoctorIssue {
+                id: "field.port".into(),
+                code: "invalid.port".into(),
+                severity: "error".into(),
+                message: "Gateway port is invalid".into(),
+                auto_fixable: false,
+                fix_hint: None,
+            });
+            penalty += 20;
+        }
+    }
+
+    penalty
+}
+
+/// Doctor issues for an in-memory config. Checks of the config file itself
+/// (syntax, permissions) and of sessions or the running gateway are not
+/// included; agent IDENTITY.md files are still read from disk.
+pub fn check_config(cfg: &Value) -> Vec<DoctorIssue> {
+    let mut issues = Vec::new();
+    check_config_structure(cfg, &mut issues);
+    issues
+}
+
+/// Flag agent workspaces that resolve outside both the user's home and the
+/// openclaw base dir. Symlinks are followed for paths that exist.
+pub fn audit_workspace_locations(cfg: &Value, base_dir: &Path) -> Vec<WorkspaceLocationIssue> {
+    let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    let allowed: Vec<PathBuf> = dirs::home_dir()
+        .into_iter()
+        .chain(std::iter::once(base_dir.to_path_buf()))
+        .map(|p| canonical(&p))
+        .collect();
+
+    let mut out = Vec::new();
+    for (agent_id, workspace) in agent_workspaces(cfg) {
+        let Some(workspace) = workspace else {
+            continue;
+        };
+        let expanded = PathBuf::from(shellexpand::tilde(workspace).to_string());
+        let reason = if !expanded.is_absolute() {
+            Some("workspace is a relative path".to_string())
+        } else if expanded.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+            Some("workspace path contains '..'".to_string())
+        } else {
+            let resolved = canonical(&expanded);
+            (!allowed.iter().any(|root| resolved.starts_with(root)))
+                .then(|| "outside home and openclaw directories".to_string())
+        };
+        if let Some(reason) = reason {
+            out.push(WorkspaceLocationIssue {
+                agent_id,
+                workspace: workspace.to_string(),
+                resolved_path: canonical(&expanded).to_string_lossy().to_string(),
+                reason,
+            });
+        }
+    }
+    out
+}
+
 pub fn run_doctor(paths: &OpenClawPaths) -> DoctorReport {
     let mut issues = Vec::new();
     let mut score: i32 = 100;
@@ -94,30 +832,22 @@ pub fn run_doctor(paths: &OpenClawPaths) -> DoctorReport {
     }
 
     if let Ok(cfg) = read_openclaw_config(paths) {
-        if cfg.get("agents").is_none() {
+        score -= check_config_structure(&cfg, &mut issues);
+
+        let suspicious: Vec<String> = audit_workspace_locations(&cfg, &paths.base_dir)
+            .into_iter()
+            .map(|w| format!("{} ({})", w.agent_id, w.resolved_path))
+            .collect();
+        if !suspicious.is_empty() {
             issues.push(DoctorIssue {
-                id: "field.agents".into(),
-                code: "required.field".into(),
+                id: "workspace.location".into(),
+                code: "workspace.outside_home".into(),
                 severity: "warn".into(),
-                message: "Missing agents field; recommend initializing defaults".into(),
-                auto_fixable: true,
-                fix_hint: Some("Add agents.defaults with safe minimal values".into()),
+                message: format!("Agent workspace outside home directory: {}", suspicious.join(", ")),
+                auto_fixable: false,
+                fix_hint: Some("Move the workspace under your home directory or confirm the location is intended".into()),
             });
-            score -= 10;
-        }
-
-        if let Some(port) = cfg.pointer("/gateway/port").and_then(|v| v.as_u64()) {
-            if port > 65535 {
-                issues.push(DoctorIssue {
-                    id: "field.port".into(),
-                    code: "invalid.port".into(),
-                    severity: "error".into(),
-                    message: "Gateway port is invalid".into(),
-                    auto_fixable: false,
-                    fix_hint: None,
-                });
-                score -= 20;
-            }
+            score -= 5;
         }
     }
 
@@ -137,6 +867,62 @@ pub fn run_doctor(paths: &OpenClawPaths) -> DoctorReport {
         score -= 20;
     }
 
+    let gateway_config = check_gateway_config(paths);
+    if gateway_config.mismatch {
+        issues.push(DoctorIssue {
+            id: "gateway.config_path".into(),
+            code: "gateway.config_mismatch".into(),
+            severity: "warn".into(),
+            message: format!(
+                "Gateway loaded {} but ClawPal edits {}",
+                gateway_config.loaded_path.as_deref().unwrap_or("an unknown config"),
+                gateway_config.expected_path
+            ),
+            auto_fixable: false,
+            fix_hint: Some("Restart the gateway without config overrides, or point ClawPal at the same file via CLAWPAL_OPENCLAW_DIR".into()),
+        });
+        score -= 10;
+    }
+
+    let port = read_openclaw_config(paths)
+        .ok()
+        .and_then(|cfg| cfg.pointer("/gateway/port").and_then(Value::as_u64))
+        .and_then(|p| u16::try_from(p).ok())
+        .unwrap_or(18789);
+    if let Some((pid, command)) = foreign_port_listener(port) {
+        issues.push(DoctorIssue {
+            id: "gateway.port_conflict".into(),
+            code: "gateway.port_in_use".into(),
+            severity: "warn".into(),
+            message: format!("Gateway port {port} is held by another process (pid {pid}: {command})"),
+            auto_fixable: false,
+            fix_hint: Some(format!(
+                "Stop pid {pid} or change gateway.port, then restart the gateway; health checks on {port} may be answered by the other service"
+            )),
+        });
+        score -= 10;
+    }
+
+    let inconsistent: Vec<String> = check_session_consistency(&paths.base_dir.join("agents"), None)
+        .into_iter()
+        .filter(|c| !c.orphaned_metadata.is_empty() || !c.untracked_files.is_empty())
+        .map(|c| c.agent)
+        .collect();
+    if !inconsistent.is_empty() {
+        issues.push(DoctorIssue {
+            id: "sessions.consistency".into(),
+            code: "sessions.mismatch".into(),
+            severity: "warn".into(),
+            message: format!(
+                "sessions.json and session files disagree for agent(s): {}",
+                inconsistent.join(", ")
+            ),
+            auto_fixable: false,
+            fix_hint: Some("Open the session consistency check to review orphaned metadata and untracked files".into()),
+        });
+        score -= 5;
+    }
+
     let mut unique = std::collections::HashSet::new();
     issues.retain(|issue| unique.insert(issue.id.clone()));
 
@@ -146,3 +932,48 @@ pub fn run_doctor(paths: &OpenClawPaths) -> DoctorReport {
         issues,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dangling_binding_fixture() -> Value {
+        serde_json::json!({
+            "agents": { "list": [{ "id": "main" }, { "id": "helper" }] },
+            "channels": { "discord": {} },
+            "bindings": [
+                { "agentId": "helper", "match": { "channel": "discord" } },
+                { "agentId": "deleted-bot", "match": { "channel": "discord" } },
+                { "agentId": "main", "match": { "channel": "discord" } }
+            ]
+        })
+    }
+
+    #[test]
+    fn parses_lsof_field_output() {
+        let text = "p123\ncnginx\nf6\np456\ncnode\n";
+        assert_eq!(
+            parse_lsof_listeners(text),
+            vec![(123, "nginx".to_string()), (456, "node".to_string())]
+        );
+        assert!(parse_lsof_listeners("").is_empty());
+    }
+
+    #[test]
+    fn detects_and_fixes_bindings_to_missing_agents() {
+        let mut cfg = dangling_binding_fixture();
+        assert_eq!(bindings_with_missing_agents(&cfg), vec![(1, "deleted-bot".to_string())]);
+
+        let mut issues = Vec::new();
+        check_config_structure(&cfg, &mut issues);
+        let issue = issues.iter().find(|i| i.id == "bindings.missing_agent").unwrap();
+        assert!(issue.auto_fixable);
+
+        assert_eq!(issue.code, "bindings.missing_agent");
+
+        assert_eq!(rebind_missing_agents_to_main(&mut cfg), 1);
+        assert_eq!(cfg["bindings"][1]["agentId"], "main");
+        assert_eq!(cfg["bindings"][0]["agentId"], "helper");
+        assert!(bindings_with_missing_agents(&cfg).is_empty());
+    }
+}
@@ -15,6 +15,9 @@ pub struct SnapshotMeta {
     pub can_rollback: bool,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub rollback_of: Option<String>,
+    /// User-assigned note, e.g. "before Discord migration".
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub label: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -81,6 +84,7 @@ pub fn add_snapshot(
         source: source.to_string(),
         can_rollback: rollbackable,
         rollback_of: rollback_of.clone(),
+        label: None,
     });
     next.items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
     if next.items.len() > 200 {
@@ -98,9 +102,26 @@ pub fn add_snapshot(
         source: source.to_string(),
         can_rollback: rollbackable,
         rollback_of,
+        label: None,
     })
 }
 
+/// Set or clear (`None`/blank) the label of a snapshot. Returns false if the
+/// snapshot is not in the index.
+pub fn set_snapshot_label(
+    metadata_path: &std::path::Path,
+    snapshot_id: &str,
+    label: Option<String>,
+) -> Result<bool, String> {
+    let mut index = list_snapshots(metadata_path)?;
+    let Some(item) = index.items.iter_mut().find(|item| item.id == snapshot_id) else {
+        return Ok(false);
+    };
+    item.label = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+    write_snapshots(metadata_path, &index)?;
+    Ok(true)
+}
+
 pub fn read_snapshot(path: &str) -> Result<String, String> {
     let canonical = std::fs::canonicalize(path).map_err(|e| e.to_string())?;
     let home = dirs::home_dir().ok_or("Cannot determine home directory")?;
@@ -37,6 +37,86 @@ use crate::commands::{
     remote_get_watchdog_status, remote_deploy_watchdog, remote_start_watchdog, remote_stop_watchdog, remote_uninstall_watchdog,
     read_app_log, read_error_log, read_gateway_log, read_gateway_error_log,
     remote_read_app_log, remote_read_error_log, remote_read_gateway_log, remote_read_gateway_error_log,
+    config_schema_tree,
+    remote_cancel_operation,
+    cache_freshness_report,
+    profiles_sharing_keys,
+    reset_channel_to_default,
+    session_model_drift,
+    remote_delete_agent,
+    validate_recipes,
+    list_session_trash, restore_session_trash, empty_session_trash,
+    cli_environment_info,
+    config_change_log,
+    test_binding,
+    inspect_support_bundle,
+    session_consistency_check,
+    set_channel_models,
+    storage_breakdown, remote_storage_breakdown,
+    validate_agent_identities,
+    rotate_logs, remote_rotate_logs,
+    gateway_loaded_config_path,
+    preview_extract_model_profiles,
+    check_version_pin, remote_check_version_pin,
+    merge_config_fragment,
+    list_openclaw_processes, kill_openclaw_process, remote_list_openclaw_processes, remote_kill_openclaw_process,
+    recipe_health_impact,
+    export_session_redacted,
+    audit_workspace_locations,
+    mirror_remote_history_local, list_remote_history_local,
+    validate_bindings_against_channels,
+    suggest_models,
+    gateway_uptime, remote_gateway_uptime,
+    find_unknown_config_keys,
+    export_name_caches, import_name_caches,
+    validate_profile_consistency,
+    self_test,
+    prune_backups_by_age, remote_prune_backups_by_age,
+    list_restore_points,
+    find_unreachable_agents,
+    set_agent_and_channels_model,
+    remote_check_clock_skew,
+    sessions_for_model_tokens,
+    normalize_binding_peer_ids,
+    remote_session_counts,
+    remote_host_fingerprint, set_host_fingerprint,
+    list_locked_sessions, clear_session_lock,
+    diff_model_profiles,
+    factory_refresh,
+    search_sessions,
+    export_session, remote_export_session,
+    test_model_profile,
+    list_model_catalog,
+    prune_snapshots, remote_prune_snapshots,
+    diff_snapshots,
+    upsert_cron_job, remote_upsert_cron_job,
+    set_cron_job_enabled, remote_set_cron_job_enabled,
+    tail_gateway_log, follow_gateway_log, stop_tail_gateway_log,
+    generate_ssh_keypair,
+    ssh_install_public_key,
+    ssh_health,
+    remote_get_status_bundle,
+    download_remote_backup,
+    validate_config,
+    read_raw_config_text,
+    write_raw_config,
+    rename_agent,
+    clone_agent,
+    update_agent_identity,
+    delete_sessions_by_category,
+    archive_session, unarchive_session, remote_archive_session, remote_unarchive_session,
+    read_memory_file, write_memory_file,
+    toggle_model_profile_favorite, remote_toggle_model_profile_favorite,
+    export_model_profiles, import_model_profiles,
+    apply_config_edits,
+    delete_snapshot, get_snapshot_config,
+    set_snapshot_label,
+    list_telegram_channels, refresh_telegram_channels,
+    refresh_channel_names,
+    find_duplicate_model_profiles, merge_model_profiles,
+    model_profile_usage,
+    verify_backup, remote_verify_backup,
+    run_openclaw, remote_run_openclaw,
 };
 use crate::bridge_client::BridgeClient;
 use crate::doctor_commands::{
@@ -48,10 +128,11 @@ use crate::doctor_commands::{
 };
 use crate::cli_runner::{
     queue_command, remove_queued_command, list_queued_commands,
-    discard_queued_commands, queued_commands_count,
+    discard_queued_commands, queued_commands_count, move_queued_command, dedupe_queued_commands,
     preview_queued_commands, apply_queued_commands, CommandQueue,
     remote_queue_command, remote_remove_queued_command, remote_list_queued_commands,
     remote_discard_queued_commands, remote_queued_commands_count,
+    remote_move_queued_command, remote_dedupe_queued_commands,
     remote_preview_queued_commands, remote_apply_queued_commands, RemoteCommandQueues,
     CliCache,
 };
@@ -197,11 +278,124 @@ pub fn run() {
             remote_read_error_log,
             remote_read_gateway_log,
             remote_read_gateway_error_log,
+            config_schema_tree,
+            remote_cancel_operation,
+            cache_freshness_report,
+            profiles_sharing_keys,
+            reset_channel_to_default,
+            session_model_drift,
+            remote_delete_agent,
+            validate_recipes,
+            list_session_trash,
+            restore_session_trash,
+            empty_session_trash,
+            cli_environment_info,
+            config_change_log,
+            test_binding,
+            inspect_support_bundle,
+            session_consistency_check,
+            set_channel_models,
+            storage_breakdown,
+            remote_storage_breakdown,
+            validate_agent_identities,
+            rotate_logs,
+            remote_rotate_logs,
+            gateway_loaded_config_path,
+            preview_extract_model_profiles,
+            check_version_pin,
+            remote_check_version_pin,
+            merge_config_fragment,
+            list_openclaw_processes,
+            kill_openclaw_process,
+            remote_list_openclaw_processes,
+            remote_kill_openclaw_process,
+            recipe_health_impact,
+            export_session_redacted,
+            audit_workspace_locations,
+            mirror_remote_history_local,
+            list_remote_history_local,
+            validate_bindings_against_channels,
+            suggest_models,
+            gateway_uptime,
+            remote_gateway_uptime,
+            find_unknown_config_keys,
+            export_name_caches,
+            import_name_caches,
+            validate_profile_consistency,
+            self_test,
+            prune_backups_by_age,
+            remote_prune_backups_by_age,
+            list_restore_points,
+            find_unreachable_agents,
+            set_agent_and_channels_model,
+            remote_check_clock_skew,
+            sessions_for_model_tokens,
+            normalize_binding_peer_ids,
+            remote_session_counts,
+            remote_host_fingerprint,
+            set_host_fingerprint,
+            list_locked_sessions,
+            clear_session_lock,
+            diff_model_profiles,
+            factory_refresh,
+            search_sessions,
+            export_session,
+            remote_export_session,
+            test_model_profile,
+            list_model_catalog,
+            prune_snapshots,
+            remote_prune_snapshots,
+            diff_snapshots,
+            upsert_cron_job,
+            remote_upsert_cron_job,
+            set_cron_job_enabled,
+            remote_set_cron_job_enabled,
+            tail_gateway_log,
+            follow_gateway_log,
+            stop_tail_gateway_log,
+            generate_ssh_keypair,
+            ssh_install_public_key,
+            ssh_health,
+            remote_get_status_bundle,
+            download_remote_backup,
+            validate_config,
+            read_raw_config_text,
+            write_raw_config,
+            rename_agent,
+            clone_agent,
+            update_agent_identity,
+            delete_sessions_by_category,
+            archive_session,
+            unarchive_session,
+            remote_archive_session,
+            remote_unarchive_session,
+            read_memory_file,
+            write_memory_file,
+            toggle_model_profile_favorite,
+            remote_toggle_model_profile_favorite,
+            export_model_profiles,
+            import_model_profiles,
+            apply_config_edits,
+            delete_snapshot,
+            get_snapshot_config,
+            set_snapshot_label,
+            list_telegram_channels,
+            refresh_telegram_channels,
+            refresh_channel_names,
+            find_duplicate_model_profiles,
+            merge_model_profiles,
+            model_profile_usage,
+            verify_backup,
+            remote_verify_backup,
+            run_openclaw,
+            remote_run_openclaw,
             queue_command,
             remove_queued_command,
             list_queued_commands,
             discard_queued_commands,
             queued_commands_count,
+            move_queued_command,
+            dedupe_queued_commands,
             preview_queued_commands,
             apply_queued_commands,
             remote_queue_command,
@@ -209,6 +403,8 @@ pub fn run() {
             remote_list_queued_commands,
             remote_discard_queued_commands,
             remote_queued_commands_count,
+            remote_move_queued_command,
+            remote_dedupe_queued_commands,
             remote_preview_queued_commands,
             remote_apply_queued_commands,
             doctor_port_forward,
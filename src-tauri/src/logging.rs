@@ -1,13 +1,13 @@
 use std::fs::{self, OpenOptions};
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 
 use dirs::home_dir;
 
 const MAX_LINES: usize = 5000;
 const TRIM_TO: usize = 3000;
 
-fn logs_dir() -> PathBuf {
+pub fn logs_dir() -> PathBuf {
     let home = home_dir().unwrap_or_else(|| PathBuf::from("."));
     let dir = home.join(".clawpal").join("logs");
     let _ = fs::create_dir_all(&dir);
@@ -17,6 +17,157 @@ fn logs_dir() -> PathBuf {
 /// Size threshold in bytes before we check for trimming (~500KB).
 const SIZE_THRESHOLD: u64 = 500_000;
 
+/// Number of rotated copies (`<name>.1` .. `<name>.N`) kept by `rotate_file`.
+pub const LOG_ROTATIONS: usize = 3;
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+/// Rotate `path` when it exceeds `max_bytes`: shift `.1`..`.N-1` up by one,
+/// copy the active file to `.1` and truncate it in place. Copying instead of
+/// renaming keeps writers that hold the file open appending to the live log.
+pub fn rotate_file(path: &Path, max_bytes: u64) -> Result<bool, String> {
+    let size = match fs::metadata(path) {
+        Ok(m) => m.len(),
+        Err(_) => return Ok(false),
+    };
+    if size <= max_bytes {
+        return Ok(false);
+    }
+    for index in (1..LOG_ROTATIONS).rev() {
+        let from = rotated_path(path, index);
+        if from.exists() {
+            fs::rename(&from, rotated_path(path, index + 1)).map_err(|e| e.to_string())?;
+        }
+    }
+    fs::copy(path, rotated_path(path, 1)).map_err(|e| e.to_string())?;
+    OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .open(path)
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// Last `lines` lines of `path`, reaching back into rotated copies when the
+/// active file alone is too short.
+pub fn read_file_tail(path: &Path, lines: usize) -> Result<String, String> {
+    let mut collected: Vec<String> = Vec::new();
+    for index in 0..=LOG_ROTATIONS {
+        let candidate = if index == 0 { path.to_path_buf() } else { rotated_path(path, index) };
+        if !candidate.exists() {
+            if index == 0 {
+                continue;
+            }
+            break;
+        }
+        let content = fs::read_to_string(&candidate).map_err(|e| e.to_string())?;
+        let mut chunk: Vec<String> = content.lines().map(str::to_string).collect();
+        chunk.append(&mut collected);
+        collected = chunk;
+        if collected.len() >= lines {
+            break;
+        }
+    }
+    let start = collected.len().saturating_sub(lines);
+    Ok(collected[start..].join("\n"))
+}
+
+/// Last `lines` lines of a single file, reading backwards from the end in
+/// fixed-size blocks so large logs are never loaded whole. A trailing line
+/// without a newline (still being written) is included as-is.
+pub fn read_last_lines(path: &Path, lines: usize) -> Result<String, String> {
+    const BLOCK: u64 = 16 * 1024;
+    let mut file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut pos = len;
+    let mut buf: Vec<u8> = Vec::new();
+    while pos > 0 {
+        let read = BLOCK.min(pos);
+        pos -= read;
+        let mut block = vec![0u8; read as usize];
+        file.seek(SeekFrom::Start(pos)).map_err(|e| e.to_string())?;
+        file.read_exact(&mut block).map_err(|e| e.to_string())?;
+        block.extend_from_slice(&buf);
+        buf = block;
+        // One extra newline is needed since the file usually ends with one.
+        if buf.iter().filter(|b| **b == b'\n').count() > lines {
+            break;
+        }
+    }
+    let text = String::from_utf8_lossy(&buf);
+    let all: Vec<&str> = text.lines().collect();
+    // When we stopped mid-file the first line is likely cut, so drop it.
+    let skip_partial = usize::from(pos > 0 && all.len() > lines);
+    let start = all.len().saturating_sub(lines).max(skip_partial);
+    Ok(all[start..].join("\n"))
+}
+
+/// Severity rank of a level name (`trace` = 0 .. `fatal` = 5).
+fn level_rank(name: &str) -> Option<u8> {
+    match name.to_ascii_lowercase().as_str() {
+        "trace" => Some(0),
+        "debug" => Some(1),
+        "info" => Some(2),
+        "warn" | "warning" => Some(3),
+        "error" | "err" => Some(4),
+        "fatal" | "panic" | "critical" => Some(5),
+        _ => None,
+    }
+}
+
+/// Level of a log line: a JSON `level` field (name or pino-style number), a
+/// logfmt `level=` token, or a level prefix right after any timestamp, e.g.
+/// `[ts] ERROR: ...`, `2024-01-01 [warn] ...` or `INFO ...`. Level words in
+/// the message itself (`[ts] plain info line`) are not levels.
+fn line_level(line: &str) -> Option<u8> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('{') {
+        if let Ok(v) = serde_json::from_str::<serde_json::Value>(trimmed) {
+            return match v.get("level") {
+                Some(serde_json::Value::String(name)) => level_rank(name),
+                Some(serde_json::Value::Number(n)) => n.as_u64().map(|n| (n / 10).saturating_sub(1).min(5) as u8),
+                _ => None,
+            };
+        }
+    }
+    for token in trimmed.split_whitespace().take(4) {
+        if let Some(value) = token.strip_prefix("level=") {
+            return level_rank(value.trim_matches('"'));
+        }
+        let bracketed = token.starts_with('[') && token.ends_with(']');
+        let word = token.trim_start_matches('[').trim_end_matches([']', ':']);
+        if let Some(rank) = level_rank(word) {
+            if bracketed || token.ends_with(':') || word.chars().all(|c| c.is_ascii_uppercase()) {
+                return Some(rank);
+            }
+        }
+        // Timestamps and logfmt fields may precede the level; anything else
+        // is the start of the message.
+        if !(bracketed || token.contains('=') || token.starts_with(|c: char| c.is_ascii_digit())) {
+            return None;
+        }
+    }
+    None
+}
+
+/// Drop lines below `min_level`. Lines without a recognizable level are
+/// kept so nothing is silently hidden.
+pub fn filter_min_level(text: String, min_level: Option<&str>) -> Result<String, String> {
+    let Some(min_level) = min_level.map(str::trim).filter(|l| !l.is_empty()) else {
+        return Ok(text);
+    };
+    let min = level_rank(min_level).ok_or_else(|| format!("unknown log level: {min_level}"))?;
+    Ok(text
+        .lines()
+        .filter(|line| line_level(line).is_none_or(|level| level >= min))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
 fn append_line(filename: &str, line: &str) {
     let path = logs_dir().join(filename);
 
@@ -53,12 +204,33 @@ pub fn read_log_tail(filename: &str, lines: usize) -> Result<String, String> {
     if filename.contains('/') || filename.contains('\\') || filename.contains("..") {
         return Err("Invalid filename".into());
     }
-    let path = logs_dir().join(filename);
-    if !path.exists() {
-        return Ok(String::new());
+    read_file_tail(&logs_dir().join(filename), lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_min_level_keeps_unparseable_lines() {
+        let text = [
+            "[2024-01-01T00:00:00Z] ERROR: boom",
+            "[2024-01-01T00:00:00Z] plain info line",
+            "2024-01-01 [debug] noisy",
+            r#"{"level":40,"msg":"pino warn"}"#,
+            r#"{"level":"info","msg":"json info"}"#,
+        ]
+        .join("\n");
+        let filtered = filter_min_level(text, Some("warn")).unwrap();
+        let kept: Vec<&str> = filtered.lines().collect();
+        assert_eq!(kept.len(), 3);
+        assert!(kept[0].contains("boom"));
+        assert!(kept[1].contains("plain"));
+        assert!(kept[2].contains("pino"));
+        assert!(filter_min_level(String::new(), Some("loud")).is_err());
+
+        assert_eq!(line_level("ts=1 level=error msg=x"), Some(4));
+        assert_eq!(line_level("INFO starting"), Some(2));
+        assert_eq!(line_level("warning about nothing"), None);
     }
-    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let all_lines: Vec<&str> = content.lines().collect();
-    let start = all_lines.len().saturating_sub(lines);
-    Ok(all_lines[start..].join("\n"))
 }
@@ -113,7 +113,7 @@ fn parse_recipes_document(text: &str) -> Result<Vec<Recipe>, String> {
     }
 }
 
-pub fn load_recipes_from_source(source: &str) -> Result<Vec<Recipe>, String> {
+fn read_recipes_source(source: &str) -> Result<String, String> {
     if source.trim().is_empty() {
         return Err("empty recipe source".into());
     }
@@ -123,19 +123,30 @@ pub fn load_recipes_from_source(source: &str) -> Result<Vec<Recipe>, String> {
         if !response.status().is_success() {
             return Err(format!("request failed: {}", response.status()));
         }
-        let text = response.text().map_err(|e| e.to_string())?;
-        parse_recipes_document(&text)
+        response.text().map_err(|e| e.to_string())
     } else {
         let path = expand_user_path(source);
         let path = Path::new(&path);
         if !path.exists() {
             return Err(format!("recipe file not found: {}", path.to_string_lossy()));
         }
-        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
-        parse_recipes_document(&text)
+        fs::read_to_string(path).map_err(|e| e.to_string())
     }
 }
 
+pub fn load_recipes_from_source(source: &str) -> Result<Vec<Recipe>, String> {
+    let text = read_recipes_source(source)?;
+    parse_recipes_document(&text)
+}
+
+/// Resolve which recipe source `load_recipes_with_fallback` would try first.
+pub fn primary_recipes_source(explicit_source: Option<String>, default_path: &Path) -> String {
+    explicit_source
+        .filter(|s| !s.trim().is_empty())
+        .or_else(|| env::var("CLAWPAL_RECIPES_SOURCE").ok().filter(|s| !s.trim().is_empty()))
+        .unwrap_or_else(|| default_path.to_string_lossy().to_string())
+}
+
 pub fn load_recipes_with_fallback(
     explicit_source: Option<String>,
     default_path: &Path,
@@ -215,6 +226,155 @@ pub fn validate(recipe: &Recipe, params: &Map<String, Value>) -> Vec<String> {
     errors
 }
 
+/// Collect the `{{param}}` placeholders referenced in a template string.
+pub fn extract_template_params(template: &str) -> Vec<String> {
+    let re = Regex::new(r"\{\{([A-Za-z0-9_]+)\}\}").expect("valid placeholder regex");
+    let mut out: Vec<String> = Vec::new();
+    for cap in re.captures_iter(template) {
+        let name = cap[1].to_string();
+        if !out.contains(&name) {
+            out.push(name);
+        }
+    }
+    out
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeValidationIssue {
+    pub index: usize,
+    pub recipe_id: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipesValidationReport {
+    pub source: String,
+    pub file_error: Option<String>,
+    pub recipe_count: usize,
+    pub issues: Vec<RecipeValidationIssue>,
+}
+
+/// Step actions the frontend registry in `src/lib/actions.ts` can execute;
+/// `step_actions_match_the_frontend_registry` keeps the two in sync.
+const KNOWN_STEP_ACTIONS: &[&str] = &[
+    "create_agent",
+    "setup_identity",
+    "bind_channel",
+    "config_patch",
+    "set_global_model",
+];
+
+fn validate_recipe_entry(index: usize, entry: &Value, seen_ids: &mut Vec<String>) -> Vec<RecipeValidationIssue> {
+    let raw_id = entry.get("id").and_then(Value::as_str).map(str::to_string);
+    let issue = |message: String| RecipeValidationIssue {
+        index,
+        recipe_id: raw_id.clone(),
+        message,
+    };
+
+    let recipe: Recipe = match serde_json::from_value(entry.clone()) {
+        Ok(recipe) => recipe,
+        Err(e) => return vec![issue(format!("invalid recipe: {e}"))],
+    };
+
+    let mut issues = Vec::new();
+    if recipe.id.trim().is_empty() {
+        issues.push(issue("missing id".into()));
+    } else if seen_ids.contains(&recipe.id) {
+        issues.push(issue(format!("duplicate id: {}", recipe.id)));
+    } else {
+        seen_ids.push(recipe.id.clone());
+    }
+
+    let declared: Vec<&str> = recipe.params.iter().map(|p| p.id.as_str()).collect();
+    for param in &recipe.params {
+        if let Some(pattern) = &param.pattern {
+            if let Err(e) = Regex::new(pattern) {
+                issues.push(issue(format!("param {} has invalid pattern: {e}", param.id)));
+            }
+        }
+        if let Some(dep) = &param.depends_on {
+            if !declared.contains(&dep.as_str()) {
+                issues.push(issue(format!("param {} depends on undeclared param {dep}", param.id)));
+            }
+        }
+    }
+
+    for (step_index, step) in recipe.steps.iter().enumerate() {
+        if !KNOWN_STEP_ACTIONS.contains(&step.action.as_str()) {
+            issues.push(issue(format!("step {step_index} has unknown action: {}", step.action)));
+        }
+        let args_text = Value::Object(step.args.clone()).to_string();
+        for name in extract_template_params(&args_text) {
+            if !declared.contains(&name.as_str()) {
+                issues.push(issue(format!("step {step_index} references undeclared param: {name}")));
+            }
+        }
+        if step.action == "config_patch" {
+            match step.args.get("patchTemplate").and_then(Value::as_str) {
+                Some(template) => {
+                    // Render with placeholder values so only structural errors surface.
+                    let sample: Map<String, Value> = declared
+                        .iter()
+                        .map(|id| (id.to_string(), Value::String("x".into())))
+                        .collect();
+                    let rendered = render_patch_template(template, &sample);
+                    if let Err(e) = json5::from_str::<Value>(&rendered) {
+                        issues.push(issue(format!("step {step_index} has invalid patchTemplate: {e}")));
+                    }
+                }
+                None => issues.push(issue(format!("step {step_index} is missing patchTemplate"))),
+            }
+        }
+    }
+    issues
+}
+
+/// Strictly validate a recipes document, reporting problems per entry
+/// instead of dropping the whole file.
+pub fn validate_recipes_text(source: &str, text: &str) -> RecipesValidationReport {
+    let mut report = RecipesValidationReport {
+        source: source.to_string(),
+        file_error: None,
+        recipe_count: 0,
+        issues: Vec::new(),
+    };
+    let document: Value = match json5::from_str(text) {
+        Ok(v) => v,
+        Err(e) => {
+            report.file_error = Some(format!("parse error: {e}"));
+            return report;
+        }
+    };
+    let entries = match document.as_array().or_else(|| document.get("recipes").and_then(Value::as_array)) {
+        Some(entries) => entries.clone(),
+        None => {
+            report.file_error = Some("expected an array of recipes or an object with a \"recipes\" array".into());
+            return report;
+        }
+    };
+    report.recipe_count = entries.len();
+    let mut seen_ids = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        report.issues.extend(validate_recipe_entry(index, entry, &mut seen_ids));
+    }
+    report
+}
+
+pub fn validate_recipes_source(source: &str) -> RecipesValidationReport {
+    match read_recipes_source(source) {
+        Ok(text) => validate_recipes_text(source, &text),
+        Err(e) => RecipesValidationReport {
+            source: source.to_string(),
+            file_error: Some(e),
+            recipe_count: 0,
+            issues: Vec::new(),
+        },
+    }
+}
+
 fn render_patch_template(template: &str, params: &Map<String, Value>) -> String {
     let mut text = template.to_string();
     for (k, v) in params {
@@ -241,7 +401,7 @@ pub fn build_candidate_config_from_template(
     Ok((merged, changes))
 }
 
-fn apply_merge_patch(target: &mut Value, patch: &Value, prefix: &str, changes: &mut Vec<ChangeItem>) {
+pub fn apply_merge_patch(target: &mut Value, patch: &Value, prefix: &str, changes: &mut Vec<ChangeItem>) {
     if patch.is_object() && target.is_object() {
         let t = target.as_object_mut().unwrap();
         for (k, pv) in patch.as_object().unwrap() {
@@ -310,8 +470,67 @@ pub fn collect_change_paths(current: &Value, patched: &Value) -> Vec<ChangeItem>
     }
 }
 
+/// List the dotted paths that differ between two configs. Objects are
+/// compared key by key; any other differing value is reported at its own
+/// path ("root" when the whole config differs).
+pub fn collect_changed_config_paths(before: &Value, after: &Value) -> Vec<String> {
+    let mut paths = Vec::new();
+    diff_value_paths(before, after, "", &mut paths);
+    paths
+}
+
+fn diff_value_paths(before: &Value, after: &Value, prefix: &str, paths: &mut Vec<String>) {
+    if before == after {
+        return;
+    }
+    match (before.as_object(), after.as_object()) {
+        (Some(b), Some(a)) => {
+            let keys: std::collections::BTreeSet<&String> = b.keys().chain(a.keys()).collect();
+            for k in keys {
+                let path = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                match (b.get(k), a.get(k)) {
+                    (Some(bv), Some(av)) => diff_value_paths(bv, av, &path, paths),
+                    (Some(_), None) | (None, Some(_)) => paths.push(path),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ => paths.push(if prefix.is_empty() { "root".to_string() } else { prefix.to_string() }),
+    }
+}
+
 pub fn format_diff(before: &Value, after: &Value) -> String {
     let before_text = serde_json::to_string_pretty(before).unwrap_or_else(|_| "{}".into());
     let after_text = serde_json::to_string_pretty(after).unwrap_or_else(|_| "{}".into());
     format!("before:\n{}\n\nafter:\n{}", before_text, after_text)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_actions_match_the_frontend_registry() {
+        let actions_ts = include_str!("../../src/lib/actions.ts");
+        let registry = &actions_ts[actions_ts.find("const registry").expect("registry in actions.ts")..];
+        let registry = &registry[..registry.find("\n};").expect("end of registry")];
+        let key = Regex::new(r"(?m)^  ([a-z_]+): \{").unwrap();
+        let frontend: Vec<&str> = key.captures_iter(registry).map(|c| c.get(1).unwrap().as_str()).collect();
+        assert!(!frontend.is_empty());
+        let mut backend = KNOWN_STEP_ACTIONS.to_vec();
+        let mut frontend_sorted = frontend.clone();
+        backend.sort_unstable();
+        frontend_sorted.sort_unstable();
+        assert_eq!(backend, frontend_sorted);
+    }
+
+    #[test]
+    fn template_params_use_the_render_syntax() {
+        let params = extract_template_params(r#"{"a": "{{name}}", "b": "{{ spaced }}", "c": "{{name}}-{{id_2}}"}"#);
+        assert_eq!(params, vec!["name".to_string(), "id_2".to_string()]);
+    }
+}
@@ -20,6 +20,69 @@ pub struct SshHostConfig {
     pub auth_method: String,
     pub key_path: Option<String>,
     pub password: Option<String>,
+    /// Expected host key fingerprint ("SHA256:..."). When set, connections
+    /// are refused unless the server presents a matching key.
+    #[serde(default)]
+    pub host_fingerprint: Option<String>,
+    /// Bastion(s) to connect through, in ssh `-J` form:
+    /// `[user@]host[:port]`, comma-separated for several hops.
+    #[serde(default)]
+    pub jump_host: Option<String>,
+    /// Refuse host keys that are not already in known_hosts instead of
+    /// adding them on first connect.
+    #[serde(default)]
+    pub strict_host_key: Option<bool>,
+}
+
+impl SshHostConfig {
+    /// Whether ssh should run with `StrictHostKeyChecking=yes`.
+    fn wants_strict_host_key(&self) -> bool {
+        self.strict_host_key == Some(true) || pinned_fingerprint(self).is_some()
+    }
+}
+
+/// The configured jump spec, ignoring a blank value.
+fn jump_host_spec(config: &SshHostConfig) -> Option<&str> {
+    config
+        .jump_host
+        .as_deref()
+        .map(str::trim)
+        .filter(|spec| !spec.is_empty())
+}
+
+/// Check a ProxyJump spec before it reaches the ssh command line.
+pub fn validate_jump_host(spec: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid jump host: {spec} (expected [user@]host[:port])");
+    for hop in spec.split(',').map(str::trim) {
+        let (user, rest) = match hop.rsplit_once('@') {
+            Some((user, rest)) => (Some(user), rest),
+            None => (None, hop),
+        };
+        if user.is_some_and(|u| {
+            u.is_empty() || !u.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        }) {
+            return Err(invalid());
+        }
+        let (host, port) = if let Some(v6) = rest.strip_prefix('[') {
+            let (host, tail) = v6.split_once(']').ok_or_else(invalid)?;
+            (host, tail.strip_prefix(':'))
+        } else {
+            match rest.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (rest, None),
+            }
+        };
+        if host.is_empty()
+            || host.starts_with('-')
+            || !host.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | ':'))
+        {
+            return Err(invalid());
+        }
+        if port.is_some_and(|p| p.parse::<u16>().map(|p| p == 0).unwrap_or(true)) {
+            return Err(invalid());
+        }
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +120,36 @@ fn build_sftp_write_command(path: &str, b64: &str) -> String {
     )
 }
 
+/// Remote write that takes the base64 payload on stdin instead of argv, so
+/// large files don't hit the argument length limit.
+fn build_sftp_write_stdin_command(path: &str) -> String {
+    let quoted = shell_quote(path);
+    format!(
+        "mkdir -p \"$(dirname {quoted})\" && ({decode}) > {quoted}",
+        decode = base64_decode_pipeline(),
+    )
+}
+
+/// Remote read matching `build_sftp_write_command`: the file is sent back
+/// base64-encoded so the transport is byte-exact.
+fn build_sftp_read_command(path: &str) -> String {
+    format!("base64 < {}", shell_quote(path))
+}
+
+/// Decode `base64` command output into raw bytes (GNU base64 wraps lines).
+pub(crate) fn decode_base64_output(stdout: &str) -> Result<Vec<u8>, String> {
+    let compact: String = stdout.chars().filter(|c| !c.is_whitespace()).collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(compact)
+        .map_err(|e| format!("Failed to decode remote file: {e}"))
+}
+
+/// Decode the output of `build_sftp_read_command`.
+fn decode_sftp_read_output(stdout: &str) -> Result<String, String> {
+    let bytes = decode_base64_output(stdout)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
 fn is_legacy_clawpal_master_for_host(command: &str, host: &str, username: Option<&str>) -> bool {
     if !command.contains(".local/state/.ssh-connection") {
         return false;
@@ -76,6 +169,34 @@ fn is_legacy_clawpal_master_for_host(command: &str, host: &str, username: Option
     false
 }
 
+/// Wrap a command with login shell setup (sources profile for PATH) so
+/// user-installed tools such as `openclaw` resolve over non-interactive SSH.
+pub(crate) fn login_shell_wrap(command: &str) -> String {
+    let target_bin = command.split_whitespace().next().unwrap_or("");
+    format!(
+        concat!(
+            "setopt nonomatch 2>/dev/null; shopt -s nullglob 2>/dev/null; ",
+            ". \"$HOME/.profile\" 2>/dev/null; ",
+            ". \"$HOME/.bashrc\" 2>/dev/null; ",
+            ". \"$HOME/.zshrc\" 2>/dev/null; ",
+            "[ -d \"$HOME/.local/bin\" ] && export PATH=\"$HOME/.local/bin:$PATH\"; ",
+            "export NVM_DIR=\"${{NVM_DIR:-$HOME/.nvm}}\"; ",
+            "[ -s \"$NVM_DIR/nvm.sh\" ] && . \"$NVM_DIR/nvm.sh\" 2>/dev/null; ",
+            "for _fnm in \"$HOME/.fnm/fnm\" \"$HOME/.local/bin/fnm\"; do ",
+              "[ -x \"$_fnm\" ] && eval \"$($_fnm env --shell bash 2>/dev/null || $_fnm env 2>/dev/null)\" 2>/dev/null && break; ",
+            "done; ",
+            "if ! command -v {target_bin} >/dev/null 2>&1; then ",
+              "for d in \"$HOME\"/.nvm/versions/node/*/bin; do ",
+                "[ -x \"$d/{target_bin}\" ] && export PATH=\"$d:$PATH\" && break; ",
+              "done; ",
+            "fi; ",
+            "{command}"
+        ),
+        target_bin = target_bin,
+        command = command
+    )
+}
+
 /// Check if an SSH exec error is likely transient (worth retrying) vs permanent.
 fn is_transient_ssh_error(err: &str) -> bool {
     let lower = err.to_lowercase();
@@ -134,6 +255,8 @@ mod inner {
         connections: Mutex<HashMap<String, SshConnection>>,
         forwards: Mutex<HashMap<String, PortForward>>,
         lifecycle: Mutex<()>,
+        /// In-flight cancellable operations keyed by operation id.
+        pub(super) operations: Mutex<HashMap<String, RunningOperation>>,
     }
 
     impl SshConnectionPool {
@@ -142,11 +265,17 @@ mod inner {
                 connections: Mutex::new(HashMap::new()),
                 forwards: Mutex::new(HashMap::new()),
                 lifecycle: Mutex::new(()),
+                operations: Mutex::new(HashMap::new()),
             }
         }
 
         pub async fn connect(&self, config: &SshHostConfig) -> Result<(), String> {
             let _lifecycle_guard = self.lifecycle.lock().await;
+            if let Some(jump) = super::jump_host_spec(config) {
+                super::validate_jump_host(jump)?;
+            }
+            super::verify_host_fingerprint(config).await?;
+            super::ensure_known_host_key(config).await?;
 
             if config.auth_method == "password" {
                 let output = Self::run_password_ssh(config, "echo $HOME", 20).await?;
@@ -189,11 +318,23 @@ mod inner {
             };
 
             let mut builder = SessionBuilder::default();
-            builder.known_hosts_check(KnownHosts::Add);
+            // A pin has been verified and written to its own known_hosts file
+            // above; the session checks against that file alone.
+            if let Some(pinned_config) = super::write_pinned_ssh_config(config)? {
+                builder.config_file(pinned_config);
+            }
+            if config.wants_strict_host_key() {
+                builder.known_hosts_check(KnownHosts::Strict);
+            } else {
+                builder.known_hosts_check(KnownHosts::Add);
+            }
 
             if config.port != 22 {
                 builder.port(config.port);
             }
+            if let Some(jump) = super::jump_host_spec(config) {
+                builder.jump_hosts(jump.split(',').map(str::trim));
+            }
 
             builder.server_alive_interval(std::time::Duration::from_secs(30));
             builder.connect_timeout(std::time::Duration::from_secs(15));
@@ -588,6 +729,16 @@ mod inner {
             config: &SshHostConfig,
             command: &str,
             timeout_secs: u64,
+        ) -> Result<std::process::Output, String> {
+            Self::run_password_ssh_with_stdin(config, command, None, timeout_secs).await
+        }
+
+        /// Run a command over `sshpass`-wrapped ssh, optionally feeding `stdin`.
+        async fn run_password_ssh_with_stdin(
+            config: &SshHostConfig,
+            command: &str,
+            stdin: Option<&[u8]>,
+            timeout_secs: u64,
         ) -> Result<std::process::Output, String> {
             let password = config
                 .password
@@ -598,9 +749,14 @@ mod inner {
             } else {
                 format!("{}@{}", config.username, config.host)
             };
+            let strict = if config.wants_strict_host_key() {
+                "StrictHostKeyChecking=yes"
+            } else {
+                "StrictHostKeyChecking=accept-new"
+            };
             let mut args = vec![
                 "-o".to_string(),
-                "StrictHostKeyChecking=accept-new".to_string(),
+                strict.to_string(),
                 "-o".to_string(),
                 "ConnectTimeout=15".to_string(),
                 "-o".to_string(),
@@ -610,6 +766,14 @@ mod inner {
                 "-o".to_string(),
                 "PubkeyAuthentication=no".to_string(),
             ];
+            for opt in super::pinned_host_key_options(config) {
+                args.push("-o".to_string());
+                args.push(opt);
+            }
+            if let Some(jump) = super::jump_host_spec(config) {
+                args.push("-J".to_string());
+                args.push(jump.to_string());
+            }
             if config.port != 22 {
                 args.push("-p".to_string());
                 args.push(config.port.to_string());
@@ -617,59 +781,69 @@ mod inner {
             args.push(dest);
             args.push(command.to_string());
 
-            tokio::time::timeout(
-                std::time::Duration::from_secs(timeout_secs),
-                Command::new("sshpass")
-                    .arg("-p")
-                    .arg(password)
-                    .arg("ssh")
-                    .args(args)
-                    .output(),
-            )
-            .await
-            .map_err(|_| format!("SSH command timed out after {timeout_secs}s"))?
-            .map_err(|e| {
+            let spawn_err = |e: std::io::Error| {
                 if e.kind() == std::io::ErrorKind::NotFound {
                     "Password auth requires `sshpass` to be installed on this system.".to_string()
                 } else {
                     format!("Failed to execute sshpass: {e}")
                 }
-            })
+            };
+            let mut cmd = Command::new("sshpass");
+            cmd.arg("-p").arg(password).arg("ssh").args(args);
+            let run = async {
+                let Some(input) = stdin else {
+                    return cmd.output().await.map_err(spawn_err);
+                };
+                cmd.stdin(std::process::Stdio::piped())
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::piped());
+                let mut child = cmd.spawn().map_err(spawn_err)?;
+                if let Some(mut pipe) = child.stdin.take() {
+                    use tokio::io::AsyncWriteExt;
+                    pipe.write_all(input)
+                        .await
+                        .map_err(|e| format!("Failed to send data over ssh: {e}"))?;
+                }
+                child
+                    .wait_with_output()
+                    .await
+                    .map_err(|e| format!("Failed to execute sshpass: {e}"))
+            };
+            tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), run)
+                .await
+                .map_err(|_| format!("SSH command timed out after {timeout_secs}s"))?
+        }
+
+        /// Config of a connected password-auth host. Those connections have
+        /// no openssh session, so file transfer goes through `sshpass` directly.
+        async fn password_host_config(&self, id: &str) -> Option<SshHostConfig> {
+            let pool = self.connections.lock().await;
+            pool.get(id)
+                .filter(|conn| conn.config.auth_method == "password")
+                .map(|conn| conn.config.clone())
         }
 
         /// Execute a command with login shell setup (sources profile for PATH).
         /// Forces bash to avoid zsh glob/nomatch quirks.
         pub async fn exec_login(&self, id: &str, command: &str) -> Result<SshExecResult, String> {
-            let target_bin = command.split_whitespace().next().unwrap_or("");
-            let wrapped = format!(
-                concat!(
-                    "setopt nonomatch 2>/dev/null; shopt -s nullglob 2>/dev/null; ",
-                    ". \"$HOME/.profile\" 2>/dev/null; ",
-                    ". \"$HOME/.bashrc\" 2>/dev/null; ",
-                    ". \"$HOME/.zshrc\" 2>/dev/null; ",
-                    "[ -d \"$HOME/.local/bin\" ] && export PATH=\"$HOME/.local/bin:$PATH\"; ",
-                    "export NVM_DIR=\"${{NVM_DIR:-$HOME/.nvm}}\"; ",
-                    "[ -s \"$NVM_DIR/nvm.sh\" ] && . \"$NVM_DIR/nvm.sh\" 2>/dev/null; ",
-                    "for _fnm in \"$HOME/.fnm/fnm\" \"$HOME/.local/bin/fnm\"; do ",
-                      "[ -x \"$_fnm\" ] && eval \"$($_fnm env --shell bash 2>/dev/null || $_fnm env 2>/dev/null)\" 2>/dev/null && break; ",
-                    "done; ",
-                    "if ! command -v {target_bin} >/dev/null 2>&1; then ",
-                      "for d in \"$HOME\"/.nvm/versions/node/*/bin; do ",
-                        "[ -x \"$d/{target_bin}\" ] && export PATH=\"$d:$PATH\" && break; ",
-                      "done; ",
-                    "fi; ",
-                    "{command}"
-                ),
-                target_bin = target_bin,
-                command = command
-            );
-            self.exec(id, &wrapped).await
+            self.exec(id, &login_shell_wrap(command)).await
         }
 
         // -- SFTP-equivalent operations via exec ------------------------------
 
         pub async fn sftp_read(&self, id: &str, path: &str) -> Result<String, String> {
             let resolved = self.resolve_path(id, path).await?;
+            if let Some(config) = self.password_host_config(id).await {
+                let output =
+                    Self::run_password_ssh(&config, &build_sftp_read_command(&resolved), 120).await?;
+                if !output.status.success() {
+                    return Err(format!(
+                        "Failed to read {resolved}: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ));
+                }
+                return decode_sftp_read_output(&String::from_utf8_lossy(&output.stdout));
+            }
             let cmd = format!("cat {}", shell_quote(&resolved));
             let result = self.exec(id, &cmd).await?;
             if result.exit_code != 0 {
@@ -684,6 +858,22 @@ mod inner {
         pub async fn sftp_write(&self, id: &str, path: &str, content: &str) -> Result<(), String> {
             let resolved = self.resolve_path(id, path).await?;
             let b64 = base64::engine::general_purpose::STANDARD.encode(content.as_bytes());
+            if let Some(config) = self.password_host_config(id).await {
+                let output = Self::run_password_ssh_with_stdin(
+                    &config,
+                    &build_sftp_write_stdin_command(&resolved),
+                    Some(b64.as_bytes()),
+                    120,
+                )
+                .await?;
+                if !output.status.success() {
+                    return Err(format!(
+                        "Failed to write {resolved}: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ));
+                }
+                return Ok(());
+            }
             let cmd = build_sftp_write_command(&resolved, &b64);
             let result = self.exec(id, &cmd).await?;
             if result.exit_code != 0 {
@@ -796,11 +986,23 @@ mod inner {
             args.push("-o".into());
             args.push("BatchMode=yes".into());
             args.push("-o".into());
-            args.push("StrictHostKeyChecking=accept-new".into());
+            if self.config.wants_strict_host_key() {
+                args.push("StrictHostKeyChecking=yes".into());
+            } else {
+                args.push("StrictHostKeyChecking=accept-new".into());
+            }
             args.push("-o".into());
             args.push("ConnectTimeout=15".into());
             args.push("-o".into());
             args.push("ServerAliveInterval=30".into());
+            for opt in super::pinned_host_key_options(&self.config) {
+                args.push("-o".into());
+                args.push(opt);
+            }
+            if let Some(jump) = super::jump_host_spec(&self.config) {
+                args.push("-J".into());
+                args.push(jump.to_string());
+            }
             if self.config.port != 22 {
                 args.push("-p".into());
                 args.push(self.config.port.to_string());
@@ -828,6 +1030,8 @@ mod inner {
         lifecycle: Mutex<()>,
         /// Bound SSH process concurrency to avoid process pileups during UI refresh bursts.
         exec_limit: Arc<tokio::sync::Semaphore>,
+        /// In-flight cancellable operations keyed by operation id.
+        pub(super) operations: Mutex<HashMap<String, RunningOperation>>,
     }
 
     impl SshConnectionPool {
@@ -837,6 +1041,7 @@ mod inner {
                 port_forwards: Mutex::new(HashMap::new()),
                 lifecycle: Mutex::new(()),
                 exec_limit: Arc::new(tokio::sync::Semaphore::new(4)),
+                operations: Mutex::new(HashMap::new()),
             }
         }
 
@@ -862,6 +1067,11 @@ mod inner {
 
         pub async fn connect(&self, config: &SshHostConfig) -> Result<(), String> {
             let _lifecycle_guard = self.lifecycle.lock().await;
+            if let Some(jump) = super::jump_host_spec(config) {
+                super::validate_jump_host(jump)?;
+            }
+            super::verify_host_fingerprint(config).await?;
+            super::ensure_known_host_key(config).await?;
             if config.auth_method == "password" {
                 return Err("Password authentication is not supported. \
                      Please use SSH Config or Private Key mode instead."
@@ -1113,30 +1323,7 @@ mod inner {
         }
 
         pub async fn exec_login(&self, id: &str, command: &str) -> Result<SshExecResult, String> {
-            let target_bin = command.split_whitespace().next().unwrap_or("");
-            let wrapped = format!(
-                concat!(
-                    "setopt nonomatch 2>/dev/null; shopt -s nullglob 2>/dev/null; ",
-                    ". \"$HOME/.profile\" 2>/dev/null; ",
-                    ". \"$HOME/.bashrc\" 2>/dev/null; ",
-                    ". \"$HOME/.zshrc\" 2>/dev/null; ",
-                    "[ -d \"$HOME/.local/bin\" ] && export PATH=\"$HOME/.local/bin:$PATH\"; ",
-                    "export NVM_DIR=\"${{NVM_DIR:-$HOME/.nvm}}\"; ",
-                    "[ -s \"$NVM_DIR/nvm.sh\" ] && . \"$NVM_DIR/nvm.sh\" 2>/dev/null; ",
-                    "for _fnm in \"$HOME/.fnm/fnm\" \"$HOME/.local/bin/fnm\"; do ",
-                      "[ -x \"$_fnm\" ] && eval \"$($_fnm env --shell bash 2>/dev/null || $_fnm env 2>/dev/null)\" 2>/dev/null && break; ",
-                    "done; ",
-                    "if ! command -v {target_bin} >/dev/null 2>&1; then ",
-                      "for d in \"$HOME\"/.nvm/versions/node/*/bin; do ",
-                        "[ -x \"$d/{target_bin}\" ] && export PATH=\"$d:$PATH\" && break; ",
-                      "done; ",
-                    "fi; ",
-                    "{command}"
-                ),
-                target_bin = target_bin,
-                command = command
-            );
-            self.exec(id, &wrapped).await
+            self.exec(id, &login_shell_wrap(command)).await
         }
 
         pub async fn sftp_read(&self, id: &str, path: &str) -> Result<String, String> {
@@ -1225,10 +1412,472 @@ mod inner {
 
 pub use inner::SshConnectionPool;
 
+// ---------------------------------------------------------------------------
+// Host key fingerprints (shared by both implementations)
+// ---------------------------------------------------------------------------
+
+/// Resolve the real hostname and port for a host entry. `ssh_config` hosts
+/// may be aliases, so ask `ssh -G` for the effective values.
+async fn resolve_host_endpoint(config: &SshHostConfig) -> (String, u16) {
+    if config.auth_method == "ssh_config" {
+        if let Ok(output) = tokio::process::Command::new("ssh")
+            .args(["-G", &config.host])
+            .output()
+            .await
+        {
+            let text = String::from_utf8_lossy(&output.stdout);
+            let mut host = config.host.clone();
+            let mut port = config.port;
+            for line in text.lines() {
+                match line.split_once(' ') {
+                    Some(("hostname", v)) => host = v.trim().to_string(),
+                    Some(("port", v)) => port = v.trim().parse().unwrap_or(port),
+                    _ => {}
+                }
+            }
+            return (host, port);
+        }
+    }
+    (config.host.clone(), config.port)
+}
+
+/// Program and arguments that collect the host's keys. A host behind a jump
+/// host is usually not reachable directly, so instead of `ssh-keyscan` a
+/// batch-mode `ssh -J` handshake records the key into `scan_file`; the key
+/// exchange happens before authentication, so no login is needed.
+fn host_key_scan_command(
+    config: &SshHostConfig,
+    host: &str,
+    port: u16,
+    scan_file: &std::path::Path,
+) -> (&'static str, Vec<String>) {
+    let Some(jump) = jump_host_spec(config) else {
+        return (
+            "ssh-keyscan",
+            vec!["-T".into(), "10".into(), "-p".into(), port.to_string(), host.to_string()],
+        );
+    };
+    let mut args = vec!["-J".to_string(), jump.to_string(), "-p".into(), port.to_string()];
+    for opt in [
+        "BatchMode=yes".to_string(),
+        "ConnectTimeout=10".to_string(),
+        "StrictHostKeyChecking=accept-new".to_string(),
+        "HashKnownHosts=no".to_string(),
+        "GlobalKnownHostsFile=/dev/null".to_string(),
+        format!("UserKnownHostsFile={}", scan_file.display()),
+    ] {
+        args.push("-o".into());
+        args.push(opt);
+    }
+    args.push(host.to_string());
+    args.push("exit".into());
+    ("ssh", args)
+}
+
+/// Fetch the host keys currently presented by the server, as known_hosts
+/// lines.
+async fn scan_host_keys(config: &SshHostConfig) -> Result<Vec<String>, String> {
+    let (host, port) = resolve_host_endpoint(config).await;
+    let scan_file = std::env::temp_dir().join(format!(
+        "clawpal-hostkeys-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0)
+    ));
+    let (program, args) = host_key_scan_command(config, &host, port, &scan_file);
+    let scan = tokio::time::timeout(
+        std::time::Duration::from_secs(15),
+        tokio::process::Command::new(program).args(&args).output(),
+    )
+    .await
+    .map_err(|_| format!("Host key scan timed out for {host}"))?
+    .map_err(|e| format!("Failed to run {program}: {e}"))?;
+    let text = if program == "ssh-keyscan" {
+        String::from_utf8_lossy(&scan.stdout).to_string()
+    } else {
+        let text = std::fs::read_to_string(&scan_file).unwrap_or_default();
+        let _ = std::fs::remove_file(&scan_file);
+        text
+    };
+    let keys = parse_known_hosts_lines(&text);
+    if keys.is_empty() {
+        return Err(format!("No host keys returned by {host}:{port}"));
+    }
+    Ok(keys)
+}
+
+/// Non-comment, non-empty lines of known_hosts-format text.
+fn parse_known_hosts_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// SHA256 fingerprint of a single known_hosts line.
+async fn fingerprint_key_line(line: &str) -> Option<String> {
+    let mut child = tokio::process::Command::new("ssh-keygen")
+        .args(["-lf", "-"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .ok()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        use tokio::io::AsyncWriteExt;
+        stdin.write_all(format!("{line}\n").as_bytes()).await.ok()?;
+    }
+    let output = child.wait_with_output().await.ok()?;
+    parse_keygen_fingerprints(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .next()
+}
+
+/// Fetch the host keys currently presented by the server and return their
+/// SHA256 fingerprints (one per key type).
+pub async fn scan_host_fingerprints(config: &SshHostConfig) -> Result<Vec<String>, String> {
+    let mut fingerprints = Vec::new();
+    for line in scan_host_keys(config).await? {
+        if let Some(fp) = fingerprint_key_line(&line).await {
+            if !fingerprints.contains(&fp) {
+                fingerprints.push(fp);
+            }
+        }
+    }
+    if fingerprints.is_empty() {
+        return Err(format!("Could not fingerprint host keys for {}", config.host));
+    }
+    Ok(fingerprints)
+}
+
+/// Extract the fingerprint column from `ssh-keygen -l` output lines such as
+/// `256 SHA256:abc... host (ED25519)`.
+fn parse_keygen_fingerprints(text: &str) -> Vec<String> {
+    let mut out: Vec<String> = text
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .filter(|fp| fp.starts_with("SHA256:"))
+        .map(str::to_string)
+        .collect();
+    out.dedup();
+    out
+}
+
+/// When the host has a pinned fingerprint, refuse to continue unless the
+/// server currently presents a matching key. The matching key is then
+/// written to the host's own pinned known_hosts file, which the real session
+/// checks strictly and exclusively, so no other stored key is accepted.
+async fn verify_host_fingerprint(config: &SshHostConfig) -> Result<(), String> {
+    let Some(expected) = pinned_fingerprint(config) else {
+        return Ok(());
+    };
+    let mut presented = Vec::new();
+    for line in scan_host_keys(config).await? {
+        let Some(fp) = fingerprint_key_line(&line).await else {
+            continue;
+        };
+        if fp == expected {
+            let blob = known_hosts_key_blob(&line)
+                .ok_or_else(|| format!("Malformed host key line for {}", config.host))?;
+            return write_pinned_known_hosts(config, &blob);
+        }
+        presented.push(fp);
+    }
+    Err(format!(
+        "Host key verification failed for {}: expected {expected}, server presented {}",
+        config.host,
+        presented.join(", ")
+    ))
+}
+
+/// The pinned fingerprint, ignoring a blank value.
+pub(crate) fn pinned_fingerprint(config: &SshHostConfig) -> Option<&str> {
+    config
+        .host_fingerprint
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// `type base64` part of a known_hosts line, ignoring the host column.
+fn known_hosts_key_blob(line: &str) -> Option<String> {
+    let mut fields = line.split_whitespace().skip(1);
+    Some(format!("{} {}", fields.next()?, fields.next()?))
+}
+
+/// Directory holding one known_hosts file per pinned host.
+fn pinned_known_hosts_dir() -> Option<std::path::PathBuf> {
+    dirs::home_dir().map(|home| home.join(".clawpal").join("known_hosts"))
+}
+
+/// The dedicated known_hosts file for a pinned host, or `None` when the host
+/// has no pin.
+fn pinned_known_hosts_file(config: &SshHostConfig) -> Option<std::path::PathBuf> {
+    pinned_fingerprint(config)?;
+    let name: String = config
+        .id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    pinned_known_hosts_dir().map(|dir| dir.join(name))
+}
+
+/// Replace the host's pinned known_hosts file with the single verified key.
+/// The host column is `*` because the file only ever serves this host, which
+/// sidesteps aliases, ports and `HostKeyAlias`.
+fn write_pinned_known_hosts(config: &SshHostConfig, key_blob: &str) -> Result<(), String> {
+    let path = pinned_known_hosts_file(config).ok_or("Cannot determine home directory")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700));
+        }
+    }
+    std::fs::write(&path, format!("* {key_blob}\n"))
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Quote a path for an ssh option value (`-o` or config file).
+fn ssh_option_path(path: &std::path::Path) -> String {
+    format!("\"{}\"", path.display())
+}
+
+/// `-o` options that restrict host key checking to the pinned key: the
+/// host's own known_hosts file and no global one. Empty for unpinned hosts.
+fn pinned_host_key_options(config: &SshHostConfig) -> Vec<String> {
+    let Some(file) = pinned_known_hosts_file(config) else {
+        return Vec::new();
+    };
+    vec![
+        format!("UserKnownHostsFile={}", ssh_option_path(&file)),
+        "GlobalKnownHostsFile=/dev/null".to_string(),
+    ]
+}
+
+/// ssh config text for a pinned host's session: the pinned known_hosts
+/// options for the destination only (a jump host keeps its usual checks),
+/// followed by the user's and system config so everything else still applies.
+fn pinned_ssh_config_text(config: &SshHostConfig, known_hosts: &std::path::Path, home: &std::path::Path) -> String {
+    format!(
+        "Host {host}\n  UserKnownHostsFile {file}\n  GlobalKnownHostsFile /dev/null\n\
+         Host *\n  Include {user_config}\n  Include /etc/ssh/ssh_config\n",
+        host = config.host,
+        file = ssh_option_path(known_hosts),
+        user_config = ssh_option_path(&home.join(".ssh").join("config")),
+    )
+}
+
+/// Write the ssh config used for a pinned host's session, for ssh clients
+/// that take a config file rather than `-o` options.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn write_pinned_ssh_config(config: &SshHostConfig) -> Result<Option<std::path::PathBuf>, String> {
+    let Some(known_hosts) = pinned_known_hosts_file(config) else {
+        return Ok(None);
+    };
+    let home = dirs::home_dir().ok_or("Cannot determine home directory")?;
+    let path = known_hosts.with_extension("ssh_config");
+    std::fs::write(&path, pinned_ssh_config_text(config, &known_hosts, &home))
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    Ok(Some(path))
+}
+
+/// With strict host key checking, refuse up front when known_hosts has no
+/// entry for the host, naming the key the server presents so the user can
+/// decide whether to trust it. A pinned host has already been verified and
+/// recorded in its own file by `verify_host_fingerprint`.
+async fn ensure_known_host_key(config: &SshHostConfig) -> Result<(), String> {
+    if !config.wants_strict_host_key()
+        || pinned_fingerprint(config).is_some()
+        || has_known_hosts_entry(config).await
+    {
+        return Ok(());
+    }
+    let presented = scan_host_fingerprints(config)
+        .await
+        .map(|fps| fps.join(", "))
+        .unwrap_or_else(|e| format!("an unknown key ({e})"));
+    Err(format!(
+        "Host key for {} is not in known_hosts and strict host key checking is on. \
+         The server presented {presented}; add it to known_hosts, or pin its \
+         fingerprint so ClawPal can record it.",
+        config.host
+    ))
+}
+
+/// known_hosts lines recorded for this host, or `None` when there are none.
+async fn known_hosts_entries(config: &SshHostConfig) -> Option<Vec<String>> {
+    let (host, port) = resolve_host_endpoint(config).await;
+    let lookup = if port == 22 {
+        host
+    } else {
+        format!("[{host}]:{port}")
+    };
+    let output = tokio::process::Command::new("ssh-keygen")
+        .args(["-F", &lookup])
+        .output()
+        .await
+        .ok()?;
+    let entries = parse_known_hosts_lines(&String::from_utf8_lossy(&output.stdout));
+    (output.status.success() && !entries.is_empty()).then_some(entries)
+}
+
+/// Whether the user's known_hosts already has an entry for this host, so
+/// strict checking will not reject it.
+async fn has_known_hosts_entry(config: &SshHostConfig) -> bool {
+    known_hosts_entries(config).await.is_some()
+}
+
+// ---------------------------------------------------------------------------
+// Connection health (shared by both implementations)
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SshHealth {
+    pub connected: bool,
+    pub latency_ms: Option<u64>,
+    pub home_dir: Option<String>,
+}
+
+impl SshConnectionPool {
+    /// Round-trip a trivial command to measure latency. Unlike `is_connected`
+    /// this actually exercises the link, so stale sessions report as down.
+    pub async fn health(&self, id: &str) -> SshHealth {
+        let Ok(home_dir) = self.get_home_dir(id).await else {
+            return SshHealth {
+                connected: false,
+                latency_ms: None,
+                home_dir: None,
+            };
+        };
+        let started = std::time::Instant::now();
+        let probe = tokio::time::timeout(std::time::Duration::from_secs(10), self.exec(id, "echo ok")).await;
+        let alive = matches!(probe, Ok(Ok(ref r)) if r.exit_code == 0 && r.stdout.trim() == "ok");
+        SshHealth {
+            connected: alive,
+            latency_ms: alive.then(|| started.elapsed().as_millis() as u64),
+            home_dir: Some(home_dir),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cancellable operations (shared by both implementations)
+// ---------------------------------------------------------------------------
+
+/// A long-running remote command that can be aborted by its operation id.
+pub struct RunningOperation {
+    host_id: String,
+    cancel: std::sync::Arc<tokio::sync::Notify>,
+}
+
+/// Remote file holding the PID of the shell running an operation.
+fn operation_pid_file(op_id: &str) -> String {
+    format!("~/.clawpal/ops/{op_id}.pid")
+}
+
+fn validate_operation_id(op_id: &str) -> Result<(), String> {
+    if op_id.is_empty()
+        || !op_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid operation id: {op_id}"));
+    }
+    Ok(())
+}
+
+impl SshConnectionPool {
+    /// Execute a command that can later be aborted via `cancel_operation`.
+    /// Without an `op_id` this is a plain `exec`/`exec_login`.
+    pub async fn exec_cancellable(
+        &self,
+        id: &str,
+        op_id: Option<&str>,
+        command: &str,
+        login: bool,
+    ) -> Result<SshExecResult, String> {
+        let command = if login {
+            login_shell_wrap(command)
+        } else {
+            command.to_string()
+        };
+        let Some(op_id) = op_id else {
+            return self.exec(id, &command).await;
+        };
+        validate_operation_id(op_id)?;
+
+        let cancel = std::sync::Arc::new(tokio::sync::Notify::new());
+        {
+            let mut ops = self.operations.lock().await;
+            if ops.contains_key(op_id) {
+                return Err(format!("Operation {op_id} is already running"));
+            }
+            ops.insert(
+                op_id.to_string(),
+                RunningOperation {
+                    host_id: id.to_string(),
+                    cancel: cancel.clone(),
+                },
+            );
+        }
+
+        // Record the remote shell PID first so cancellation can kill the
+        // whole process group instead of orphaning it on the host.
+        let pid_file = operation_pid_file(op_id);
+        let tracked = format!("mkdir -p ~/.clawpal/ops && echo $$ > {pid_file}; {command}");
+        let result = tokio::select! {
+            res = self.exec(id, &tracked) => res,
+            _ = cancel.notified() => Err(format!("Operation {op_id} was cancelled")),
+        };
+
+        if self.operations.lock().await.remove(op_id).is_some() {
+            let _ = self.exec(id, &format!("rm -f {pid_file}")).await;
+        }
+        result
+    }
+
+    /// Abort a running operation: kill its remote process group, then
+    /// resolve the local future with a cancellation error.
+    pub async fn cancel_operation(&self, op_id: &str) -> Result<bool, String> {
+        validate_operation_id(op_id)?;
+        let Some(op) = self.operations.lock().await.remove(op_id) else {
+            return Ok(false);
+        };
+        let pid_file = operation_pid_file(op_id);
+        let kill_cmd = format!(
+            "pid=$(cat {pid_file} 2>/dev/null); \
+             if [ -n \"$pid\" ]; then kill -TERM -- -\"$pid\" 2>/dev/null || {{ pkill -TERM -P \"$pid\" 2>/dev/null; kill -TERM \"$pid\" 2>/dev/null; }}; fi; \
+             rm -f {pid_file}"
+        );
+        let kill_result = self.exec(&op.host_id, &kill_cmd).await;
+        op.cancel.notify_one();
+        kill_result.map(|_| true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_jump_host() {
+        assert!(validate_jump_host("bastion").is_ok());
+        assert!(validate_jump_host("ops@bastion.example.com:2222").is_ok());
+        assert!(validate_jump_host("a@hop1, hop2:22").is_ok());
+        assert!(validate_jump_host("[fe80::1]:22").is_ok());
+        assert!(validate_jump_host("").is_err());
+        assert!(validate_jump_host("-oProxyCommand=x").is_err());
+        assert!(validate_jump_host("host:0").is_err());
+        assert!(validate_jump_host("host;rm").is_err());
+        assert!(validate_jump_host("@host").is_err());
+    }
+
     #[test]
     fn test_base64_decode_pipeline_is_cross_platform() {
         let pipe = base64_decode_pipeline();
@@ -1244,6 +1893,16 @@ mod tests {
         assert!(cmd.contains("base64 -D"));
     }
 
+    #[test]
+    fn test_sftp_read_output_roundtrip() {
+        let cmd = build_sftp_read_command("/tmp/a b.txt");
+        assert_eq!(cmd, "base64 < '/tmp/a b.txt'");
+        // GNU base64 wraps output at 76 columns.
+        let encoded = "aGVsbG8g\nd29ybGQ=\n";
+        assert_eq!(decode_sftp_read_output(encoded).unwrap(), "hello world");
+        assert!(build_sftp_write_stdin_command("/tmp/a.txt").contains("base64 -d"));
+    }
+
     #[test]
     fn test_legacy_cleanup_match_with_username_host() {
         let cmd = "ssh -E /Users/a/.local/state/.ssh-connectionXYZ/log -S /Users/a/.local/state/.ssh-connectionXYZ/master -M -f -N -o ControlPersist=yes ubuntu@vm1";
@@ -1269,4 +1928,90 @@ mod tests {
             Some("ubuntu")
         ));
     }
+
+    #[test]
+    fn test_parse_keygen_fingerprints() {
+        let out = "256 SHA256:abc123 vm1 (ED25519)\n3072 SHA256:def456 vm1 (RSA)\nnoise\n";
+        assert_eq!(
+            parse_keygen_fingerprints(out),
+            vec!["SHA256:abc123".to_string(), "SHA256:def456".to_string()]
+        );
+        assert!(parse_keygen_fingerprints("2048 MD5:aa:bb vm1 (RSA)\n").is_empty());
+    }
+
+    #[test]
+    fn test_jump_host_key_scan_never_uses_keyscan() {
+        let mut config = SshHostConfig {
+            id: "h".into(),
+            label: "h".into(),
+            host: "10.0.0.5".into(),
+            port: 22,
+            username: "u".into(),
+            auth_method: "key".into(),
+            key_path: None,
+            password: None,
+            host_fingerprint: Some("SHA256:x".into()),
+            jump_host: Some("ops@bastion:2222".into()),
+            strict_host_key: Some(true),
+        };
+        let file = std::path::Path::new("/tmp/scan");
+        let (program, args) = host_key_scan_command(&config, "10.0.0.5", 22, file);
+        assert_eq!(program, "ssh");
+        assert_eq!(&args[..2], &["-J".to_string(), "ops@bastion:2222".to_string()]);
+        assert!(args.contains(&"UserKnownHostsFile=/tmp/scan".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("exit"));
+
+        config.jump_host = None;
+        let (program, _) = host_key_scan_command(&config, "10.0.0.5", 22, file);
+        assert_eq!(program, "ssh-keyscan");
+    }
+
+    #[test]
+    fn test_pinned_host_only_trusts_its_own_known_hosts() {
+        let mut config = SshHostConfig {
+            id: "vm/1".into(),
+            label: "vm1".into(),
+            host: "vm1".into(),
+            port: 22,
+            username: "u".into(),
+            auth_method: "key".into(),
+            key_path: None,
+            password: None,
+            host_fingerprint: Some("SHA256:x".into()),
+            jump_host: Some("bastion".into()),
+            strict_host_key: None,
+        };
+        let opts = pinned_host_key_options(&config);
+        assert_eq!(opts.len(), 2);
+        assert!(opts[0].starts_with("UserKnownHostsFile=\""));
+        assert!(opts[0].ends_with("vm_1\""));
+        assert_eq!(opts[1], "GlobalKnownHostsFile=/dev/null");
+
+        let text = pinned_ssh_config_text(
+            &config,
+            std::path::Path::new("/home/u/.clawpal/known_hosts/vm_1"),
+            std::path::Path::new("/home/u"),
+        );
+        assert!(text.starts_with(
+            "Host vm1\n  UserKnownHostsFile \"/home/u/.clawpal/known_hosts/vm_1\"\n  GlobalKnownHostsFile /dev/null\n"
+        ));
+        assert!(text.contains("Host *\n  Include \"/home/u/.ssh/config\"\n"));
+
+        config.host_fingerprint = Some("  ".into());
+        assert!(pinned_host_key_options(&config).is_empty());
+        assert!(!config.wants_strict_host_key());
+    }
+
+    #[test]
+    fn test_known_hosts_key_blob_ignores_host_column() {
+        let scanned = "[vm1]:2222 ssh-ed25519 AAAAC3Nz";
+        let hashed = "|1|abc=|def= ssh-ed25519 AAAAC3Nz";
+        assert_eq!(known_hosts_key_blob(scanned), known_hosts_key_blob(hashed));
+        assert_eq!(known_hosts_key_blob(scanned).as_deref(), Some("ssh-ed25519 AAAAC3Nz"));
+        assert!(known_hosts_key_blob("host-only").is_none());
+        assert_eq!(
+            parse_known_hosts_lines("# vm1:22 SSH-2.0\nvm1 ssh-rsa AAAA\n\n"),
+            vec!["vm1 ssh-rsa AAAA".to_string()]
+        );
+    }
 }
@@ -18,6 +18,9 @@ fn vm1_config() -> SshHostConfig {
         auth_method: "ssh_config".into(),
         key_path: None,
         password: None,
+        host_fingerprint: None,
+        jump_host: None,
+        strict_host_key: None,
     }
 }
 
@@ -448,7 +448,7 @@ export function SessionAnalysisPanel() {
                                     setPreviewLoading(true);
                                     setPreviewOpen(true);
                                     ua.previewSession(agentData.agent, session.sessionId)
-                                      .then(setPreviewMessages)
+                                      .then((page) => setPreviewMessages(page.messages))
                                       .catch(() => setPreviewMessages([{ role: "error", content: t('doctor.failedLoadSession') }]))
                                       .finally(() => setPreviewLoading(false));
                                   }}
@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { AgentOverview, AgentSessionAnalysis, ApplyQueueResult, ApplyResult, BackupInfo, Binding, ChannelNode, CronJob, CronRun, DiscordGuildChannel, GatewayCredentials, HistoryItem, InstanceStatus, StatusExtra, ModelCatalogProvider, ModelProfile, PendingCommand, PreviewQueueResult, PreviewResult, ProviderAuthSuggestion, Recipe, ResolvedApiKey, SystemStatus, DoctorReport, SessionFile, SshHost, WatchdogStatus } from "./types";
+import type { AgentOverview, AgentSessionAnalysis, ApplyQueueResult, ApplyResult, BackupInfo, BackupVerification, Binding, ChannelNode, ConfigFinding, CronJob, CronRun, DiscordGuildChannel, GatewayCredentials, HistoryItem, InstanceStatus, StatusExtra, ModelCatalogProvider, ModelBinding, ModelProfile, ModelProfileMergeResult, OpenclawCommandOutput, PendingCommand, PreviewQueueResult, PreviewResult, ProviderAuthSuggestion, Recipe, ResolvedApiKey, SystemStatus, TelegramChannel, DoctorReport, SessionFile, SessionPreviewPage, SshHost, WatchdogStatus } from "./types";
 
 export const api = {
   getSystemStatus: (): Promise<SystemStatus> =>
@@ -24,12 +24,18 @@ export const api = {
     invoke("rollback", { snapshotId }),
   listModelProfiles: (): Promise<ModelProfile[]> =>
     invoke("list_model_profiles", {}),
-  extractModelProfilesFromConfig: (): Promise<{ created: number; reused: number; skippedInvalid: number }> =>
-    invoke("extract_model_profiles_from_config", {}),
+  extractModelProfilesFromConfig: (approved?: string[]): Promise<{ created: number; reused: number; skippedInvalid: number }> =>
+    invoke("extract_model_profiles_from_config", { approved }),
   upsertModelProfile: (profile: ModelProfile): Promise<ModelProfile> =>
     invoke("upsert_model_profile", { profile }),
-  deleteModelProfile: (profileId: string): Promise<boolean> =>
-    invoke("delete_model_profile", { profileId }),
+  deleteModelProfile: (profileId: string, force?: boolean): Promise<boolean> =>
+    invoke("delete_model_profile", { profileId, force }),
+  modelProfileUsage: (profileId: string): Promise<ModelBinding[]> =>
+    invoke("model_profile_usage", { profileId }),
+  findDuplicateModelProfiles: (): Promise<ModelProfile[][]> =>
+    invoke("find_duplicate_model_profiles", {}),
+  mergeModelProfiles: (keepId: string, removeIds: string[]): Promise<ModelProfileMergeResult> =>
+    invoke("merge_model_profiles", { keepId, removeIds }),
   resolveProviderAuth: (provider: string): Promise<ProviderAuthSuggestion> =>
     invoke("resolve_provider_auth", { provider }),
   resolveApiKeys: (): Promise<ResolvedApiKey[]> =>
@@ -38,8 +44,8 @@ export const api = {
     invoke("list_agents_overview", {}),
   createAgent: (agentId: string, modelValue?: string, independent?: boolean): Promise<AgentOverview> =>
     invoke("create_agent", { agentId, modelValue, independent }),
-  deleteAgent: (agentId: string): Promise<boolean> =>
-    invoke("delete_agent", { agentId }),
+  deleteAgent: (agentId: string, purgeData?: boolean): Promise<{ agentId: string; configRemoved: boolean; purgedPath: string | null; purgedBytes: number }> =>
+    invoke("delete_agent", { agentId, purgeData }),
   setupAgentIdentity: (agentId: string, name: string, emoji?: string): Promise<boolean> =>
     invoke("setup_agent_identity", { agentId, name, emoji }),
   listSessionFiles: (): Promise<SessionFile[]> =>
@@ -50,24 +56,32 @@ export const api = {
     invoke("analyze_sessions", {}),
   deleteSessionsByIds: (agentId: string, sessionIds: string[]): Promise<number> =>
     invoke("delete_sessions_by_ids", { agentId, sessionIds }),
-  previewSession: (agentId: string, sessionId: string): Promise<{ role: string; content: string }[]> =>
-    invoke("preview_session", { agentId, sessionId }),
+  previewSession: (agentId: string, sessionId: string, offset?: number, limit?: number): Promise<SessionPreviewPage> =>
+    invoke("preview_session", { agentId, sessionId, offset, limit }),
   runDoctor: (): Promise<DoctorReport> =>
     invoke("run_doctor_command", {}),
   fixIssues: (ids: string[]): Promise<{ ok: boolean; applied: string[]; remainingIssues: string[] }> =>
     invoke("fix_issues", { ids }),
   readRawConfig: (): Promise<string> =>
     invoke("read_raw_config", {}),
+  readRawConfigText: (): Promise<string> =>
+    invoke("read_raw_config_text", {}),
+  writeRawConfig: (content: string): Promise<boolean> =>
+    invoke("write_raw_config", { content }),
+  validateConfig: (content: string): Promise<ConfigFinding[]> =>
+    invoke("validate_config", { content }),
   openUrl: (url: string): Promise<void> =>
     invoke("open_url", { url }),
   chatViaOpenclaw: (agentId: string, message: string, sessionId?: string): Promise<Record<string, unknown>> =>
     invoke("chat_via_openclaw", { agentId, message, sessionId }),
-  backupBeforeUpgrade: (): Promise<BackupInfo> =>
-    invoke("backup_before_upgrade", {}),
+  verifyBackup: (backupName: string): Promise<BackupVerification> =>
+    invoke("verify_backup", { backupName }),
+  backupBeforeUpgrade: (format?: "dir" | "targz", maxBackups?: number): Promise<BackupInfo> =>
+    invoke("backup_before_upgrade", { format, maxBackups }),
   listBackups: (): Promise<BackupInfo[]> =>
     invoke("list_backups", {}),
-  restoreFromBackup: (backupName: string): Promise<string> =>
-    invoke("restore_from_backup", { backupName }),
+  restoreFromBackup: (backupName: string, components: string[] = []): Promise<string> =>
+    invoke("restore_from_backup", { backupName, components }),
   deleteBackup: (backupName: string): Promise<boolean> =>
     invoke("delete_backup", { backupName }),
   listChannelsMinimal: (): Promise<ChannelNode[]> =>
@@ -76,8 +90,16 @@ export const api = {
     invoke("list_discord_guild_channels", {}),
   refreshDiscordGuildChannels: (): Promise<DiscordGuildChannel[]> =>
     invoke("refresh_discord_guild_channels", {}),
+  refreshChannelNames: (platforms: string[]): Promise<ChannelNode[]> =>
+    invoke("refresh_channel_names", { platforms }),
+  listTelegramChannels: (): Promise<TelegramChannel[]> =>
+    invoke("list_telegram_channels", {}),
+  refreshTelegramChannels: (): Promise<TelegramChannel[]> =>
+    invoke("refresh_telegram_channels", {}),
   restartGateway: (): Promise<boolean> =>
     invoke("restart_gateway", {}),
+  runOpenclaw: (args: string[], timeoutSecs?: number): Promise<OpenclawCommandOutput> =>
+    invoke("run_openclaw", { args, timeoutSecs }),
   setGlobalModel: (modelValue: string | null): Promise<boolean> =>
     invoke("set_global_model", { modelValue }),
   setAgentModel: (agentId: string, modelValue: string | null): Promise<boolean> =>
@@ -107,8 +129,8 @@ export const api = {
     invoke("remote_get_system_status", { hostId }),
   remoteGetStatusExtra: (hostId: string): Promise<StatusExtra> =>
     invoke("remote_get_status_extra", { hostId }),
-  remoteListAgentsOverview: (hostId: string): Promise<AgentOverview[]> =>
-    invoke("remote_list_agents_overview", { hostId }),
+  remoteListAgentsOverview: (hostId: string, force?: boolean): Promise<AgentOverview[]> =>
+    invoke("remote_list_agents_overview", { hostId, force }),
   remoteListChannelsMinimal: (hostId: string): Promise<ChannelNode[]> =>
     invoke("remote_list_channels_minimal", { hostId }),
   remoteListBindings: (hostId: string): Promise<Binding[]> =>
@@ -121,6 +143,8 @@ export const api = {
     invoke("remote_list_discord_guild_channels", { hostId }),
   remoteRunDoctor: (hostId: string): Promise<DoctorReport> =>
     invoke("remote_run_doctor", { hostId }),
+  remoteRunOpenclaw: (hostId: string, args: string[], timeoutSecs?: number): Promise<OpenclawCommandOutput> =>
+    invoke("remote_run_openclaw", { hostId, args, timeoutSecs }),
   remoteFixIssues: (hostId: string, ids: string[]): Promise<{ ok: boolean; applied: string[]; remainingIssues: string[] }> =>
     invoke("remote_fix_issues", { hostId, ids }),
   remoteSetupAgentIdentity: (hostId: string, agentId: string, name: string, emoji?: string): Promise<boolean> =>
@@ -133,26 +157,26 @@ export const api = {
     invoke("remote_rollback", { hostId, snapshotId }),
   remoteWriteRawConfig: (hostId: string, content: string): Promise<boolean> =>
     invoke("remote_write_raw_config", { hostId, content }),
-  remoteAnalyzeSessions: (hostId: string): Promise<AgentSessionAnalysis[]> =>
-    invoke("remote_analyze_sessions", { hostId }),
+  remoteAnalyzeSessions: (hostId: string, sinceMtime?: number): Promise<AgentSessionAnalysis[]> =>
+    invoke("remote_analyze_sessions", { hostId, sinceMtime }),
   remoteDeleteSessionsByIds: (hostId: string, agentId: string, sessionIds: string[]): Promise<number> =>
     invoke("remote_delete_sessions_by_ids", { hostId, agentId, sessionIds }),
   remoteListSessionFiles: (hostId: string): Promise<SessionFile[]> =>
     invoke("remote_list_session_files", { hostId }),
   remoteClearAllSessions: (hostId: string): Promise<number> =>
     invoke("remote_clear_all_sessions", { hostId }),
-  remotePreviewSession: (hostId: string, agentId: string, sessionId: string): Promise<{ role: string; content: string }[]> =>
-    invoke("remote_preview_session", { hostId, agentId, sessionId }),
+  remotePreviewSession: (hostId: string, agentId: string, sessionId: string, offset?: number, limit?: number): Promise<SessionPreviewPage> =>
+    invoke("remote_preview_session", { hostId, agentId, sessionId, offset, limit }),
   remoteListModelProfiles: (hostId: string): Promise<ModelProfile[]> =>
     invoke("remote_list_model_profiles", { hostId }),
   remoteUpsertModelProfile: (hostId: string, profile: ModelProfile): Promise<ModelProfile> =>
     invoke("remote_upsert_model_profile", { hostId, profile }),
-  remoteDeleteModelProfile: (hostId: string, profileId: string): Promise<boolean> =>
-    invoke("remote_delete_model_profile", { hostId, profileId }),
+  remoteDeleteModelProfile: (hostId: string, profileId: string, force?: boolean): Promise<boolean> =>
+    invoke("remote_delete_model_profile", { hostId, profileId, force }),
   remoteResolveApiKeys: (hostId: string): Promise<ResolvedApiKey[]> =>
     invoke("remote_resolve_api_keys", { hostId }),
-  remoteExtractModelProfilesFromConfig: (hostId: string): Promise<{ created: number; reused: number; skippedInvalid: number }> =>
-    invoke("remote_extract_model_profiles_from_config", { hostId }),
+  remoteExtractModelProfilesFromConfig: (hostId: string, approved?: string[]): Promise<{ created: number; reused: number; skippedInvalid: number }> =>
+    invoke("remote_extract_model_profiles_from_config", { hostId, approved }),
   remoteRefreshModelCatalog: (hostId: string): Promise<ModelCatalogProvider[]> =>
     invoke("remote_refresh_model_catalog", { hostId }),
   remoteChatViaOpenclaw: (hostId: string, agentId: string, message: string, sessionId?: string): Promise<Record<string, unknown>> =>
@@ -160,12 +184,15 @@ export const api = {
   remoteCheckOpenclawUpdate: (hostId: string): Promise<{ upgradeAvailable: boolean; latestVersion: string | null; installedVersion: string }> =>
     invoke("remote_check_openclaw_update", { hostId }),
   // Remote backup
-  remoteBackupBeforeUpgrade: (hostId: string): Promise<BackupInfo> =>
-    invoke("remote_backup_before_upgrade", { hostId }),
+  remoteVerifyBackup: (hostId: string, backupName: string): Promise<BackupVerification> =>
+    invoke("remote_verify_backup", { hostId, backupName }),
+  // `format` mirrors the local signature for `dispatch`; remote backups are always directories.
+  remoteBackupBeforeUpgrade: (hostId: string, _format?: "dir" | "targz", maxBackups?: number): Promise<BackupInfo> =>
+    invoke("remote_backup_before_upgrade", { hostId, maxBackups }),
   remoteListBackups: (hostId: string): Promise<BackupInfo[]> =>
     invoke("remote_list_backups", { hostId }),
-  remoteRestoreFromBackup: (hostId: string, backupName: string): Promise<string> =>
-    invoke("remote_restore_from_backup", { hostId, backupName }),
+  remoteRestoreFromBackup: (hostId: string, backupName: string, components: string[] = []): Promise<string> =>
+    invoke("remote_restore_from_backup", { hostId, backupName, components }),
   remoteDeleteBackup: (hostId: string, backupName: string): Promise<boolean> =>
     invoke("remote_delete_backup", { hostId, backupName }),
 
@@ -284,20 +311,20 @@ export const api = {
     invoke("doctor_bridge_node_id"),
 
   // Logs
-  readAppLog: (lines?: number): Promise<string> =>
-    invoke("read_app_log", { lines }),
-  readErrorLog: (lines?: number): Promise<string> =>
-    invoke("read_error_log", { lines }),
-  readGatewayLog: (lines?: number): Promise<string> =>
-    invoke("read_gateway_log", { lines }),
-  readGatewayErrorLog: (lines?: number): Promise<string> =>
-    invoke("read_gateway_error_log", { lines }),
-  remoteReadAppLog: (hostId: string, lines?: number): Promise<string> =>
-    invoke("remote_read_app_log", { hostId, lines }),
-  remoteReadErrorLog: (hostId: string, lines?: number): Promise<string> =>
-    invoke("remote_read_error_log", { hostId, lines }),
-  remoteReadGatewayLog: (hostId: string, lines?: number): Promise<string> =>
-    invoke("remote_read_gateway_log", { hostId, lines }),
-  remoteReadGatewayErrorLog: (hostId: string, lines?: number): Promise<string> =>
-    invoke("remote_read_gateway_error_log", { hostId, lines }),
+  readAppLog: (lines?: number, minLevel?: string): Promise<string> =>
+    invoke("read_app_log", { lines, minLevel }),
+  readErrorLog: (lines?: number, minLevel?: string): Promise<string> =>
+    invoke("read_error_log", { lines, minLevel }),
+  readGatewayLog: (lines?: number, minLevel?: string): Promise<string> =>
+    invoke("read_gateway_log", { lines, minLevel }),
+  readGatewayErrorLog: (lines?: number, minLevel?: string): Promise<string> =>
+    invoke("read_gateway_error_log", { lines, minLevel }),
+  remoteReadAppLog: (hostId: string, lines?: number, minLevel?: string): Promise<string> =>
+    invoke("remote_read_app_log", { hostId, lines, minLevel }),
+  remoteReadErrorLog: (hostId: string, lines?: number, minLevel?: string): Promise<string> =>
+    invoke("remote_read_error_log", { hostId, lines, minLevel }),
+  remoteReadGatewayLog: (hostId: string, lines?: number, minLevel?: string): Promise<string> =>
+    invoke("remote_read_gateway_log", { hostId, lines, minLevel }),
+  remoteReadGatewayErrorLog: (hostId: string, lines?: number, minLevel?: string): Promise<string> =>
+    invoke("remote_read_gateway_error_log", { hostId, lines, minLevel }),
 };
@@ -18,6 +18,12 @@ export interface DiscordGuildChannel {
   channelName: string;
 }
 
+export interface TelegramChannel {
+  accountId: string | null;
+  chatId: string;
+  chatName: string;
+}
+
 export interface RecipeParam {
   id: string;
   label: string;
@@ -135,9 +141,17 @@ export interface SessionAnalysis {
   lastActivity: string | null;
   ageDays: number;
   totalTokens: number;
+  tokensEstimated: boolean;
   model: string | null;
   category: "empty" | "low_value" | "valuable";
   kind: string;
+  mtime: number;
+}
+
+export interface SessionPreviewPage {
+  messages: { role: string; content: string }[];
+  totalMessages: number;
+  offset: number;
 }
 
 export interface AgentSessionAnalysis {
@@ -160,11 +174,28 @@ export interface ModelProfile {
   baseUrl?: string;
   description?: string;
   enabled: boolean;
+  favorite?: boolean;
+}
+
+export interface ModelBinding {
+  scope: "global" | "agent" | "channel";
+  scopeId: string;
+  modelProfileId: string | null;
+  modelValue: string | null;
+  path: string | null;
+}
+
+export interface ModelProfileMergeResult {
+  removed: number;
+  rewrittenRefs: number;
 }
 
 export interface ModelCatalogModel {
   id: string;
   name?: string;
+  contextWindow?: number;
+  inputPrice?: number;
+  outputPrice?: number;
 }
 
 export interface ModelCatalogProvider {
@@ -191,6 +222,7 @@ export interface HistoryItem {
   source: string;
   canRollback: boolean;
   rollbackOf?: string;
+  label?: string;
 }
 
 export interface DoctorIssue {
@@ -216,6 +248,7 @@ export interface AgentOverview {
   channels: string[];
   online: boolean;
   workspace?: string;
+  lastActive?: string;
 }
 
 export interface InstanceStatus {
@@ -235,11 +268,25 @@ export interface Binding {
   match: { channel: string; peer?: { id: string; kind: string } };
 }
 
+export interface ConfigFinding {
+  path: string;
+  severity: "error" | "warn";
+  message: string;
+}
+
 export interface BackupInfo {
   name: string;
   path: string;
   createdAt: string;
   sizeBytes: number;
+  pruned?: string[];
+}
+
+export interface BackupVerification {
+  configValid: boolean;
+  hasAgents: boolean;
+  hasMemory: boolean;
+  totalBytes: number;
 }
 
 export interface SshHost {
@@ -251,6 +298,15 @@ export interface SshHost {
   authMethod: "key" | "ssh_config" | "password";
   keyPath?: string;
   password?: string;
+  hostFingerprint?: string;
+  jumpHost?: string;
+  strictHostKey?: boolean;
+}
+
+export interface OpenclawCommandOutput {
+  stdout: string;
+  stderr: string;
+  exitCode: number;
 }
 
 export interface SshExecResult {
@@ -332,11 +388,20 @@ export interface PendingCommand {
   createdAt: string;
 }
 
+export interface QueuedCommandEffect {
+  id: string;
+  label: string;
+  changedPaths: string[];
+  detail: string;
+}
+
 export interface PreviewQueueResult {
   commands: PendingCommand[];
   configBefore: string;
   configAfter: string;
   errors: string[];
+  diff: string;
+  effects: QueuedCommandEffect[];
 }
 
 // Doctor Agent